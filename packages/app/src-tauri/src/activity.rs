@@ -0,0 +1,415 @@
+// ── Activity log rotation and paging ─────────────────────────────
+//
+// activity.json stays the live tail that the MCP server appends to (the app
+// journals its own events, see journal.rs). Once it holds more than
+// ROTATE_ABOVE events, all but the newest KEEP_LIVE move into
+// activity/<YYYY-MM-DD>.json, one file per UTC day, and activity/index.json
+// records each segment's size, time span and per-type counts. Paging walks
+// the live tail and then the segments newest first, skipping days that can't
+// contain a match, so a page costs the same after a year as after a week.
+//
+// Segments are written before the tail is trimmed: a crash in between leaves
+// an event in both places, and both readers and the next rotation skip ids
+// they have already seen.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+
+use serde::Serialize;
+
+use crate::journal;
+use crate::model::{ActivityEvent, ActivityFile, ActivityIndex, ActivitySegment};
+
+const LIVE_FILE: &str = "activity.json";
+const SEGMENT_DIR: &str = "activity";
+const INDEX_FILE: &str = "activity/index.json";
+const ROTATE_ABOVE: usize = 300;
+/// ActivityStore.getRecent reads the newest 200 by default.
+const KEEP_LIVE: usize = 200;
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+/// The Activity tab grows one page instead of stitching cursors together,
+/// so this bounds how far back it can scroll.
+const MAX_PAGE_SIZE: usize = 2000;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityPage {
+    /// Newest first.
+    pub activities: Vec<ActivityEvent>,
+    /// Pass back as `cursor` for the next (older) page; absent on the last one.
+    pub next_cursor: Option<String>,
+    /// Matches across all pages.
+    pub total: usize,
+    /// Events per type ignoring the type filter, for the filter chips.
+    pub type_counts: BTreeMap<String, usize>,
+}
+
+/// Events sort by time, then id so that a cursor is unambiguous.
+fn sort_key(event: &ActivityEvent) -> (u64, &str) {
+    (crate::millis_from_iso(&event.timestamp).unwrap_or(0), event.id.as_str())
+}
+
+fn day_of(event: &ActivityEvent) -> String {
+    match crate::millis_from_iso(&event.timestamp) {
+        Some(ms) => crate::iso_from_millis(ms)[..10].to_string(),
+        None => "undated".to_string(),
+    }
+}
+
+/// Segments oldest first. Undated events sort as time 0, so their segment
+/// goes before every dated one rather than after it by name.
+fn sort_segments(segments: &mut [ActivitySegment]) {
+    segments.sort_by(|a, b| (a.file != "undated.json", &a.file).cmp(&(b.file != "undated.json", &b.file)));
+}
+
+fn segment_path(day: &str) -> String {
+    format!("{}/{}.json", SEGMENT_DIR, day)
+}
+
+/// Segments and the index are rewritten whole and can be rebuilt from each
+/// other, so they skip the `.bak` generations the top-level files keep.
+fn write_plain<T: Serialize>(project_path: &str, file_name: &str, data: &T) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(data)
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+    crate::storage::write_atomic(&crate::hw_path(project_path, file_name), contents.as_bytes())
+}
+
+/// Record an event in the same shape ActivityStore.append in packages/core
+/// uses. It lands in the journal and reaches activity.json on compaction.
+pub fn append(project_path: &str, event_type: &str, description: String, details: String) -> Result<(), String> {
+    let event = ActivityEvent {
+        id: crate::generate_id("act"),
+        event_type: event_type.to_string(),
+        description,
+        details,
+        session_id: None,
+        timestamp: crate::utc_now_iso(),
+        extra: Default::default(),
+    };
+    journal::append::<ActivityFile>(project_path, LIVE_FILE, &event)
+}
+
+/// Move older events out of activity.json if it has grown past
+/// ROTATE_ABOVE. Returns how many moved; 0 leaves the file untouched.
+pub fn rotate(project_path: &str) -> Result<usize, String> {
+    journal::compact::<ActivityFile>(project_path, LIVE_FILE)?;
+    // Cheap unlocked check first, since this runs on every change to the file
+    let live: ActivityFile = crate::read_versioned_or_default(project_path, LIVE_FILE)?.data;
+    if live.activities.len() <= ROTATE_ABOVE {
+        return Ok(0);
+    }
+    crate::update_document(project_path, LIVE_FILE, None, |live: &mut ActivityFile| {
+        let split = live.activities.len().saturating_sub(KEEP_LIVE);
+        let older: Vec<ActivityEvent> = live.activities.drain(..split).collect();
+        append_to_segments(project_path, older)
+    })
+}
+
+fn append_to_segments(project_path: &str, events: Vec<ActivityEvent>) -> Result<usize, String> {
+    let moved = events.len();
+    let mut by_day: BTreeMap<String, Vec<ActivityEvent>> = BTreeMap::new();
+    for event in events {
+        by_day.entry(day_of(&event)).or_default().push(event);
+    }
+
+    let dir = crate::hw_path(project_path, SEGMENT_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let mut updated = Vec::new();
+    for (day, events) in by_day {
+        let file_name = segment_path(&day);
+        let segment = crate::with_file_lock(project_path, &file_name, || {
+            let mut segment: ActivityFile = crate::read_versioned_or_default(project_path, &file_name)?.data;
+            let known: HashSet<String> = segment.activities.iter().map(|e| e.id.clone()).collect();
+            segment.activities.extend(events.into_iter().filter(|e| !known.contains(&e.id)));
+            segment.activities.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+            write_plain(project_path, &file_name, &segment)?;
+            Ok(segment)
+        })?;
+        updated.push(describe(&format!("{}.json", day), &segment));
+    }
+
+    crate::with_file_lock(project_path, INDEX_FILE, || {
+        let mut index = load_index(project_path)?;
+        for info in updated {
+            index.segments.retain(|s| s.file != info.file);
+            index.segments.push(info);
+        }
+        sort_segments(&mut index.segments);
+        write_plain(project_path, INDEX_FILE, &index)
+    })?;
+    Ok(moved)
+}
+
+fn describe(file: &str, segment: &ActivityFile) -> ActivitySegment {
+    let mut type_counts = BTreeMap::new();
+    for event in &segment.activities {
+        *type_counts.entry(event.event_type.clone()).or_insert(0) += 1;
+    }
+    ActivitySegment {
+        file: file.to_string(),
+        count: segment.activities.len(),
+        oldest: segment.activities.first().map(|e| e.timestamp.clone()).unwrap_or_default(),
+        newest: segment.activities.last().map(|e| e.timestamp.clone()).unwrap_or_default(),
+        type_counts,
+    }
+}
+
+/// The index, rebuilt from the segment files if it's missing.
+fn load_index(project_path: &str) -> Result<ActivityIndex, String> {
+    if crate::hw_path(project_path, INDEX_FILE).exists() {
+        // Older builds sorted the undated segment last
+        let mut index: ActivityIndex = crate::read_document(project_path, INDEX_FILE)?;
+        sort_segments(&mut index.segments);
+        return Ok(index);
+    }
+    let dir = crate::hw_path(project_path, SEGMENT_DIR);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(ActivityIndex::default());
+    };
+    let mut index = ActivityIndex::default();
+    for entry in entries.flatten() {
+        let file = entry.file_name().to_string_lossy().to_string();
+        if !file.ends_with(".json") || file == "index.json" {
+            continue;
+        }
+        let segment: ActivityFile = crate::read_document(project_path, &format!("{}/{}", SEGMENT_DIR, file))?;
+        index.segments.push(describe(&file, &segment));
+    }
+    sort_segments(&mut index.segments);
+    Ok(index)
+}
+
+fn parse_cursor(cursor: &str) -> Result<(u64, String), String> {
+    cursor.split_once(':')
+        .and_then(|(ms, id)| Some((ms.parse().ok()?, id.to_string())))
+        .ok_or_else(|| format!("Invalid activity cursor: {}", cursor))
+}
+
+/// Events per type across the tail and every segment, each id once. Only
+/// tail events from a day that already has a segment can be counted twice,
+/// so at most the segments for those days are read.
+fn count_types(project_path: &str, live: &ActivityFile, index: &ActivityIndex) -> Result<BTreeMap<String, usize>, String> {
+    let mut type_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for segment in &index.segments {
+        for (event_type, n) in &segment.type_counts {
+            *type_counts.entry(event_type.clone()).or_insert(0) += n;
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut maybe_rotated: BTreeMap<String, Vec<&ActivityEvent>> = BTreeMap::new();
+    for event in live.activities.iter().filter(|e| seen.insert(e.id.as_str())) {
+        let file = format!("{}.json", day_of(event));
+        if index.segments.iter().any(|s| s.file == file) {
+            maybe_rotated.entry(file).or_default().push(event);
+        } else {
+            *type_counts.entry(event.event_type.clone()).or_insert(0) += 1;
+        }
+    }
+    for (file, events) in maybe_rotated {
+        let segment: ActivityFile = crate::read_document(project_path, &format!("{}/{}", SEGMENT_DIR, file))?;
+        let rotated: HashSet<&str> = segment.activities.iter().map(|e| e.id.as_str()).collect();
+        for event in events.into_iter().filter(|e| !rotated.contains(e.id.as_str())) {
+            *type_counts.entry(event.event_type.clone()).or_insert(0) += 1;
+        }
+    }
+    Ok(type_counts)
+}
+
+/// One page of activity, newest first, optionally limited to `types`.
+pub fn page(project_path: &str, cursor: Option<&str>, limit: Option<usize>, types: &[String]) -> Result<ActivityPage, String> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let cursor = cursor.map(parse_cursor).transpose()?;
+    let live: ActivityFile = journal::read(project_path, LIVE_FILE)?;
+    let index = load_index(project_path)?;
+
+    let type_counts = count_types(project_path, &live, &index)?;
+    let total = if types.is_empty() {
+        type_counts.values().sum()
+    } else {
+        types.iter().filter_map(|t| type_counts.get(t)).sum()
+    };
+
+    let wanted = |e: &ActivityEvent| {
+        (types.is_empty() || types.contains(&e.event_type))
+            && cursor.as_ref().is_none_or(|(ms, id)| sort_key(e) < (*ms, id.as_str()))
+    };
+    let mut seen = HashSet::new();
+    let mut found: Vec<ActivityEvent> = live.activities.into_iter()
+        .filter(|e| wanted(e) && seen.insert(e.id.clone()))
+        .collect();
+
+    for segment in index.segments.iter().rev() {
+        if !types.is_empty() && !types.iter().any(|t| segment.type_counts.contains_key(t)) {
+            continue;
+        }
+        let oldest = crate::millis_from_iso(&segment.oldest).unwrap_or(0);
+        if cursor.as_ref().is_some_and(|(ms, _)| oldest > *ms) {
+            continue;
+        }
+        // Segments are whole days, newest first: once the page is full with
+        // events newer than anything in this one, nothing older can get in
+        if found.len() > limit {
+            found.sort_by(|a, b| sort_key(b).cmp(&sort_key(a)));
+            let newest = crate::millis_from_iso(&segment.newest).unwrap_or(0);
+            if sort_key(&found[limit]).0 > newest {
+                break;
+            }
+        }
+        let data: ActivityFile = crate::read_document(project_path, &format!("{}/{}", SEGMENT_DIR, segment.file))?;
+        found.extend(data.activities.into_iter().filter(|e| wanted(e) && seen.insert(e.id.clone())));
+    }
+
+    found.sort_by(|a, b| sort_key(b).cmp(&sort_key(a)));
+    let next_cursor = if found.len() > limit {
+        found.truncate(limit);
+        found.last().map(|e| {
+            let (ms, id) = sort_key(e);
+            format!("{}:{}", ms, id)
+        })
+    } else {
+        None
+    };
+    Ok(ActivityPage { activities: found, next_cursor, total, type_counts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempProject;
+    use serde_json::json;
+
+    const DAY_START: u64 = 1_767_225_600_000; // 2026-01-01T00:00:00Z
+
+    /// `undated` events with unparsable timestamps, then `dated` events 20
+    /// minutes apart from 2026-01-01, alternating between two types.
+    fn seed(project: &TempProject, undated: usize, dated: usize) {
+        let mut activities = Vec::new();
+        for i in 0..undated {
+            activities.push(json!({ "id": format!("act_u{:03}", i), "type": "note", "description": "", "timestamp": "unknown" }));
+        }
+        for i in 0..dated {
+            activities.push(json!({
+                "id": format!("act_{:03}", i),
+                "type": if i % 2 == 0 { "task_created" } else { "note" },
+                "description": format!("event {}", i),
+                "timestamp": crate::iso_from_millis(DAY_START + i as u64 * 20 * 60_000),
+            }));
+        }
+        project.write(LIVE_FILE, &json!({ "activities": activities }).to_string());
+    }
+
+    fn all_pages(project: &TempProject, limit: usize, types: &[String]) -> Vec<ActivityEvent> {
+        let mut events = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = page(project.path(), cursor.as_deref(), Some(limit), types).unwrap();
+            assert!(page.activities.len() <= limit);
+            events.extend(page.activities);
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return events,
+            }
+        }
+    }
+
+    #[test]
+    fn rotation_moves_older_events_into_day_segments() {
+        let project = TempProject::new("activity-rotate");
+        seed(&project, 0, ROTATE_ABOVE);
+        assert_eq!(rotate(project.path()).unwrap(), 0);
+
+        seed(&project, 0, 350);
+        assert_eq!(rotate(project.path()).unwrap(), 150);
+        let live: ActivityFile = crate::read_document(project.path(), LIVE_FILE).unwrap();
+        assert_eq!(live.activities.len(), KEEP_LIVE);
+        assert_eq!(live.activities[0].id, "act_150");
+
+        // 72 events a day: the moved ones span the first three days
+        let index = load_index(project.path()).unwrap();
+        let files: Vec<&str> = index.segments.iter().map(|s| s.file.as_str()).collect();
+        assert_eq!(files, ["2026-01-01.json", "2026-01-02.json", "2026-01-03.json"]);
+        assert_eq!(index.segments.iter().map(|s| s.count).sum::<usize>(), 150);
+        assert_eq!(index.segments[0].type_counts["task_created"], 36);
+
+        // A missing index is rebuilt from the segments
+        fs::remove_file(crate::hw_path(project.path(), INDEX_FILE)).unwrap();
+        assert_eq!(load_index(project.path()).unwrap().segments.len(), 3);
+    }
+
+    #[test]
+    fn cursors_walk_every_event_newest_first() {
+        let project = TempProject::new("activity-paging");
+        seed(&project, 5, 350);
+        rotate(project.path()).unwrap();
+        assert!(crate::hw_path(project.path(), "activity/undated.json").exists());
+
+        let events = all_pages(&project, 40, &[]);
+        assert_eq!(events.len(), 355);
+        assert!(events.windows(2).all(|w| sort_key(&w[0]) > sort_key(&w[1])));
+        assert_eq!(events[0].id, "act_349");
+        assert_eq!(events[349].id, "act_000");
+        assert!(events[350..].iter().all(|e| e.id.starts_with("act_u")));
+        assert_eq!(page(project.path(), None, None, &[]).unwrap().total, 355);
+    }
+
+    #[test]
+    fn undated_segment_does_not_hide_dated_ones() {
+        let project = TempProject::new("activity-undated");
+        // The tail is in append order, not time order: the events rotated
+        // out (written by a clock running ahead) are newer than those kept
+        let mut activities = Vec::new();
+        for i in 0..5 {
+            activities.push(json!({ "id": format!("act_u{}", i), "type": "note", "description": "", "timestamp": "unknown" }));
+        }
+        for i in 0..345u64 {
+            let offset = if i < 145 { 10_000 + i } else { i };
+            activities.push(json!({
+                "id": format!("act_{:03}", i), "type": "note", "description": "",
+                "timestamp": crate::iso_from_millis(DAY_START + offset * 60_000),
+            }));
+        }
+        project.write(LIVE_FILE, &json!({ "activities": activities }).to_string());
+        rotate(project.path()).unwrap();
+
+        let first = page(project.path(), None, Some(40), &[]).unwrap();
+        assert_eq!(first.activities[0].id, "act_144");
+        assert_eq!(all_pages(&project, 40, &[]).len(), 350);
+    }
+
+    #[test]
+    fn type_filter_skips_other_events() {
+        let project = TempProject::new("activity-types");
+        seed(&project, 0, 350);
+        rotate(project.path()).unwrap();
+
+        let types = vec!["task_created".to_string()];
+        let events = all_pages(&project, 30, &types);
+        assert_eq!(events.len(), 175);
+        assert!(events.iter().all(|e| e.event_type == "task_created"));
+        let first = page(project.path(), None, Some(30), &types).unwrap();
+        assert_eq!(first.total, 175);
+        assert_eq!(first.type_counts["note"], 175);
+    }
+
+    #[test]
+    fn events_in_both_tail_and_segment_show_once() {
+        let project = TempProject::new("activity-crash");
+        seed(&project, 0, 350);
+        rotate(project.path()).unwrap();
+        // A crash after writing the segment but before trimming the tail
+        let mut live: ActivityFile = crate::read_document(project.path(), LIVE_FILE).unwrap();
+        let segment: ActivityFile = crate::read_document(project.path(), "activity/2026-01-03.json").unwrap();
+        live.activities.insert(0, segment.activities.last().unwrap().clone());
+        project.write(LIVE_FILE, &serde_json::to_string(&live).unwrap());
+
+        let events = all_pages(&project, 50, &[]);
+        assert_eq!(events.len(), 350);
+        let first = page(project.path(), None, None, &[]).unwrap();
+        assert_eq!(first.total, 350);
+        assert_eq!((first.type_counts["note"], first.type_counts["task_created"]), (175, 175));
+        assert_eq!(page(project.path(), None, None, &["note".to_string()]).unwrap().total, 175);
+        assert!(page(project.path(), Some("nonsense"), None, &[]).is_err());
+    }
+}
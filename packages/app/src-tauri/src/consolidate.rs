@@ -0,0 +1,354 @@
+// ── Memory consolidation ─────────────────────────────────────────
+//
+// A daily pass over memories.json, run by the app while a project is open:
+//
+// 1. Decay: `synapticStrength` shrinks exponentially with the time since the
+//    memory was created, accessed or last decayed, at a quarter of the type's
+//    recall decay rate and slower for severe memories. Splitting the elapsed
+//    time across runs gives the same result as one long run, so missed or
+//    extra runs don't change the outcome.
+// 2. Archive: memories that decayed below ARCHIVE_BELOW move to
+//    memories-archive.json in the pruner's format, weakest first, never
+//    leaving fewer than MIN_ACTIVE behind.
+// 3. Suggest merges: active memories of the same type whose word sets
+//    overlap by at least DUPLICATE_SIMILARITY (Jaccard) are grouped for
+//    merge_memories. Nothing is merged automatically.
+//
+// Each run stamps STATE_FILE, which the schedule checks; the activity log
+// only gets a summary, since rotation soon moves old entries out of it.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+
+use crate::model::{ActivityFile, ArchivedMemory, ConsolidationState, MemoriesFile, Memory};
+use crate::recall;
+
+pub const ACTIVITY_TYPE: &str = "memory_consolidation";
+pub const INTERVAL_MS: u64 = 24 * 3_600_000;
+pub const STATE_FILE: &str = "consolidate.json";
+
+/// Strength decays at this fraction of the recall decay rate.
+const DECAY_SCALE: f64 = 0.25;
+const ARCHIVE_BELOW: f64 = 0.2;
+/// Same floor as the pruner's minMemoryCount.
+const MIN_ACTIVE: usize = 50;
+const DUPLICATE_SIMILARITY: f64 = 0.6;
+/// Memories with fewer distinct words than this are too short to compare.
+const MIN_WORDS: usize = 3;
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsolidationReport {
+    pub dry_run: bool,
+    pub ran_at: String,
+    /// Memories whose strength was decayed.
+    pub decayed: usize,
+    pub archived: Vec<ArchivedMemory>,
+    pub merge_suggestions: Vec<MergeSuggestion>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeSuggestion {
+    /// The strongest memory of the group.
+    pub keep_id: String,
+    pub merge_ids: Vec<String>,
+    pub titles: Vec<String>,
+    /// Weakest pairwise overlap that joined the group.
+    pub similarity: f64,
+}
+
+impl ConsolidationReport {
+    pub fn summary(&self) -> String {
+        format!(
+            "Consolidated memories: {} decayed, {} archived, {} possible duplicate groups",
+            self.decayed,
+            self.archived.len(),
+            self.merge_suggestions.len()
+        )
+    }
+
+    pub fn details(&self) -> String {
+        let mut lines = Vec::new();
+        for a in &self.archived {
+            lines.push(format!("Archived {} \"{}\": {}", a.memory.id, a.memory.title, a.reason));
+        }
+        for s in &self.merge_suggestions {
+            lines.push(format!(
+                "Possible duplicates ({:.2}): keep {}, merge {}",
+                s.similarity,
+                s.keep_id,
+                s.merge_ids.join(", ")
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+pub fn consolidate(file: &mut MemoriesFile, now_ms: u64, now_iso: &str) -> ConsolidationReport {
+    let decayed = decay(&mut file.memories, now_ms, now_iso);
+
+    let archived: Vec<ArchivedMemory> = select_for_archive(&file.memories)
+        .into_iter()
+        .map(|i| {
+            let m = &file.memories[i];
+            ArchivedMemory {
+                reason: format!("Synaptic strength {:.2} decayed below {}", m.synaptic_strength, ARCHIVE_BELOW),
+                archived_at: now_iso.to_string(),
+                score_at_archive: recall::vitality(m, now_ms),
+                memory: m.clone(),
+                extra: Default::default(),
+            }
+        })
+        .collect();
+    let archived_ids: HashSet<&str> = archived.iter().map(|a| a.memory.id.as_str()).collect();
+    file.memories.retain(|m| !archived_ids.contains(m.id.as_str()));
+
+    ConsolidationReport {
+        dry_run: false,
+        ran_at: now_iso.to_string(),
+        decayed,
+        merge_suggestions: suggest_merges(&file.memories),
+        archived,
+    }
+}
+
+/// Superseded memories are left to the pruner, which archives them anyway.
+fn decay(memories: &mut [Memory], now_ms: u64, now_iso: &str) -> usize {
+    let mut decayed = 0;
+    for m in memories.iter_mut().filter(|m| m.superseded_by.is_none()) {
+        let since = [m.last_decayed_at.as_deref(), m.last_accessed.as_deref(), Some(m.created_at.as_str())]
+            .into_iter()
+            .flatten()
+            .filter_map(crate::millis_from_iso)
+            .max();
+        let Some(since) = since.filter(|&t| t < now_ms) else { continue };
+        let days = (now_ms - since) as f64 / 86_400_000.0;
+        let rate = recall::decay_rate(m.memory_type) * DECAY_SCALE / recall::severity_weight(m.severity);
+        m.synaptic_strength *= (-rate * days).exp();
+        m.last_decayed_at = Some(now_iso.to_string());
+        decayed += 1;
+    }
+    decayed
+}
+
+fn select_for_archive(memories: &[Memory]) -> Vec<usize> {
+    let active = memories.iter().filter(|m| m.superseded_by.is_none()).count();
+    let mut weak: Vec<usize> = memories.iter()
+        .enumerate()
+        .filter(|(_, m)| m.superseded_by.is_none() && m.synaptic_strength < ARCHIVE_BELOW)
+        .map(|(i, _)| i)
+        .collect();
+    weak.sort_by(|&a, &b| memories[a].synaptic_strength.total_cmp(&memories[b].synaptic_strength));
+    weak.truncate(active.saturating_sub(MIN_ACTIVE));
+    weak
+}
+
+fn word_set(m: &Memory) -> HashSet<String> {
+    let mut words: HashSet<String> = recall::tokenize(&m.title).into_iter().collect();
+    words.extend(recall::tokenize(&m.content));
+    words.extend(recall::tokenize(&m.rule));
+    for tag in &m.tags {
+        words.extend(recall::tokenize(tag));
+    }
+    words
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let shared = a.intersection(b).count();
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Groups of active memories of one type linked by pairwise overlap, in
+/// file order.
+pub fn suggest_merges(memories: &[Memory]) -> Vec<MergeSuggestion> {
+    let active: Vec<&Memory> = memories.iter().filter(|m| m.superseded_by.is_none()).collect();
+    let words: Vec<HashSet<String>> = active.iter().map(|m| word_set(m)).collect();
+
+    let mut parent: Vec<usize> = (0..active.len()).collect();
+    let mut edges = Vec::new();
+    for i in 0..active.len() {
+        if words[i].len() < MIN_WORDS {
+            continue;
+        }
+        for j in i + 1..active.len() {
+            if words[j].len() < MIN_WORDS || active[j].memory_type != active[i].memory_type {
+                continue;
+            }
+            let similarity = jaccard(&words[i], &words[j]);
+            if similarity >= DUPLICATE_SIMILARITY {
+                edges.push((i, similarity));
+                let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut weakest: BTreeMap<usize, f64> = BTreeMap::new();
+    for (i, similarity) in edges {
+        let root = find(&mut parent, i);
+        let entry = weakest.entry(root).or_insert(1.0);
+        *entry = entry.min(similarity);
+    }
+
+    // Roots are always the earliest member, so BTreeMap order is file order
+    weakest.into_iter()
+        .map(|(root, similarity)| {
+            let members: Vec<&Memory> = (0..active.len())
+                .filter(|&i| find(&mut parent, i) == root)
+                .map(|i| active[i])
+                .collect();
+            // Strongest, then most used, then oldest
+            let keep = members.iter()
+                .copied()
+                .reduce(|best, m| {
+                    let better = m.synaptic_strength.total_cmp(&best.synaptic_strength)
+                        .then(m.access_count.cmp(&best.access_count))
+                        .is_gt();
+                    if better { m } else { best }
+                })
+                .expect("a group has at least two members");
+            MergeSuggestion {
+                keep_id: keep.id.clone(),
+                merge_ids: members.iter().filter(|m| m.id != keep.id).map(|m| m.id.clone()).collect(),
+                titles: members.iter().map(|m| m.title.clone()).collect(),
+                similarity,
+            }
+        })
+        .collect()
+}
+
+/// When the last consolidation ran.
+pub fn last_run(state: &ConsolidationState) -> Option<u64> {
+    state.last_consolidated_at.as_deref().and_then(crate::millis_from_iso)
+}
+
+/// The last consolidation still in the activity log, for projects last
+/// consolidated before STATE_FILE existed.
+pub fn last_logged_run(activity: &ActivityFile) -> Option<u64> {
+    activity.activities.iter()
+        .rev()
+        .find(|a| a.event_type == ACTIVITY_TYPE)
+        .and_then(|a| crate::millis_from_iso(&a.timestamp))
+}
+
+pub fn is_due(last_run: Option<u64>, now_ms: u64) -> bool {
+    last_run.is_none_or(|t| now_ms.saturating_sub(t) >= INTERVAL_MS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::MemoryType;
+    use serde_json::json;
+
+    const NOW: &str = "2026-03-01T00:00:00.000Z";
+
+    fn memory(id: &str, fields: serde_json::Value) -> Memory {
+        let mut m = json!({
+            "id": id, "projectId": "p", "type": "fact", "title": "", "createdAt": NOW,
+        });
+        m.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(m).unwrap()
+    }
+
+    fn now_ms() -> u64 {
+        crate::millis_from_iso(NOW).unwrap()
+    }
+
+    fn file(memories: Vec<Memory>) -> MemoriesFile {
+        MemoriesFile { memories, extra: Default::default() }
+    }
+
+    #[test]
+    fn decays_one_interval() {
+        let created = crate::iso_from_millis(now_ms() - INTERVAL_MS);
+        let mut data = file(vec![memory("m_1", json!({ "createdAt": created, "severity": "medium" }))]);
+
+        let report = consolidate(&mut data, now_ms(), NOW);
+        assert_eq!(report.decayed, 1);
+        // One day at a quarter of the fact rate, medium severity
+        let expected = (-recall::decay_rate(MemoryType::Fact) * DECAY_SCALE).exp();
+        assert!((data.memories[0].synaptic_strength - expected).abs() < 1e-12);
+        assert_eq!(data.memories[0].last_decayed_at.as_deref(), Some(NOW));
+    }
+
+    #[test]
+    fn rerunning_does_not_decay_twice() {
+        let created = crate::iso_from_millis(now_ms() - 3 * INTERVAL_MS);
+        let mut once = file(vec![memory("m_1", json!({ "createdAt": created }))]);
+        consolidate(&mut once, now_ms(), NOW);
+
+        // A run at the same moment has nothing left to decay
+        let mut twice = once.clone();
+        assert_eq!(consolidate(&mut twice, now_ms(), NOW).decayed, 0);
+        assert_eq!(twice.memories[0].synaptic_strength, once.memories[0].synaptic_strength);
+
+        // Splitting the time across runs ends where one run would
+        let mut split = file(vec![memory("m_1", json!({ "createdAt": created }))]);
+        let midway = now_ms() - INTERVAL_MS;
+        consolidate(&mut split, midway, &crate::iso_from_millis(midway));
+        consolidate(&mut split, now_ms(), NOW);
+        assert!((split.memories[0].synaptic_strength - once.memories[0].synaptic_strength).abs() < 1e-12);
+    }
+
+    #[test]
+    fn schedule_is_due_after_one_interval() {
+        assert!(is_due(None, now_ms()));
+        assert!(!is_due(Some(now_ms() - INTERVAL_MS + 1), now_ms()));
+        assert!(is_due(Some(now_ms() - INTERVAL_MS), now_ms()));
+
+        let state = ConsolidationState { last_consolidated_at: Some(NOW.to_string()), extra: Default::default() };
+        assert_eq!(last_run(&state), Some(now_ms()));
+    }
+
+    #[test]
+    fn archives_below_the_threshold_down_to_the_floor() {
+        let mut memories: Vec<Memory> = (0..MIN_ACTIVE - 1)
+            .map(|i| memory(&format!("m_{}", i), json!({})))
+            .collect();
+        memories.push(memory("m_at", json!({ "synapticStrength": ARCHIVE_BELOW })));
+        memories.push(memory("m_weak", json!({ "synapticStrength": 0.15 })));
+        memories.push(memory("m_weakest", json!({ "synapticStrength": 0.1 })));
+        let mut data = file(memories);
+
+        // Two over the floor; m_at sits exactly on the threshold and stays
+        let report = consolidate(&mut data, now_ms(), NOW);
+        let archived: Vec<&str> = report.archived.iter().map(|a| a.memory.id.as_str()).collect();
+        assert_eq!(archived, ["m_weakest", "m_weak"]);
+        assert!(data.memories.iter().any(|m| m.id == "m_at"));
+
+        // At the floor nothing more goes, however weak
+        data.memories.push(memory("m_weaker", json!({ "synapticStrength": 0.05 })));
+        data.memories.retain(|m| m.id != "m_0");
+        assert!(consolidate(&mut data, now_ms(), NOW).archived.is_empty());
+    }
+
+    #[test]
+    fn suggests_merges_within_a_type_only() {
+        let text = json!({ "title": "Deploy script", "content": "run the deploy script before every release" });
+        let mut pain = text.clone();
+        pain["type"] = json!("pain");
+        let memories = vec![
+            memory("m_a", text.clone()),
+            memory("m_b", json!({ "title": "Deploy script", "content": "run the deploy script before each release", "synapticStrength": 2.0 })),
+            memory("m_pain", pain),
+            memory("m_other", json!({ "title": "Database", "content": "postgres connection pool size" })),
+        ];
+
+        let suggestions = suggest_merges(&memories);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].keep_id, "m_b");
+        assert_eq!(suggestions[0].merge_ids, ["m_a"]);
+        assert!(suggestions[0].similarity >= DUPLICATE_SIMILARITY);
+    }
+}
@@ -0,0 +1,374 @@
+// ── Project context for new Claude sessions ──────────────────────
+//
+// Condenses .hello-world/ into a briefing that fits a token budget. Sections
+// are listed in priority order; each is made of items (one line per task,
+// note, memory...) so truncation drops whole items before cutting text.
+// Tokens are estimated at 4 characters each — close enough for English
+// prose and cheap enough to run on every PTY spawn.
+
+use serde::Serialize;
+
+use crate::model::{
+    ApprovalRequest, ContextSettings, Decision, Direction, Memory, MemorySeverity, Question,
+    QuestionStatus, Task, TaskStatus, TruncationStrategy,
+};
+
+const CHARS_PER_TOKEN: usize = 4;
+/// Longest single line before it is cut, so one essay-length memory can't
+/// crowd out a whole section.
+const ITEM_CHAR_LIMIT: usize = 400;
+const RECENT_DECISIONS: usize = 10;
+/// Largest budget that still fits on a `cmd /c` command line (8191
+/// characters) next to the rest of the PTY's arguments.
+pub const COMMAND_LINE_TOKEN_LIMIT: usize = 1500;
+
+/// Everything the builder reads, already loaded. Missing files are empty.
+#[derive(Debug, Default)]
+pub struct ContextSources {
+    pub project_name: String,
+    pub project_path: String,
+    pub phase: String,
+    pub tasks: Vec<Task>,
+    pub decisions: Vec<Decision>,
+    pub questions: Vec<Question>,
+    pub direction: Direction,
+    pub pending_approvals: Vec<ApprovalRequest>,
+    pub memories: Vec<Memory>,
+    pub handoff: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectContext {
+    pub text: String,
+    pub estimated_tokens: usize,
+    pub token_budget: usize,
+    pub truncation: TruncationStrategy,
+    pub sections: Vec<SectionSummary>,
+}
+
+/// How much of each section made it in — shown in the UI so it's obvious
+/// when the budget is too tight.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionSummary {
+    pub title: String,
+    pub included: usize,
+    pub total: usize,
+}
+
+struct Section {
+    title: &'static str,
+    items: Vec<String>,
+}
+
+/// `text` as a single `cmd /c` argument. cmd.exe ends the command at a line
+/// break and expands `%VAR%` even inside quotes, so lines are joined with
+/// spaces (every item already starts with "- ") and `"` / `%` are replaced.
+pub fn command_line_arg(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('"', "'")
+        .replace('%', " percent")
+}
+
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max.saturating_sub(1)).collect();
+    format!("{}…", cut.trim_end())
+}
+
+fn line(text: &str) -> String {
+    // Collapse multi-line fields so every item is exactly one line
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate_chars(&flat, ITEM_CHAR_LIMIT)
+}
+
+/// snake_case name of a serde enum, e.g. `TaskStatus::InProgress` → "in_progress".
+fn label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+fn sections(src: &ContextSources) -> Vec<Section> {
+    let handoff = src.handoff.iter()
+        .filter(|h| !h.trim().is_empty())
+        .map(|h| h.trim().to_string())
+        .collect();
+
+    let notes = src.direction.notes.iter()
+        .filter(|n| !n.read)
+        .map(|n| format!("- {}", line(&n.text)))
+        .collect();
+
+    let approvals = src.pending_approvals.iter()
+        .map(|a| format!("- [{}] {}: {}", label(&a.tier), a.action, line(&a.description)))
+        .collect();
+
+    let mut active: Vec<&Task> = src.tasks.iter()
+        .filter(|t| matches!(t.status, TaskStatus::InProgress | TaskStatus::Todo | TaskStatus::Blocked))
+        .collect();
+    // In progress first, then todo, then blocked; stable within each
+    active.sort_by_key(|t| match t.status {
+        TaskStatus::InProgress => 0,
+        TaskStatus::Todo => 1,
+        _ => 2,
+    });
+    let tasks = active.iter()
+        .map(|t| format!("- [{}] {}", label(&t.status), line(&t.title)))
+        .collect();
+
+    let questions = src.questions.iter()
+        .filter(|q| q.status == QuestionStatus::Open)
+        .map(|q| format!("- {}", line(&q.question)))
+        .collect();
+
+    let mut direction = Vec::new();
+    if !src.direction.vision.trim().is_empty() {
+        direction.push(format!("Vision: {}", line(&src.direction.vision)));
+    }
+    direction.extend(src.direction.scope.iter().map(|s| {
+        format!("- [{}] {} — {}", s.decision, s.area, line(&s.rationale))
+    }));
+
+    let mut critical: Vec<&Memory> = src.memories.iter()
+        .filter(|m| m.severity == MemorySeverity::High && m.superseded_by.is_none())
+        .collect();
+    critical.sort_by(|a, b| b.synaptic_strength.total_cmp(&a.synaptic_strength));
+    let memories = critical.iter()
+        .map(|m| {
+            let body = if m.rule.trim().is_empty() { &m.content } else { &m.rule };
+            format!("- {}: {}", m.title, line(body))
+        })
+        .collect();
+
+    let mut recent: Vec<&Decision> = src.decisions.iter().filter(|d| d.superseded_by.is_none()).collect();
+    recent.sort_by(|a, b| b.decided_at.cmp(&a.decided_at));
+    let decisions = recent.iter()
+        .take(RECENT_DECISIONS)
+        .map(|d| format!("- {} → {}", d.title, line(&d.chosen)))
+        .collect();
+
+    vec![
+        Section { title: "Last session handoff", items: handoff },
+        Section { title: "Unread notes from Pat", items: notes },
+        Section { title: "Pending approvals", items: approvals },
+        Section { title: "Active tasks", items: tasks },
+        Section { title: "Open questions", items: questions },
+        Section { title: "Direction", items: direction },
+        Section { title: "Critical memories", items: memories },
+        Section { title: "Recent decisions", items: decisions },
+    ]
+}
+
+fn heading(title: &str) -> String {
+    format!("\n\n{}:", title)
+}
+
+fn section_chars(section: &Section) -> usize {
+    heading(section.title).chars().count()
+        + section.items.iter().map(|i| i.chars().count() + 1).sum::<usize>()
+}
+
+/// Render as much of `section` as fits in `budget` characters. Returns the
+/// text and how many items made it in.
+fn fit_section(section: &Section, budget: usize) -> (String, usize) {
+    let head = heading(section.title);
+    let mut used = head.chars().count();
+    if section.items.is_empty() || used >= budget {
+        return (String::new(), 0);
+    }
+
+    let mut out = head;
+    let mut included = 0;
+    for item in &section.items {
+        let cost = item.chars().count() + 1;
+        if used + cost <= budget {
+            out.push('\n');
+            out.push_str(item);
+            used += cost;
+            included += 1;
+            continue;
+        }
+        // A lone oversized item (the handoff) is cut to fit rather than dropped
+        if included == 0 && budget - used > 40 {
+            out.push('\n');
+            out.push_str(&truncate_chars(item, budget - used - 1));
+            return (out, 1);
+        }
+        break;
+    }
+
+    if included == 0 {
+        return (String::new(), 0);
+    }
+    let dropped = section.items.len() - included;
+    if dropped > 0 {
+        let more = format!("\n- (+{} more)", dropped);
+        if used + more.chars().count() <= budget {
+            out.push_str(&more);
+        }
+    }
+    (out, included)
+}
+
+pub fn build(src: &ContextSources, settings: &ContextSettings) -> ProjectContext {
+    let header = format!(
+        "You are Claude, the autonomous AI CEO. Project: '{}' at {}. Workflow phase: {}.",
+        src.project_name, src.project_path, src.phase
+    );
+    let footer = "\n\nYou have access to hw_* MCP tools. Act autonomously. Report outcomes to Pat.";
+
+    let budget_chars = settings.token_budget * CHARS_PER_TOKEN;
+    let fixed = header.chars().count() + footer.chars().count();
+    let available = budget_chars.saturating_sub(fixed);
+
+    let sections = sections(src);
+
+    // Priority fills sections in order, each taking what it needs of what's
+    // left. Proportional visits the smallest first with an equal share of
+    // what's left, so room small sections don't use passes to larger ones.
+    let mut order: Vec<usize> = (0..sections.len()).collect();
+    if settings.truncation == TruncationStrategy::Proportional {
+        order.sort_by_key(|&i| section_chars(&sections[i]));
+    }
+    let mut rendered = vec![(String::new(), 0); sections.len()];
+    let mut remaining = available;
+    let mut left = sections.iter().filter(|s| !s.items.is_empty()).count();
+    for i in order {
+        if sections[i].items.is_empty() {
+            continue;
+        }
+        let grant = match settings.truncation {
+            TruncationStrategy::Priority => remaining,
+            TruncationStrategy::Proportional => remaining / left,
+        };
+        let (text, included) = fit_section(&sections[i], grant);
+        remaining -= text.chars().count();
+        left -= 1;
+        rendered[i] = (text, included);
+    }
+
+    let mut text = header;
+    let mut summaries = Vec::new();
+    for (section, (rendered, included)) in sections.iter().zip(rendered) {
+        text.push_str(&rendered);
+        if !section.items.is_empty() {
+            summaries.push(SectionSummary {
+                title: section.title.to_string(),
+                included,
+                total: section.items.len(),
+            });
+        }
+    }
+    text.push_str(footer);
+
+    ProjectContext {
+        estimated_tokens: estimate_tokens(&text),
+        text,
+        token_budget: settings.token_budget,
+        truncation: settings.truncation,
+        sections: summaries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn task(id: usize, status: &str) -> Task {
+        serde_json::from_value(json!({
+            "id": format!("t_{}", id), "title": format!("Task number {}", id), "status": status,
+            "createdAt": "2026-01-01T00:00:00.000Z", "updatedAt": "2026-01-01T00:00:00.000Z",
+        }))
+        .unwrap()
+    }
+
+    fn decision(id: usize) -> Decision {
+        serde_json::from_value(json!({
+            "id": format!("d_{}", id), "title": format!("Decision {}", id), "context": "",
+            "chosen": "the first option", "rationale": "", "decidedBy": "claude", "decidedAt": format!("2026-01-{:02}T00:00:00.000Z", id % 28 + 1),
+        }))
+        .unwrap()
+    }
+
+    fn sources(tasks: usize, decisions: usize) -> ContextSources {
+        ContextSources {
+            project_name: "demo".to_string(),
+            project_path: "C:/code/demo".to_string(),
+            phase: "build".to_string(),
+            tasks: (0..tasks).map(|i| task(i, "todo")).collect(),
+            decisions: (0..decisions).map(decision).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn settings(token_budget: usize, truncation: TruncationStrategy) -> ContextSettings {
+        ContextSettings { token_budget, truncation, ..Default::default() }
+    }
+
+    fn summary<'a>(ctx: &'a ProjectContext, title: &str) -> &'a SectionSummary {
+        ctx.sections.iter().find(|s| s.title == title).unwrap()
+    }
+
+    #[test]
+    fn everything_fits_in_a_generous_budget() {
+        let ctx = build(&sources(3, 2), &settings(2000, TruncationStrategy::Priority));
+        assert_eq!(summary(&ctx, "Active tasks").included, 3);
+        assert_eq!(summary(&ctx, "Recent decisions").included, 2);
+        assert!(ctx.text.starts_with("You are Claude"));
+        assert!(ctx.text.ends_with("Report outcomes to Pat."));
+        assert!(!ctx.text.contains("more)"));
+    }
+
+    #[test]
+    fn stays_within_budget_for_both_strategies() {
+        for strategy in [TruncationStrategy::Priority, TruncationStrategy::Proportional] {
+            let ctx = build(&sources(200, 10), &settings(300, strategy));
+            assert!(ctx.estimated_tokens <= 300, "{:?}: {}", strategy, ctx.estimated_tokens);
+        }
+    }
+
+    #[test]
+    fn priority_drops_later_sections_first() {
+        let ctx = build(&sources(200, 10), &settings(300, TruncationStrategy::Priority));
+        let tasks = summary(&ctx, "Active tasks");
+        assert!(tasks.included > 0 && tasks.included < 200);
+        assert!(ctx.text.contains(&format!("- (+{} more)", 200 - tasks.included)));
+        assert_eq!(summary(&ctx, "Recent decisions").included, 0);
+    }
+
+    #[test]
+    fn proportional_leaves_room_for_small_sections() {
+        let ctx = build(&sources(200, 3), &settings(300, TruncationStrategy::Proportional));
+        assert_eq!(summary(&ctx, "Recent decisions").included, 3);
+        assert!(summary(&ctx, "Active tasks").included > 0);
+    }
+
+    #[test]
+    fn oversized_handoff_is_cut_not_dropped() {
+        let src = ContextSources { handoff: Some("word ".repeat(2000)), ..sources(0, 0) };
+        let ctx = build(&src, &settings(200, TruncationStrategy::Priority));
+        assert_eq!(summary(&ctx, "Last session handoff").included, 1);
+        assert!(ctx.text.contains("word wo") && ctx.text.contains('…'));
+        assert!(ctx.estimated_tokens <= 200);
+    }
+
+    #[test]
+    fn command_line_arg_is_one_safe_line() {
+        let arg = command_line_arg("Active tasks:\n- [todo] Ship \"v2\" at 100%\n\n- [todo] Next");
+        assert_eq!(arg, "Active tasks: - [todo] Ship 'v2' at 100 percent - [todo] Next");
+    }
+}
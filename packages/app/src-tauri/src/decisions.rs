@@ -0,0 +1,286 @@
+// ── Decision log (decisions.json) ────────────────────────────────
+//
+// Decisions are never edited in place or deleted. An amendment snapshots the
+// current content into `history` before changing it; a reversal records a
+// new decision that `supersedes` the old one, which is then frozen with a
+// `supersededBy` link. Following those links gives the full story of how a
+// question was settled.
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+
+use crate::model::{Alternative, DecidedBy, Decision, DecisionRevision, DecisionsFile};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewDecision {
+    pub title: String,
+    pub context: String,
+    pub chosen: String,
+    #[serde(default)]
+    pub alternatives: Vec<Alternative>,
+    pub rationale: String,
+    pub decided_by: DecidedBy,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecisionPatch {
+    pub title: Option<String>,
+    pub context: Option<String>,
+    pub chosen: Option<String>,
+    pub alternatives: Option<Vec<Alternative>>,
+    pub rationale: Option<String>,
+    pub decided_by: Option<DecidedBy>,
+    pub tags: Option<Vec<String>>,
+    /// Why the decision was amended; kept with the superseded content.
+    pub reason: Option<String>,
+}
+
+fn find<'a>(file: &'a DecisionsFile, id: &str) -> Result<&'a Decision, String> {
+    file.decisions.iter().find(|d| d.id == id).ok_or_else(|| format!("Decision not found: {}", id))
+}
+
+fn find_mut<'a>(file: &'a mut DecisionsFile, id: &str) -> Result<&'a mut Decision, String> {
+    file.decisions.iter_mut().find(|d| d.id == id).ok_or_else(|| format!("Decision not found: {}", id))
+}
+
+fn validate_title(title: &str) -> Result<String, String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Decision title cannot be empty".to_string());
+    }
+    Ok(title.to_string())
+}
+
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    tags.into_iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty() && seen.insert(t.clone()))
+        .collect()
+}
+
+fn check_not_superseded(decision: &Decision) -> Result<(), String> {
+    match &decision.superseded_by {
+        Some(by) => Err(format!(
+            "Decision {} was superseded by {}; amend or supersede that one instead",
+            decision.id, by
+        )),
+        None => Ok(()),
+    }
+}
+
+fn new_decision(file: &DecisionsFile, input: NewDecision) -> Result<Decision, String> {
+    let id = loop {
+        let id = crate::generate_id("d");
+        if !file.decisions.iter().any(|d| d.id == id) {
+            break id;
+        }
+    };
+    Ok(Decision {
+        id,
+        title: validate_title(&input.title)?,
+        context: input.context,
+        chosen: input.chosen,
+        alternatives: input.alternatives,
+        rationale: input.rationale,
+        decided_at: crate::utc_now_iso(),
+        decided_by: input.decided_by,
+        tags: normalize_tags(input.tags),
+        supersedes: None,
+        superseded_by: None,
+        superseded_at: None,
+        history: Vec::new(),
+        extra: Default::default(),
+    })
+}
+
+pub fn record(file: &mut DecisionsFile, input: NewDecision) -> Result<Decision, String> {
+    let decision = new_decision(file, input)?;
+    file.decisions.push(decision.clone());
+    Ok(decision)
+}
+
+pub fn amend(file: &mut DecisionsFile, id: &str, patch: DecisionPatch) -> Result<Decision, String> {
+    let decision = find_mut(file, id)?;
+    check_not_superseded(decision)?;
+    let title = patch.title.as_deref().map(validate_title).transpose()?;
+
+    decision.history.push(DecisionRevision {
+        title: decision.title.clone(),
+        context: decision.context.clone(),
+        chosen: decision.chosen.clone(),
+        alternatives: decision.alternatives.clone(),
+        rationale: decision.rationale.clone(),
+        decided_by: decision.decided_by,
+        tags: decision.tags.clone(),
+        amended_at: crate::utc_now_iso(),
+        reason: patch.reason.filter(|r| !r.trim().is_empty()),
+        extra: Default::default(),
+    });
+
+    if let Some(title) = title {
+        decision.title = title;
+    }
+    if let Some(context) = patch.context {
+        decision.context = context;
+    }
+    if let Some(chosen) = patch.chosen {
+        decision.chosen = chosen;
+    }
+    if let Some(alternatives) = patch.alternatives {
+        decision.alternatives = alternatives;
+    }
+    if let Some(rationale) = patch.rationale {
+        decision.rationale = rationale;
+    }
+    if let Some(decided_by) = patch.decided_by {
+        decision.decided_by = decided_by;
+    }
+    if let Some(tags) = patch.tags {
+        decision.tags = normalize_tags(tags);
+    }
+    Ok(decision.clone())
+}
+
+/// Record `replacement` and freeze `id` behind it. Returns the new decision.
+pub fn supersede(file: &mut DecisionsFile, id: &str, replacement: NewDecision) -> Result<Decision, String> {
+    check_not_superseded(find(file, id)?)?;
+    let mut decision = new_decision(file, replacement)?;
+    decision.supersedes = Some(id.to_string());
+
+    let old = find_mut(file, id)?;
+    old.superseded_by = Some(decision.id.clone());
+    old.superseded_at = Some(decision.decided_at.clone());
+    file.decisions.push(decision.clone());
+    Ok(decision)
+}
+
+/// Every decision in the supersession chain containing `id`, original first.
+pub fn chain(file: &DecisionsFile, id: &str) -> Result<Vec<Decision>, String> {
+    let mut first = find(file, id)?;
+    let mut seen = HashSet::from([first.id.as_str()]);
+    while let Some(prev) = first.supersedes.as_deref().and_then(|p| find(file, p).ok()) {
+        // Hand-edited files could link in a loop
+        if !seen.insert(prev.id.as_str()) {
+            break;
+        }
+        first = prev;
+    }
+
+    let mut out = vec![first.clone()];
+    let mut seen = HashSet::from([first.id.as_str()]);
+    let mut current = first;
+    while let Some(next) = current.superseded_by.as_deref().and_then(|n| find(file, n).ok()) {
+        if !seen.insert(next.id.as_str()) {
+            break;
+        }
+        out.push(next.clone());
+        current = next;
+    }
+    Ok(out)
+}
+
+/// "What was decided about X": decisions carrying `tag` and containing every
+/// word of `text` (case-insensitive, across title, context, choice,
+/// alternatives and rationale), newest first. Superseded decisions are left
+/// out unless asked for.
+pub fn query(file: &DecisionsFile, text: Option<&str>, tag: Option<&str>, include_superseded: bool) -> Vec<Decision> {
+    let terms: Vec<String> = text.unwrap_or_default().split_whitespace().map(str::to_lowercase).collect();
+    let tag = tag.map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty());
+
+    let mut matches: Vec<Decision> = file.decisions.iter()
+        .filter(|d| include_superseded || d.superseded_by.is_none())
+        .filter(|d| tag.as_ref().is_none_or(|tag| d.tags.contains(tag)))
+        .filter(|d| {
+            if terms.is_empty() {
+                return true;
+            }
+            let mut haystack = format!("{}\n{}\n{}\n{}\n{}", d.title, d.context, d.chosen, d.rationale, d.tags.join(" "));
+            for alt in &d.alternatives {
+                haystack.push('\n');
+                haystack.push_str(&alt.option);
+                haystack.push('\n');
+                haystack.push_str(&alt.tradeoff);
+            }
+            let haystack = haystack.to_lowercase();
+            terms.iter().all(|term| haystack.contains(term.as_str()))
+        })
+        .cloned()
+        .collect();
+    matches.sort_by(|a, b| b.decided_at.cmp(&a.decided_at));
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn input(title: &str) -> NewDecision {
+        serde_json::from_value(json!({
+            "title": title, "context": "ctx", "chosen": "a", "rationale": "why", "decidedBy": "pat",
+        }))
+        .unwrap()
+    }
+
+    fn patch(value: serde_json::Value) -> DecisionPatch {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn superseded_decision_cannot_be_superseded_again() {
+        let mut file = DecisionsFile::default();
+        let first = record(&mut file, input("Use JSON")).unwrap();
+        let second = supersede(&mut file, &first.id, input("Use SQLite")).unwrap();
+
+        let err = supersede(&mut file, &first.id, input("Use Postgres")).unwrap_err();
+        assert!(err.contains(&format!("superseded by {}", second.id)), "{}", err);
+        assert_eq!(file.decisions.len(), 2);
+        assert!(amend(&mut file, &first.id, patch(json!({ "chosen": "b" }))).is_err());
+    }
+
+    #[test]
+    fn chain_runs_from_the_original_to_the_head() {
+        let mut file = DecisionsFile::default();
+        let first = record(&mut file, input("v1")).unwrap();
+        let second = supersede(&mut file, &first.id, input("v2")).unwrap();
+        let third = supersede(&mut file, &second.id, input("v3")).unwrap();
+        record(&mut file, input("unrelated")).unwrap();
+
+        let ids = |chain: Vec<Decision>| chain.into_iter().map(|d| d.id).collect::<Vec<_>>();
+        let expected = vec![first.id.clone(), second.id.clone(), third.id.clone()];
+        assert_eq!(ids(chain(&file, &first.id).unwrap()), expected);
+        assert_eq!(ids(chain(&file, &second.id).unwrap()), expected);
+        assert_eq!(third.supersedes.as_deref(), Some(second.id.as_str()));
+        assert!(query(&file, Some("v"), None, false).iter().all(|d| d.id == third.id));
+    }
+
+    #[test]
+    fn amendments_keep_history_oldest_first() {
+        let mut file = DecisionsFile::default();
+        let decision = record(&mut file, input("Cache")).unwrap();
+        amend(&mut file, &decision.id, patch(json!({ "chosen": "b", "reason": "benchmarks" }))).unwrap();
+        let amended = amend(&mut file, &decision.id, patch(json!({ "chosen": "c", "tags": ["Perf"] }))).unwrap();
+
+        assert_eq!(amended.chosen, "c");
+        assert_eq!(amended.tags, vec!["perf"]);
+        let chosen: Vec<&str> = amended.history.iter().map(|r| r.chosen.as_str()).collect();
+        assert_eq!(chosen, vec!["a", "b"]);
+        assert_eq!(amended.history[0].reason.as_deref(), Some("benchmarks"));
+        assert_eq!(amended.history[1].reason, None);
+        assert_eq!(amended.decided_at, decision.decided_at);
+    }
+
+    #[test]
+    fn superseding_an_unknown_id_is_rejected() {
+        let mut file = DecisionsFile::default();
+        let err = supersede(&mut file, "d_missing", input("Orphan")).unwrap_err();
+        assert_eq!(err, "Decision not found: d_missing");
+        assert!(file.decisions.is_empty());
+    }
+}
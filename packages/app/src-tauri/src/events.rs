@@ -0,0 +1,459 @@
+// ── Events sent to the frontend ──────────────────────────────────
+//
+// Every event the backend emits is one payload type here, tied to its name
+// by `Event::NAME`. Emit through `emit` rather than `Emitter::emit` with a
+// string, so a payload can't go out under the wrong name or shape. `emit`
+// takes any EventSink: the app handle in production, a recorder in tests.
+// Events marked STREAMED also go to every open EventStream; each notify
+// listener keeps one and serves it as `GET /events` for tools outside the app.
+//
+// packages/app/src/bindings/events.ts is generated from these definitions
+// (see the tests below) and holds the payload types, an `EventPayloads` map
+// from name to payload, and CONTRACT_VERSION. Bump CONTRACT_VERSION on any
+// change existing listeners would misread: a renamed event, a removed or
+// retyped field.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::Emitter;
+#[cfg(test)]
+use ts_rs::TS;
+
+use crate::migrate::MigrationReport;
+
+pub const CONTRACT_VERSION: u32 = 1;
+
+pub trait Event: Serialize + Clone {
+    const NAME: &'static str;
+    /// Whether `GET /events` subscribers get it too.
+    const STREAMED: bool = false;
+}
+
+/// Where emitted events go.
+pub trait EventSink: Send + Sync + 'static {
+    fn send(&self, name: &'static str, payload: Value);
+}
+
+impl EventSink for tauri::AppHandle {
+    fn send(&self, name: &'static str, payload: Value) {
+        let _ = self.emit(name, payload);
+    }
+}
+
+/// Best effort, like every emit in the app: a window that's gone just
+/// misses the event.
+pub fn emit<E: Event>(sink: &dyn EventSink, event: E) {
+    if let Ok(payload) = serde_json::to_value(event) {
+        if E::STREAMED {
+            publish(E::NAME, &payload);
+        }
+        sink.send(E::NAME, payload);
+    }
+}
+
+/// `.hello-world/` files whose contents changed, by file name.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(TS))]
+pub struct FilesChanged(pub Vec<String>);
+
+impl Event for FilesChanged {
+    const NAME: &'static str = "hw-files-changed";
+    const STREAMED: bool = true;
+}
+
+/// Posted to the notify server by the MCP server after tool calls, and by
+/// hooks with just a `type` ("typing", "awaiting", "brain_retrieval", ...).
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+#[cfg_attr(test, derive(TS))]
+pub struct ToolSummary {
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(test, ts(optional))]
+    pub kind: Option<String>,
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(test, ts(optional))]
+    pub files: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(test, ts(optional))]
+    pub events: Option<Vec<ToolEvent>>,
+}
+
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+#[cfg_attr(test, derive(TS))]
+pub struct ToolEvent {
+    pub tool: String,
+    pub summary: String,
+}
+
+impl Event for ToolSummary {
+    const NAME: &'static str = "hw-tool-summary";
+    const STREAMED: bool = true;
+}
+
+/// Streamed reply text from send_claude_message; `done` ends the reply.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(TS))]
+pub struct ChatChunk {
+    pub text: String,
+    pub done: bool,
+}
+
+impl Event for ChatChunk {
+    const NAME: &'static str = "hw-chat-chunk";
+    const STREAMED: bool = true;
+}
+
+/// Raw terminal output, base64 encoded.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(TS))]
+pub struct PtyData(pub String);
+
+impl Event for PtyData {
+    const NAME: &'static str = "pty-data";
+}
+
+/// The terminal process exited.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(TS))]
+pub struct PtyDied;
+
+impl Event for PtyDied {
+    const NAME: &'static str = "pty-died";
+}
+
+/// One line of terminal output with ANSI codes stripped, for the buddy.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(TS))]
+pub struct PtyLine(pub String);
+
+impl Event for PtyLine {
+    const NAME: &'static str = "hw-pty-line";
+    const STREAMED: bool = true;
+}
+
+/// The SQLite mirror couldn't be updated; reads use the JSON meanwhile.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(TS))]
+pub struct StorageError(pub String);
+
+impl Event for StorageError {
+    const NAME: &'static str = "hw-storage-error";
+}
+
+impl Event for MigrationReport {
+    const NAME: &'static str = "hw-migrated";
+}
+
+// ── Stream for GET /events ───────────────────────────────────────
+//
+// Streamed events are numbered from 1 per launch, and each open stream keeps
+// the last STREAM_CAPACITY it was sent, so a subscriber that reconnects with
+// Last-Event-ID gets what it missed. The numbering is shared by all streams:
+// an id a stream never saw (one from an earlier listener or launch) shows up
+// as a gap rather than as a position in the wrong sequence. Subscribers wait
+// on the stream's condvar rather than holding a channel each.
+
+const STREAM_CAPACITY: usize = 256;
+
+/// The last id handed out, and every stream that may still be served.
+struct Streams {
+    last_id: u64,
+    open: Vec<Weak<EventStream>>,
+}
+
+static STREAMS: Mutex<Streams> = Mutex::new(Streams { last_id: 0, open: Vec::new() });
+
+/// A stream that receives every streamed event from now until it is
+/// dropped.
+pub fn open_stream() -> Arc<EventStream> {
+    let Ok(mut streams) = STREAMS.lock() else {
+        return Arc::new(EventStream::new(0));
+    };
+    let stream = Arc::new(EventStream::new(streams.last_id));
+    streams.open.push(Arc::downgrade(&stream));
+    stream
+}
+
+fn publish(name: &'static str, payload: &Value) {
+    let Ok(mut streams) = STREAMS.lock() else { return };
+    streams.last_id += 1;
+    let id = streams.last_id;
+    let data = payload.to_string();
+    streams.open.retain(|stream| stream.strong_count() > 0);
+    for stream in streams.open.iter().filter_map(Weak::upgrade) {
+        stream.push(StreamEvent { id, name, data: data.clone() });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamEvent {
+    pub id: u64,
+    pub name: &'static str,
+    /// The payload as one line of JSON.
+    pub data: String,
+}
+
+/// What `EventStream::since` found.
+pub struct Since {
+    pub events: Vec<StreamEvent>,
+    /// Events after the given id were dropped from the buffer, or the id
+    /// isn't one this stream was sent.
+    pub missed: bool,
+    /// The id to resume from next time.
+    pub latest: u64,
+}
+
+pub struct EventStream {
+    state: Mutex<StreamState>,
+    published: Condvar,
+}
+
+struct StreamState {
+    /// The id of the last event sent here, or the id the stream opened at.
+    last_id: u64,
+    recent: VecDeque<StreamEvent>,
+    /// Set by `close`; subscribers stop waiting.
+    closed: bool,
+}
+
+impl EventStream {
+    fn new(opened_at: u64) -> Self {
+        EventStream {
+            state: Mutex::new(StreamState { last_id: opened_at, recent: VecDeque::new(), closed: false }),
+            published: Condvar::new(),
+        }
+    }
+
+    fn push(&self, event: StreamEvent) {
+        let Ok(mut state) = self.state.lock() else { return };
+        state.last_id = event.id;
+        if state.recent.len() == STREAM_CAPACITY {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(event);
+        self.published.notify_all();
+    }
+
+    pub fn last_id(&self) -> u64 {
+        self.state.lock().map(|s| s.last_id).unwrap_or(0)
+    }
+
+    /// Events after `after`, waiting up to `timeout` for one if there are
+    /// none yet and the stream is still open.
+    pub fn since(&self, after: u64, timeout: Duration) -> Since {
+        let Ok(state) = self.state.lock() else {
+            return Since { events: Vec::new(), missed: false, latest: after };
+        };
+        let Ok((state, _)) = self.published.wait_timeout_while(state, timeout, |s| s.last_id == after && !s.closed) else {
+            return Since { events: Vec::new(), missed: false, latest: after };
+        };
+        if after > state.last_id {
+            return Since { events: Vec::new(), missed: true, latest: state.last_id };
+        }
+        let oldest = state.recent.front().map_or(state.last_id + 1, |e| e.id);
+        Since {
+            events: state.recent.iter().filter(|e| e.id > after).cloned().collect(),
+            missed: after + 1 < oldest,
+            latest: state.last_id,
+        }
+    }
+
+    /// Wake every waiting subscriber and stop later ones from waiting, so
+    /// they notice their server stopped.
+    pub fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+        self.published.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    /// Name and TypeScript payload type of every event, in binding order.
+    fn contract() -> Vec<(&'static str, String)> {
+        vec![
+            (FilesChanged::NAME, FilesChanged::name()),
+            (ToolSummary::NAME, ToolSummary::name()),
+            (ChatChunk::NAME, ChatChunk::name()),
+            (PtyData::NAME, PtyData::name()),
+            (PtyDied::NAME, PtyDied::name()),
+            (PtyLine::NAME, PtyLine::name()),
+            (StorageError::NAME, StorageError::name()),
+            (MigrationReport::NAME, MigrationReport::name()),
+        ]
+    }
+
+    fn declarations() -> Vec<String> {
+        vec![
+            FilesChanged::decl(),
+            ToolSummary::decl(),
+            ToolEvent::decl(),
+            ChatChunk::decl(),
+            PtyData::decl(),
+            PtyDied::decl(),
+            PtyLine::decl(),
+            StorageError::decl(),
+            MigrationReport::decl(),
+            crate::migrate::AppliedMigration::decl(),
+        ]
+    }
+
+    fn render() -> String {
+        let mut out = String::from(
+            "// Generated from packages/app/src-tauri/src/events.rs. Do not edit.\n\
+             // Regenerate with HW_UPDATE_BINDINGS=1 cargo test events\n\n",
+        );
+        out.push_str(&format!("export const CONTRACT_VERSION = {};\n\n", CONTRACT_VERSION));
+        for decl in declarations() {
+            out.push_str(&format!("export {}\n\n", decl));
+        }
+        out.push_str("export type EventPayloads = {\n");
+        for (name, payload) in contract() {
+            out.push_str(&format!("  '{}': {};\n", name, payload));
+        }
+        out.push_str("};\n\nexport type EventName = keyof EventPayloads;\n");
+        out
+    }
+
+    /// packages/app/src/bindings/events.ts, found from the crate's manifest
+    /// directory so it doesn't depend on where cargo is run from.
+    fn bindings_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../src/bindings/events.ts")
+    }
+
+    #[test]
+    fn event_names_are_unique() {
+        let mut names: Vec<&str> = contract().into_iter().map(|(name, _)| name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), contract().len());
+    }
+
+    #[test]
+    fn tool_summary_keeps_hook_payloads() {
+        let hook: ToolSummary = serde_json::from_str(r#"{"type":"typing","summary":"","extra":1}"#).unwrap();
+        assert_eq!(serde_json::to_value(&hook).unwrap(), serde_json::json!({ "type": "typing", "summary": "" }));
+    }
+
+    struct Discard;
+
+    impl EventSink for Discard {
+        fn send(&self, _name: &'static str, _payload: Value) {}
+    }
+
+    /// Send `payload` to `stream` alone, as the next id.
+    fn push(stream: &EventStream, name: &'static str, payload: Value) {
+        stream.push(StreamEvent { id: stream.last_id() + 1, name, data: payload.to_string() });
+    }
+
+    #[test]
+    fn stream_replays_after_an_id() {
+        let stream = EventStream::new(0);
+        push(&stream, FilesChanged::NAME, serde_json::json!(["tasks.json"]));
+        push(&stream, PtyLine::NAME, serde_json::json!("$ ls"));
+
+        let since = stream.since(1, Duration::ZERO);
+        assert!(!since.missed);
+        assert_eq!(since.latest, 2);
+        assert_eq!(since.events, vec![StreamEvent { id: 2, name: "hw-pty-line", data: r#""$ ls""#.to_string() }]);
+        assert_eq!(stream.since(0, Duration::ZERO).events.len(), 2);
+    }
+
+    #[test]
+    fn stream_reports_gaps() {
+        let stream = EventStream::new(0);
+        for i in 0..STREAM_CAPACITY + 2 {
+            push(&stream, PtyLine::NAME, serde_json::json!(i.to_string()));
+        }
+        let since = stream.since(1, Duration::ZERO);
+        assert!(since.missed);
+        assert_eq!(since.events.len(), STREAM_CAPACITY);
+        assert_eq!(since.events[0].id, 3);
+        assert!(!stream.since(2, Duration::ZERO).missed);
+
+        // An id from an earlier launch
+        let since = stream.since(10_000, Duration::ZERO);
+        assert!(since.missed && since.events.is_empty());
+        assert_eq!(since.latest, stream.last_id());
+    }
+
+    #[test]
+    fn stream_waits_for_the_next_event() {
+        let stream = Arc::new(EventStream::new(0));
+        assert!(stream.since(0, Duration::from_millis(10)).events.is_empty());
+
+        let publisher = stream.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            push(&publisher, ChatChunk::NAME, serde_json::json!({ "text": "hi", "done": false }));
+        });
+        let since = stream.since(0, Duration::from_secs(5));
+        assert_eq!(since.events.len(), 1);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn close_wakes_waiting_subscribers() {
+        let stream = Arc::new(EventStream::new(0));
+        let closer = stream.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            closer.close();
+        });
+        let started = std::time::Instant::now();
+        assert!(stream.since(0, Duration::from_secs(30)).events.is_empty());
+        assert!(started.elapsed() < Duration::from_secs(5));
+        handle.join().unwrap();
+
+        // Later calls don't wait either
+        let started = std::time::Instant::now();
+        stream.since(0, Duration::from_secs(30));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn streams_only_hold_events_sent_while_open() {
+        let first = open_stream();
+        emit(&Discard, PtyLine("before".to_string()));
+        let second = open_stream();
+        emit(&Discard, PtyLine("after".to_string()));
+
+        let mine = |stream: &EventStream| -> Vec<String> {
+            stream.since(0, Duration::ZERO).events.into_iter()
+                .filter(|e| e.data.contains("before") || e.data.contains("after"))
+                .map(|e| e.data)
+                .collect()
+        };
+        assert_eq!(mine(&first), [r#""before""#, r#""after""#]);
+        assert_eq!(mine(&second), [r#""after""#]);
+
+        // An id from before the second stream opened is a gap there
+        let before = first.since(0, Duration::ZERO).events.iter().find(|e| e.data.contains("before")).unwrap().id;
+        assert!(second.since(before - 1, Duration::ZERO).missed);
+        assert!(!first.since(before - 1, Duration::ZERO).missed);
+    }
+
+    #[test]
+    fn typescript_bindings_are_current() {
+        let path = bindings_path();
+        let expected = render();
+        if std::env::var_os("HW_UPDATE_BINDINGS").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &expected).unwrap();
+        }
+        let actual = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            actual == expected,
+            "{} is out of date; regenerate with HW_UPDATE_BINDINGS=1 cargo test events",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,337 @@
+// ── Task dependency graph ────────────────────────────────────────
+//
+// `dependsOn` edges point from a task to the tasks it waits on. The graph is
+// rebuilt from tasks.json on every request — boards hold tens of tasks, not
+// thousands — and never rejects bad data: cycles and references to deleted
+// tasks are reported so the UI can flag them.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::model::{Task, TaskSize, TaskStatus};
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskGraph {
+    pub nodes: Vec<GraphNode>,
+    /// Todo tasks whose dependencies are all done, in topological order.
+    pub ready: Vec<String>,
+    /// Dependencies before dependents. Tasks on or behind a cycle are left out.
+    pub topological_order: Vec<String>,
+    /// Longest chain of unfinished work, first step first, weighted by size.
+    pub critical_path: Vec<String>,
+    pub critical_path_weight: u32,
+    /// Each entry is one strongly connected group of tasks, in file order.
+    pub cycles: Vec<Vec<String>>,
+    pub dangling: Vec<DanglingRef>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphNode {
+    pub id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub depends_on: Vec<String>,
+    pub dependents: Vec<String>,
+    /// Dependencies that aren't done yet, including ones that no longer exist.
+    pub blocked_by: Vec<String>,
+    pub ready: bool,
+    pub in_cycle: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DanglingRef {
+    pub task_id: String,
+    pub missing_id: String,
+}
+
+/// Rough effort per size for the critical path. Unsized tasks count as M.
+fn weight(task: &Task) -> u32 {
+    match task.size {
+        Some(TaskSize::S) => 1,
+        Some(TaskSize::M) | None => 2,
+        Some(TaskSize::L) => 3,
+        Some(TaskSize::Epic) => 5,
+    }
+}
+
+pub fn build(tasks: &[Task]) -> TaskGraph {
+    let index: HashMap<&str, usize> = tasks.iter().enumerate().map(|(i, t)| (t.id.as_str(), i)).collect();
+
+    // deps[i] = indices task i waits on; dependents[i] = indices waiting on i
+    let mut deps: Vec<Vec<usize>> = vec![Vec::new(); tasks.len()];
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); tasks.len()];
+    let mut dangling = Vec::new();
+    for (i, task) in tasks.iter().enumerate() {
+        for dep in &task.depends_on {
+            match index.get(dep.as_str()) {
+                Some(&j) if !deps[i].contains(&j) => {
+                    deps[i].push(j);
+                    dependents[j].push(i);
+                }
+                Some(_) => {}
+                None => dangling.push(DanglingRef { task_id: task.id.clone(), missing_id: dep.clone() }),
+            }
+        }
+    }
+
+    let cycles = strongly_connected(&deps)
+        .into_iter()
+        .filter(|group| group.len() > 1 || deps[group[0]].contains(&group[0]))
+        .collect::<Vec<_>>();
+    let mut in_cycle = vec![false; tasks.len()];
+    for &i in cycles.iter().flatten() {
+        in_cycle[i] = true;
+    }
+
+    let order = topological_order(&deps, &dependents);
+    let (critical_path, critical_path_weight) = critical_path(tasks, &deps, &order);
+
+    let done = |i: usize| tasks[i].status == TaskStatus::Done;
+    let mut nodes = Vec::with_capacity(tasks.len());
+    for (i, task) in tasks.iter().enumerate() {
+        let blocked_by: Vec<String> = task.depends_on.iter()
+            .filter(|dep| index.get(dep.as_str()).is_none_or(|&j| !done(j)))
+            .cloned()
+            .collect();
+        nodes.push(GraphNode {
+            id: task.id.clone(),
+            title: task.title.clone(),
+            status: task.status,
+            depends_on: deps[i].iter().map(|&j| tasks[j].id.clone()).collect(),
+            dependents: dependents[i].iter().map(|&j| tasks[j].id.clone()).collect(),
+            ready: task.status == TaskStatus::Todo && blocked_by.is_empty() && !in_cycle[i],
+            blocked_by,
+            in_cycle: in_cycle[i],
+        });
+    }
+
+    let id = |i: usize| tasks[i].id.clone();
+    TaskGraph {
+        ready: order.iter().filter(|&&i| nodes[i].ready).map(|&i| id(i)).collect(),
+        topological_order: order.iter().map(|&i| id(i)).collect(),
+        critical_path: critical_path.into_iter().map(id).collect(),
+        critical_path_weight,
+        cycles: cycles.into_iter().map(|group| group.into_iter().map(id).collect()).collect(),
+        dangling,
+        nodes,
+    }
+}
+
+/// Kahn's algorithm, taking the earliest task in file order whenever there's
+/// a choice so the order matches the board as closely as possible.
+fn topological_order(deps: &[Vec<usize>], dependents: &[Vec<usize>]) -> Vec<usize> {
+    let mut waiting: Vec<usize> = deps.iter().map(Vec::len).collect();
+    let mut available: std::collections::BTreeSet<usize> = (0..deps.len()).filter(|&i| waiting[i] == 0).collect();
+    let mut order = Vec::with_capacity(deps.len());
+    while let Some(i) = available.pop_first() {
+        order.push(i);
+        for &j in &dependents[i] {
+            waiting[j] -= 1;
+            if waiting[j] == 0 {
+                available.insert(j);
+            }
+        }
+    }
+    order
+}
+
+/// Heaviest chain of unfinished tasks. Done tasks are already paid for, so
+/// they neither add weight nor link two unfinished tasks together.
+fn critical_path(tasks: &[Task], deps: &[Vec<usize>], order: &[usize]) -> (Vec<usize>, u32) {
+    let open = |i: usize| tasks[i].status != TaskStatus::Done;
+    let mut best: Vec<Option<(u32, Option<usize>)>> = vec![None; tasks.len()];
+    for &i in order.iter().filter(|&&i| open(i)) {
+        let via = deps[i].iter()
+            .filter_map(|&j| best[j].map(|(w, _)| (w, j)))
+            .max_by_key(|&(w, _)| w);
+        best[i] = Some(match via {
+            Some((w, j)) => (w + weight(&tasks[i]), Some(j)),
+            None => (weight(&tasks[i]), None),
+        });
+    }
+
+    let Some((mut current, total)) = best.iter()
+        .enumerate()
+        .filter_map(|(i, b)| b.map(|(w, _)| (i, w)))
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+    else {
+        return (Vec::new(), 0);
+    };
+
+    let mut path = vec![current];
+    while let Some((_, Some(prev))) = best[current] {
+        path.push(prev);
+        current = prev;
+    }
+    path.reverse();
+    (path, total)
+}
+
+/// Tarjan's algorithm, with an explicit call stack so a long dependency chain
+/// can't overflow the thread's stack. Each group is sorted into file order.
+fn strongly_connected(deps: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = deps.len();
+    let mut next = 0;
+    let mut index: Vec<Option<usize>> = vec![None; n];
+    let mut low = vec![0; n];
+    let mut stack = Vec::new();
+    let mut on_stack = vec![false; n];
+    let mut groups = Vec::new();
+    // (task, next edge to follow) for each task being visited
+    let mut calls: Vec<(usize, usize)> = Vec::new();
+
+    for root in 0..n {
+        if index[root].is_some() {
+            continue;
+        }
+        calls.push((root, 0));
+        while let Some(&(v, edge)) = calls.last() {
+            if index[v].is_none() {
+                index[v] = Some(next);
+                low[v] = next;
+                next += 1;
+                stack.push(v);
+                on_stack[v] = true;
+            }
+
+            if let Some(&w) = deps[v].get(edge) {
+                calls.last_mut().expect("just read").1 += 1;
+                match index[w] {
+                    None => calls.push((w, 0)),
+                    Some(iw) if on_stack[w] => low[v] = low[v].min(iw),
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            // Every edge followed: return to the caller with our low-link
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if Some(low[v]) == index[v] {
+                let mut group = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    group.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                group.sort_unstable();
+                groups.push(group);
+            }
+        }
+    }
+    groups.sort_by_key(|g| g[0]);
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// `(id, size, status, dependsOn)`
+    fn tasks(specs: &[(&str, &str, &str, &[&str])]) -> Vec<Task> {
+        specs.iter()
+            .map(|(id, size, status, deps)| {
+                serde_json::from_value(json!({
+                    "id": id, "title": id, "size": size, "status": status, "dependsOn": deps,
+                    "createdAt": "2026-01-01T00:00:00.000Z", "updatedAt": "2026-01-01T00:00:00.000Z",
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn node<'a>(graph: &'a TaskGraph, id: &str) -> &'a GraphNode {
+        graph.nodes.iter().find(|n| n.id == id).unwrap()
+    }
+
+    #[test]
+    fn empty_board() {
+        let graph = build(&[]);
+        assert!(graph.nodes.is_empty());
+        assert!(graph.topological_order.is_empty());
+        assert!(graph.critical_path.is_empty());
+        assert_eq!(graph.critical_path_weight, 0);
+        assert!(graph.cycles.is_empty());
+    }
+
+    #[test]
+    fn diamond_orders_dependencies_first() {
+        // d waits on b and c, which both wait on a
+        let graph = build(&tasks(&[
+            ("d", "S", "todo", &["b", "c"]),
+            ("c", "L", "todo", &["a"]),
+            ("b", "S", "todo", &["a"]),
+            ("a", "M", "todo", &[]),
+        ]));
+        assert_eq!(graph.topological_order, ["a", "c", "b", "d"]);
+        assert_eq!(graph.ready, ["a"]);
+        assert!(graph.cycles.is_empty());
+        // a(2) → c(3) → d(1) beats the route through b
+        assert_eq!(graph.critical_path, ["a", "c", "d"]);
+        assert_eq!(graph.critical_path_weight, 6);
+        assert_eq!(node(&graph, "a").dependents, ["c", "b"]);
+    }
+
+    #[test]
+    fn done_tasks_unblock_and_leave_the_critical_path() {
+        let graph = build(&tasks(&[
+            ("a", "L", "done", &[]),
+            ("b", "S", "todo", &["a"]),
+            ("c", "M", "todo", &["b"]),
+        ]));
+        assert_eq!(graph.ready, ["b"]);
+        assert!(node(&graph, "b").blocked_by.is_empty());
+        assert_eq!(node(&graph, "c").blocked_by, ["b"]);
+        assert_eq!(graph.critical_path, ["b", "c"]);
+        assert_eq!(graph.critical_path_weight, 3);
+    }
+
+    #[test]
+    fn cycles_are_reported_and_excluded() {
+        let graph = build(&tasks(&[
+            ("a", "S", "todo", &["c"]),
+            ("b", "S", "todo", &["a"]),
+            ("c", "S", "todo", &["b"]),
+            ("d", "S", "todo", &["c"]),
+            ("e", "S", "todo", &["e"]),
+            ("f", "S", "todo", &[]),
+        ]));
+        assert_eq!(graph.cycles, vec![vec!["a", "b", "c"], vec!["e"]]);
+        assert!(node(&graph, "a").in_cycle && !node(&graph, "d").in_cycle);
+        // d sits behind the cycle, so it never becomes orderable
+        assert_eq!(graph.topological_order, ["f"]);
+        assert_eq!(graph.ready, ["f"]);
+    }
+
+    #[test]
+    fn dangling_references_block_without_breaking_the_graph() {
+        let graph = build(&tasks(&[
+            ("a", "S", "todo", &["t_deleted"]),
+            ("b", "S", "todo", &["a"]),
+        ]));
+        assert_eq!(graph.dangling.len(), 1);
+        assert_eq!((graph.dangling[0].task_id.as_str(), graph.dangling[0].missing_id.as_str()), ("a", "t_deleted"));
+        assert_eq!(node(&graph, "a").blocked_by, ["t_deleted"]);
+        assert!(node(&graph, "a").depends_on.is_empty());
+        assert!(graph.ready.is_empty());
+        assert_eq!(graph.topological_order, ["a", "b"]);
+    }
+
+    #[test]
+    fn long_chains_do_not_recurse() {
+        let n = 200_000;
+        let mut deps: Vec<Vec<usize>> = (0..n).map(|i| if i == 0 { vec![] } else { vec![i - 1] }).collect();
+        assert_eq!(strongly_connected(&deps).len(), n);
+        deps[0].push(n - 1);
+        assert_eq!(strongly_connected(&deps), vec![(0..n).collect::<Vec<_>>()]);
+    }
+}
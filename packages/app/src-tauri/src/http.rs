@@ -0,0 +1,447 @@
+// ── Minimal HTTP/1.1 server ──────────────────────────────────────
+//
+// Just enough HTTP for the loopback notify listener: Content-Length and
+// chunked bodies, keep-alive, and a limit on everything a client controls —
+// line and header count, body size, how long a request may take to arrive
+// and how many connections are served at once. A request that breaks a
+// limit gets a 400 or 413 and the connection is closed, since what follows
+// it can't be trusted to start a new request.
+//
+// A streaming response (server-sent events) takes its connection off the
+// pool onto a thread of its own, up to `max_streams` at once.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+/// Longest request line, header line or chunk-size line, in bytes.
+const MAX_LINE: usize = 8 * 1024;
+const MAX_HEADERS: usize = 64;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub max_body: usize,
+    /// From waiting for a request's first byte to having all of it; also
+    /// how long an idle keep-alive connection is kept.
+    pub request_timeout: Duration,
+    pub workers: usize,
+    /// Accepted connections waiting for a worker. Past this they get a 503.
+    pub queue: usize,
+    pub max_requests_per_connection: usize,
+    /// Open streaming responses. Past this they get a 503.
+    pub max_streams: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_body: 1024 * 1024,
+            request_timeout: Duration::from_secs(10),
+            workers: 4,
+            queue: 32,
+            max_requests_per_connection: 100,
+            max_streams: 8,
+        }
+    }
+}
+
+pub type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// Writes a streaming response's body until it returns; the connection is
+/// closed after.
+pub type StreamBody = Box<dyn FnOnce(&mut dyn Write) -> io::Result<()> + Send>;
+
+/// Serve `listener` on a pool of `limits.workers` threads. The accept loop
+/// ends at the first connection after `stop` is set; the workers once the
+/// queue drains.
+pub fn start(listener: TcpListener, limits: Limits, stop: Arc<AtomicBool>, handler: Arc<Handler>) {
+    let (queue, connections) = mpsc::sync_channel::<TcpStream>(limits.queue);
+    let connections = Arc::new(Mutex::new(connections));
+    let streams = Arc::new(AtomicUsize::new(0));
+
+    for _ in 0..limits.workers {
+        let connections = connections.clone();
+        let handler = handler.clone();
+        let streams = streams.clone();
+        std::thread::spawn(move || loop {
+            // The lock is held while waiting for a connection, not while serving it
+            let next = match connections.lock() {
+                Ok(connections) => connections.recv(),
+                Err(_) => return,
+            };
+            let Ok(stream) = next else { return };
+            serve_connection(stream, &limits, &*handler, &streams);
+        });
+    }
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            if stop.load(Ordering::SeqCst) { break; }
+            let Ok(stream) = stream else { continue };
+            if let Err(TrySendError::Full(mut stream)) = queue.try_send(stream) {
+                let _ = stream.set_write_timeout(Some(limits.request_timeout));
+                let _ = stream.write_all(&Response::error(503, "Too many connections").to_bytes(false));
+            }
+        }
+    });
+}
+
+fn serve_connection(stream: TcpStream, limits: &Limits, handler: &Handler, streams: &Arc<AtomicUsize>) {
+    let _ = stream.set_write_timeout(Some(limits.request_timeout));
+    let Ok(mut writer) = stream.try_clone() else { return };
+    let mut reader = BufReader::new(DeadlineReader { stream, deadline: Instant::now() });
+
+    for served in 1..=limits.max_requests_per_connection {
+        reader.get_mut().deadline = Instant::now() + limits.request_timeout;
+        let (mut response, keep_alive) = match read_request(&mut reader, limits.max_body) {
+            Ok(request) => (handler(&request), request.keep_alive && served < limits.max_requests_per_connection),
+            Err(ReadError::Closed) => return,
+            Err(ReadError::Bad(status, message)) => (Response::error(status, &message), false),
+        };
+        if let Some(body) = response.stream.take() {
+            if streams.fetch_add(1, Ordering::SeqCst) >= limits.max_streams {
+                streams.fetch_sub(1, Ordering::SeqCst);
+                let _ = writer.write_all(&Response::error(503, "Too many open streams").to_bytes(false));
+                return;
+            }
+            let streams = streams.clone();
+            std::thread::spawn(move || {
+                if writer.write_all(response.head("Connection: close\r\n").as_bytes()).is_ok() {
+                    let _ = writer.flush();
+                    let _ = body(&mut writer);
+                }
+                streams.fetch_sub(1, Ordering::SeqCst);
+            });
+            return;
+        }
+        if writer.write_all(&response.to_bytes(keep_alive)).is_err() || !keep_alive {
+            return;
+        }
+    }
+}
+
+/// Reads from the socket until `deadline`, however slowly the client
+/// trickles bytes in.
+struct DeadlineReader {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "request timed out"));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+// ── Requests ─────────────────────────────────────────────────────
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    keep_alive: bool,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReadError {
+    /// Hung up, timed out or failed: nothing worth answering.
+    Closed,
+    /// Answer with this status, then close.
+    Bad(u16, String),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(_: io::Error) -> Self {
+        ReadError::Closed
+    }
+}
+
+fn bad(message: impl Into<String>) -> ReadError {
+    ReadError::Bad(400, message.into())
+}
+
+fn too_large(max_body: usize) -> ReadError {
+    ReadError::Bad(413, format!("Request body over {} bytes", max_body))
+}
+
+pub fn read_request(reader: &mut impl BufRead, max_body: usize) -> Result<Request, ReadError> {
+    // Request line: "POST /path HTTP/1.1"
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path), Some(version), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(bad(format!("Malformed request line: {}", request_line)));
+    };
+    if version != "HTTP/1.1" && version != "HTTP/1.0" {
+        return Err(bad(format!("Unsupported HTTP version: {}", version)));
+    }
+    let method = method.to_uppercase();
+    let path = path.to_string();
+
+    // Headers until the blank line
+    let mut headers: Vec<(String, String)> = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() { break; }
+        if headers.len() == MAX_HEADERS {
+            return Err(bad("Too many headers"));
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(bad(format!("Malformed header: {}", line)));
+        };
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+    let values = |name: &'static str| headers.iter().filter(move |(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str());
+
+    let chunked = match values("transfer-encoding").collect::<Vec<_>>()[..] {
+        [] => false,
+        [encoding] if encoding.eq_ignore_ascii_case("chunked") => true,
+        ref other => return Err(bad(format!("Unsupported Transfer-Encoding: {}", other.join(", ")))),
+    };
+    let content_length = match values("content-length").collect::<Vec<_>>()[..] {
+        [] => None,
+        [length] => Some(length.parse::<usize>().map_err(|_| bad(format!("Invalid Content-Length: {}", length)))?),
+        _ => return Err(bad("Multiple Content-Length headers")),
+    };
+    // Either could frame the body, so a request with both is ambiguous
+    if chunked && content_length.is_some() {
+        return Err(bad("Both Content-Length and Transfer-Encoding"));
+    }
+
+    let body = if chunked {
+        read_chunked(reader, max_body)?
+    } else {
+        let length = content_length.unwrap_or(0);
+        if length > max_body {
+            return Err(too_large(max_body));
+        }
+        let mut body = vec![0u8; length];
+        reader.read_exact(&mut body)?;
+        body
+    };
+
+    // HTTP/1.1 keeps the connection unless told otherwise; 1.0 only if asked
+    let connection = |token: &str| values("connection").any(|v| v.split(',').any(|t| t.trim().eq_ignore_ascii_case(token)));
+    let keep_alive = if version == "HTTP/1.1" { !connection("close") } else { connection("keep-alive") };
+
+    Ok(Request { method, path, headers, body, keep_alive })
+}
+
+/// One line without its CRLF (or bare LF).
+fn read_line(reader: &mut impl BufRead) -> Result<String, ReadError> {
+    let mut line = Vec::new();
+    reader.by_ref().take(MAX_LINE as u64 + 2).read_until(b'\n', &mut line)?;
+    if line.last() != Some(&b'\n') {
+        // Either the limit cut the line off or the client hung up mid-line
+        return Err(if line.len() > MAX_LINE { bad("Line too long") } else { ReadError::Closed });
+    }
+    line.pop();
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    String::from_utf8(line).map_err(|_| bad("Request is not valid UTF-8"))
+}
+
+fn read_chunked(reader: &mut impl BufRead, max_body: usize) -> Result<Vec<u8>, ReadError> {
+    let mut body = Vec::new();
+    loop {
+        // "<hex size>[;extension]", then that many bytes and a CRLF
+        let line = read_line(reader)?;
+        let size = line.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size, 16).map_err(|_| bad(format!("Invalid chunk size: {}", size)))?;
+        if size == 0 { break; }
+        if size > max_body - body.len() {
+            return Err(too_large(max_body));
+        }
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..])?;
+        if !read_line(reader)?.is_empty() {
+            return Err(bad("Chunk longer than its size"));
+        }
+    }
+    // Trailers are allowed and ignored
+    for _ in 0..=MAX_HEADERS {
+        if read_line(reader)?.is_empty() {
+            return Ok(body);
+        }
+    }
+    Err(bad("Too many trailers"))
+}
+
+// ── Responses ────────────────────────────────────────────────────
+
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+    stream: Option<StreamBody>,
+}
+
+impl Response {
+    pub fn empty(status: u16) -> Self {
+        Response { status, headers: Vec::new(), body: Vec::new(), stream: None }
+    }
+
+    pub fn json(status: u16, body: &Value) -> Self {
+        Response {
+            status,
+            headers: vec![("Content-Type", "application/json".to_string())],
+            body: body.to_string().into_bytes(),
+            stream: None,
+        }
+    }
+
+    /// A body written as it's produced, until `body` returns.
+    pub fn stream(status: u16, body: impl FnOnce(&mut dyn Write) -> io::Result<()> + Send + 'static) -> Self {
+        Response { status, headers: Vec::new(), body: Vec::new(), stream: Some(Box::new(body)) }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Response::json(status, &json!({ "error": message }))
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    pub fn to_bytes(&self, keep_alive: bool) -> Vec<u8> {
+        let framing = format!(
+            "Content-Length: {}\r\nConnection: {}\r\n",
+            self.body.len(),
+            if keep_alive { "keep-alive" } else { "close" }
+        );
+        let mut bytes = self.head(&framing).into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+
+    /// Status line and headers, `framing` (CRLF-terminated lines) last.
+    fn head(&self, framing: &str) -> String {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason(self.status));
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(framing);
+        head.push_str("\r\n");
+        head
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Content Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BODY: usize = 64;
+
+    fn parse(raw: &str) -> Result<Request, ReadError> {
+        read_request(&mut io::Cursor::new(raw.as_bytes().to_vec()), MAX_BODY)
+    }
+
+    fn status(raw: &str) -> u16 {
+        match parse(raw) {
+            Err(ReadError::Bad(status, _)) => status,
+            Err(ReadError::Closed) => panic!("closed"),
+            Ok(_) => 200,
+        }
+    }
+
+    #[test]
+    fn reads_content_length_body() {
+        let req = parse("POST /notify HTTP/1.1\r\ncontent-length: 5\r\nX-Test: a: b\r\n\r\nhello").unwrap();
+        assert_eq!((req.method.as_str(), req.path.as_str()), ("POST", "/notify"));
+        assert_eq!(req.body, b"hello");
+        assert_eq!(req.header("x-test"), Some("a: b"));
+    }
+
+    #[test]
+    fn reads_chunked_body() {
+        let raw = "POST / HTTP/1.1\r\nTransfer-Encoding: Chunked\r\n\r\n\
+                   4\r\nWiki\r\n6;ext=1\r\npedia \r\nE\r\nin \r\n\r\nchunks.\r\n0\r\nTrailer: x\r\n\r\n";
+        assert_eq!(parse(raw).unwrap().body, b"Wikipedia in \r\n\r\nchunks.");
+    }
+
+    #[test]
+    fn leaves_pipelined_requests_unread() {
+        let raw = "GET /a HTTP/1.1\r\n\r\nGET /b HTTP/1.1\r\n\r\n";
+        let mut reader = io::Cursor::new(raw.as_bytes().to_vec());
+        assert_eq!(read_request(&mut reader, MAX_BODY).unwrap().path, "/a");
+        assert_eq!(read_request(&mut reader, MAX_BODY).unwrap().path, "/b");
+        assert_eq!(read_request(&mut reader, MAX_BODY).err(), Some(ReadError::Closed));
+    }
+
+    #[test]
+    fn rejects_oversized_bodies() {
+        let over = MAX_BODY + 1;
+        assert_eq!(status(&format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", over)), 413);
+        let chunks = format!("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n20\r\n{}\r\n21\r\n", "x".repeat(32));
+        assert_eq!(status(&chunks), 413);
+        let huge = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nffffffffffffffffff\r\n";
+        assert_eq!(status(huge), 400);
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        assert_eq!(status("garbage\r\n\r\n"), 400);
+        assert_eq!(status("GET / HTTP/2\r\n\r\n"), 400);
+        assert_eq!(status("GET / HTTP/1.1\r\nno colon\r\n\r\n"), 400);
+        assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: lots\r\n\r\n"), 400);
+        assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: 1\r\nContent-Length: 2\r\n\r\nab"), 400);
+        assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: 1\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n"), 400);
+        assert_eq!(status("POST / HTTP/1.1\r\nTransfer-Encoding: gzip\r\n\r\n"), 400);
+        assert_eq!(status("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n2\r\nabc\r\n0\r\n\r\n"), 400);
+        assert_eq!(status(&format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE))), 400);
+        let headers = "X: y\r\n".repeat(MAX_HEADERS + 1);
+        assert_eq!(status(&format!("GET / HTTP/1.1\r\n{}\r\n", headers)), 400);
+    }
+
+    #[test]
+    fn truncated_requests_are_closed_not_answered() {
+        assert_eq!(parse("").err(), Some(ReadError::Closed));
+        assert_eq!(parse("GET / HTTP/1.1\r\nHost: x").err(), Some(ReadError::Closed));
+        assert_eq!(parse("POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc").err(), Some(ReadError::Closed));
+    }
+
+    #[test]
+    fn keep_alive_follows_version_and_connection_header() {
+        assert!(parse("GET / HTTP/1.1\r\n\r\n").unwrap().keep_alive);
+        assert!(!parse("GET / HTTP/1.1\r\nConnection: Close\r\n\r\n").unwrap().keep_alive);
+        assert!(!parse("GET / HTTP/1.0\r\n\r\n").unwrap().keep_alive);
+        assert!(parse("GET / HTTP/1.0\r\nConnection: keep-alive\r\n\r\n").unwrap().keep_alive);
+    }
+}
@@ -0,0 +1,324 @@
+// ── Append-only event journals ───────────────────────────────────
+//
+// For "array of events" documents the app only ever appends to. Instead of
+// rewriting `<doc>.json` per event, each event becomes one line of
+// `<doc>.jsonl` next to it:
+//
+// - Appends are a single write of one line under the document's lock, so
+//   they cost the same however long the history is.
+// - A crash mid-append leaves a torn last line without its newline. Readers
+//   ignore it and the next append cuts it off before writing.
+// - A complete line that doesn't parse is skipped (and logged) rather than
+//   failing every read; compaction drops it.
+// - Once the journal passes COMPACT_AFTER bytes it is folded into the
+//   snapshot (`<doc>.json`, the file everything else already reads) and
+//   truncated. The snapshot is written first, so a crash in between only
+//   leaves events in both places, and they are deduplicated by id.
+//
+// Readers see the snapshot followed by the journal, so get_* commands don't
+// need to know whether an event has been compacted yet. The Node side reads
+// activity.jsonl and chatroom.jsonl the same way (packages/core/src/journal.ts).
+
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::model::{ActivityEvent, ActivityFile, ChatHistory, ChatHistoryMessage, ChatMessage, ChatroomState};
+
+const COMPACT_AFTER: u64 = 64 * 1024;
+
+/// A snapshot document whose event list can be journaled.
+pub trait Journaled: DeserializeOwned + Serialize + Default {
+    type Entry: DeserializeOwned + Serialize;
+    fn entries(&mut self) -> &mut Vec<Self::Entry>;
+    fn entry_id(entry: &Self::Entry) -> &str;
+    /// Whether a journaled entry still belongs in this snapshot.
+    fn accepts(&self, _entry: &Self::Entry) -> bool {
+        true
+    }
+}
+
+impl Journaled for ActivityFile {
+    type Entry = ActivityEvent;
+    fn entries(&mut self) -> &mut Vec<ActivityEvent> {
+        &mut self.activities
+    }
+    fn entry_id(entry: &ActivityEvent) -> &str {
+        &entry.id
+    }
+}
+
+impl Journaled for ChatHistory {
+    type Entry = ChatHistoryMessage;
+    fn entries(&mut self) -> &mut Vec<ChatHistoryMessage> {
+        &mut self.messages
+    }
+    fn entry_id(entry: &ChatHistoryMessage) -> &str {
+        &entry.id
+    }
+}
+
+/// Chatroom messages are stamped with the session they were posted to: the
+/// chatroom runner replaces the whole document when a new deliberation
+/// starts, and the old session's messages must not reappear in it.
+impl Journaled for ChatroomState {
+    type Entry = ChatMessage;
+    fn entries(&mut self) -> &mut Vec<ChatMessage> {
+        &mut self.messages
+    }
+    fn entry_id(entry: &ChatMessage) -> &str {
+        &entry.id
+    }
+    fn accepts(&self, entry: &ChatMessage) -> bool {
+        entry.extra.get("sessionId").and_then(|v| v.as_str()) == Some(self.session.id.as_str())
+    }
+}
+
+/// `activity.json` → `.hello-world/activity.jsonl`
+fn journal_path(project_path: &str, doc_file: &str) -> PathBuf {
+    let stem = doc_file.strip_suffix(".json").unwrap_or(doc_file);
+    crate::hw_path(project_path, &format!("{}.jsonl", stem))
+}
+
+/// Cut a torn last line left by a crash mid-append.
+fn recover(file: &mut File, path: &Path) -> Result<(), String> {
+    let len = file.metadata().map_err(|e| format!("Failed to read {}: {}", path.display(), e))?.len();
+    if len == 0 {
+        return Ok(());
+    }
+    let mut last = [0u8];
+    file.seek(SeekFrom::Start(len - 1))
+        .and_then(|_| file.read_exact(&mut last))
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if last[0] == b'\n' {
+        return Ok(());
+    }
+    let contents = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let keep = contents.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    file.set_len(keep as u64).map_err(|e| format!("Failed to repair {}: {}", path.display(), e))
+}
+
+/// Journal `entry` for `doc_file`, compacting if the journal has grown large.
+pub fn append<T: Journaled>(project_path: &str, doc_file: &str, entry: &T::Entry) -> Result<(), String> {
+    let path = journal_path(project_path, doc_file);
+    let mut line = serde_json::to_vec(entry).map_err(|e| format!("Failed to serialize: {}", e))?;
+    line.push(b'\n');
+
+    let size = crate::with_file_lock(project_path, doc_file, || {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        recover(&mut file, &path)?;
+        file.write_all(&line)
+            .and_then(|_| file.sync_data())
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        file.metadata().map(|m| m.len()).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    })?;
+
+    if size > COMPACT_AFTER {
+        compact::<T>(project_path, doc_file)?;
+    }
+    Ok(())
+}
+
+/// Complete lines of the journal; a torn last line and damaged lines are
+/// skipped.
+fn read_entries<T: Journaled>(project_path: &str, doc_file: &str) -> Result<Vec<T::Entry>, String> {
+    let path = journal_path(project_path, doc_file);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let complete = contents.rfind('\n').map_or("", |i| &contents[..i]);
+    let entries = complete.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(i, line)| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                eprintln!("Skipping {} line {}: {}", path.display(), i + 1, e);
+                None
+            }
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// Add journal entries the snapshot doesn't already hold.
+fn merge<T: Journaled>(doc: &mut T, entries: Vec<T::Entry>) {
+    let mut seen: HashSet<String> = doc.entries().iter().map(|e| T::entry_id(e).to_string()).collect();
+    for entry in entries {
+        if doc.accepts(&entry) && seen.insert(T::entry_id(&entry).to_string()) {
+            doc.entries().push(entry);
+        }
+    }
+}
+
+/// Size of the journal in bytes; 0 without one.
+pub fn len(project_path: &str, doc_file: &str) -> u64 {
+    fs::metadata(journal_path(project_path, doc_file)).map_or(0, |m| m.len())
+}
+
+/// The snapshot with the journal applied.
+pub fn read<T: Journaled>(project_path: &str, doc_file: &str) -> Result<T, String> {
+    let mut doc: T = crate::read_versioned_or_default(project_path, doc_file)?.data;
+    merge(&mut doc, read_entries::<T>(project_path, doc_file)?);
+    Ok(doc)
+}
+
+/// Fold the journal into the snapshot and empty it. A no-op without a journal.
+pub fn compact<T: Journaled>(project_path: &str, doc_file: &str) -> Result<(), String> {
+    let path = journal_path(project_path, doc_file);
+    if !path.exists() {
+        return Ok(());
+    }
+    crate::with_file_lock(project_path, doc_file, || {
+        let entries = read_entries::<T>(project_path, doc_file)?;
+        if !entries.is_empty() {
+            let mut doc: T = crate::read_versioned_or_default(project_path, doc_file)?.data;
+            merge(&mut doc, entries);
+            crate::write_document(project_path, doc_file, &doc)?;
+        }
+        // Also drops a torn last line, which never made it into the snapshot
+        File::create(&path)
+            .and_then(|f| f.sync_all())
+            .map_err(|e| format!("Failed to truncate {}: {}", path.display(), e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempProject;
+    use serde_json::json;
+
+    fn event(id: &str) -> ActivityEvent {
+        serde_json::from_value(json!({
+            "id": id, "type": "note", "description": format!("event {}", id), "timestamp": "2026-01-01T00:00:00.000Z",
+        }))
+        .unwrap()
+    }
+
+    fn ids(doc: &ActivityFile) -> Vec<&str> {
+        doc.activities.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    fn journal_len(project: &TempProject) -> u64 {
+        fs::metadata(journal_path(project.path(), "activity.json")).map_or(0, |m| m.len())
+    }
+
+    #[test]
+    fn torn_last_line_is_ignored_then_cut() {
+        let project = TempProject::new("journal-torn");
+        append::<ActivityFile>(project.path(), "activity.json", &event("act_1")).unwrap();
+        // A crash mid-append
+        let path = journal_path(project.path(), "activity.json");
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"id":"act_torn","type":"no"#).unwrap();
+        drop(file);
+
+        let doc: ActivityFile = read(project.path(), "activity.json").unwrap();
+        assert_eq!(ids(&doc), ["act_1"]);
+
+        append::<ActivityFile>(project.path(), "activity.json", &event("act_2")).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("act_torn"));
+        let doc: ActivityFile = read(project.path(), "activity.json").unwrap();
+        assert_eq!(ids(&doc), ["act_1", "act_2"]);
+    }
+
+    #[test]
+    fn damaged_line_is_skipped_then_compacted_away() {
+        let project = TempProject::new("journal-damaged");
+        append::<ActivityFile>(project.path(), "activity.json", &event("act_1")).unwrap();
+        let path = journal_path(project.path(), "activity.json");
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"id\":\"act_bad\",\"type\":\n").unwrap();
+        drop(file);
+        append::<ActivityFile>(project.path(), "activity.json", &event("act_2")).unwrap();
+
+        let doc: ActivityFile = read(project.path(), "activity.json").unwrap();
+        assert_eq!(ids(&doc), ["act_1", "act_2"]);
+
+        compact::<ActivityFile>(project.path(), "activity.json").unwrap();
+        assert_eq!(journal_len(&project), 0);
+        let snapshot: ActivityFile = crate::read_document(project.path(), "activity.json").unwrap();
+        assert_eq!(ids(&snapshot), ["act_1", "act_2"]);
+    }
+
+    #[test]
+    fn crash_between_snapshot_and_truncate_leaves_no_duplicates() {
+        let project = TempProject::new("journal-crash");
+        project.write("activity.json", r#"{"activities":[]}"#);
+        for id in ["act_1", "act_2"] {
+            append::<ActivityFile>(project.path(), "activity.json", &event(id)).unwrap();
+        }
+        // The snapshot got written, the journal was never truncated
+        let snapshot: ActivityFile = read(project.path(), "activity.json").unwrap();
+        crate::write_document(project.path(), "activity.json", &snapshot).unwrap();
+        append::<ActivityFile>(project.path(), "activity.json", &event("act_3")).unwrap();
+
+        let doc: ActivityFile = read(project.path(), "activity.json").unwrap();
+        assert_eq!(ids(&doc), ["act_1", "act_2", "act_3"]);
+
+        compact::<ActivityFile>(project.path(), "activity.json").unwrap();
+        assert_eq!(journal_len(&project), 0);
+        let snapshot: ActivityFile = crate::read_document(project.path(), "activity.json").unwrap();
+        assert_eq!(ids(&snapshot), ["act_1", "act_2", "act_3"]);
+    }
+
+    #[test]
+    fn large_journal_is_compacted_into_the_snapshot() {
+        let project = TempProject::new("journal-compact");
+        let mut n = 0;
+        // Append until an append crosses COMPACT_AFTER and empties the journal
+        loop {
+            append::<ActivityFile>(project.path(), "activity.json", &event(&format!("act_{}", n))).unwrap();
+            n += 1;
+            if journal_len(&project) == 0 {
+                break;
+            }
+            assert!(n < 10_000, "journal never compacted");
+        }
+        assert!(n > 100);
+
+        let snapshot: ActivityFile = crate::read_document(project.path(), "activity.json").unwrap();
+        assert_eq!(snapshot.activities.len(), n);
+        let doc: ActivityFile = read(project.path(), "activity.json").unwrap();
+        assert_eq!(doc.activities.len(), n);
+        assert_eq!(doc.activities.last().unwrap().id, format!("act_{}", n - 1));
+        // Without a journal there is nothing to do
+        fs::remove_file(journal_path(project.path(), "activity.json")).unwrap();
+        compact::<ActivityFile>(project.path(), "activity.json").unwrap();
+    }
+
+    #[test]
+    fn chatroom_messages_stay_with_their_session() {
+        let project = TempProject::new("journal-chatroom");
+        let message = |id: &str, session: &str| -> ChatMessage {
+            serde_json::from_value(json!({
+                "id": id, "agentId": "pat", "text": id, "timestamp": "2026-01-01T00:00:00.000Z",
+                "type": "pat", "sessionId": session,
+            }))
+            .unwrap()
+        };
+        append::<ChatroomState>(project.path(), "chatroom.json", &message("msg_old", "chat_a")).unwrap();
+        // The runner starts a new deliberation, replacing the document
+        project.write("chatroom.json", &json!({
+            "session": { "id": "chat_b", "topic": "t", "status": "active", "startedAt": "", "startedBy": "pat",
+                         "waitingForInput": false, "roundNumber": 0 },
+        }).to_string());
+        append::<ChatroomState>(project.path(), "chatroom.json", &message("msg_new", "chat_b")).unwrap();
+
+        let doc: ChatroomState = read(project.path(), "chatroom.json").unwrap();
+        assert_eq!(doc.messages.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(), ["msg_new"]);
+    }
+}
@@ -1,14 +1,49 @@
 use std::fs;
 use std::io::{BufRead, BufReader, Read, Write};
 use std::path::PathBuf;
-use std::process::Child;
 use std::sync::Mutex;
 use std::time::Duration;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
-use tauri::{Emitter, Manager};
-use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+use tauri::Manager;
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 
+mod activity;
+mod consolidate;
+mod context;
+mod decisions;
+mod events;
+mod graph;
+mod http;
+mod journal;
+mod lock;
+mod memories;
+mod migrate;
+mod model;
+mod notify_server;
+mod projects;
+mod questions;
+mod recall;
+mod scaffold;
+mod sqlite;
+mod storage;
+mod store;
+mod tasks;
+#[cfg(test)]
+mod tests;
+#[cfg(test)]
+mod testutil;
+
+use model::{
+    ActivityFile, ApprovalStatus, ApprovalsFile, BrainStateFile, ChatHistory,
+    ChatHistoryMessage, ChatMessage, ChatMessageType, ChatroomState, ConfigFile, ConsolidationState, ContextSettings,
+    DecidedBy, Decision, DecisionsFile, Direction, MemoriesFile, Memory, MemoryArchiveFile, ModeState, ProjectState, Question,
+    QuestionsFile, SessionsFile, StateRevisions, StorageBackend, Task, TasksFile, Timestamp,
+    TruncationStrategy, Versioned, WatcherStatus, WatchersFile, WorkflowState,
+};
+use projects::ProjectEntry;
+use store::{Documents, FsStore, ProjectStore};
+
 // ── UTC timestamp helper (no chrono dependency) ─────────────────
 
 /// Convert days since Unix epoch to (year, month, day).
@@ -27,12 +62,63 @@ fn civil_from_days(days: i64) -> (i64, u32, u32) {
     (y, m, d)
 }
 
+/// Inverse of civil_from_days.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as u32;
+    let mp = if m > 2 { m - 3 } else { m + 9 };
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe as i64 - 719468
+}
+
+/// Parse an ISO 8601 timestamp ("2026-02-26T05:30:00.123Z", with or without
+/// fraction and offset) into epoch milliseconds. A missing offset means UTC.
+fn millis_from_iso(iso: &str) -> Option<u64> {
+    let iso = iso.trim();
+    let field = |range: std::ops::Range<usize>| iso.get(range)?.parse::<u32>().ok();
+    let (y, mo, d) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let (h, mi, sec) = (field(11..13)?, field(14..16)?, field(17..19)?);
+    if !(1..=12).contains(&mo) || !(1..=31).contains(&d) || h > 23 || mi > 59 || sec > 60 {
+        return None;
+    }
+
+    let mut rest = &iso[19..];
+    let mut ms = 0u64;
+    if let Some(frac) = rest.strip_prefix('.') {
+        let digits = frac.find(|c: char| !c.is_ascii_digit()).unwrap_or(frac.len());
+        let padded = format!("{:0<3}", &frac[..digits.min(3)]);
+        ms = padded.parse().ok()?;
+        rest = &frac[digits..];
+    }
+    let offset_secs: i64 = match rest {
+        "" | "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let hh: i64 = rest.get(1..3)?.parse().ok()?;
+            let mm: i64 = rest.get(3..).map(|r| r.trim_start_matches(':')).filter(|r| !r.is_empty())
+                .map_or(Some(0), |r| r.parse().ok())?;
+            sign * (hh * 3600 + mm * 60)
+        }
+    };
+
+    let days = days_from_civil(y as i64, mo, d);
+    let secs = days * 86400 + (h * 3600 + mi * 60 + sec) as i64 - offset_secs;
+    u64::try_from(secs).ok().map(|s| s * 1000 + ms)
+}
+
 /// Returns current UTC time as ISO 8601 string (e.g. "2026-02-26T05:30:00.123Z").
 fn utc_now_iso() -> String {
-    let millis = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64;
+    iso_from_millis(epoch_ms())
+}
+
+/// Format epoch milliseconds the way JS `Date.toISOString()` does.
+fn iso_from_millis(millis: u64) -> String {
     let secs = (millis / 1000) as i64;
     let ms = millis % 1000;
     let time_of_day = secs.rem_euclid(86400);
@@ -122,40 +208,101 @@ fn get_capabilities() -> Value {
     })
 }
 
-// ── App config (project path storage) ───────────────────────────
-
-fn app_config_path() -> PathBuf {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .unwrap_or_else(|_| ".".to_string());
-    PathBuf::from(home).join(".hello-world-app.json")
+/// CONTRACT_VERSION of the event payloads this backend emits, so the UI can
+/// tell when it was built against different bindings.
+#[tauri::command]
+fn get_event_contract_version() -> u32 {
+    events::CONTRACT_VERSION
 }
 
+// ── Project registry ─────────────────────────────────────────────
+
 #[tauri::command]
-fn get_app_project_path() -> Option<String> {
-    let path = app_config_path();
-    let contents = fs::read_to_string(&path).ok()?;
-    let v: Value = serde_json::from_str(&contents).ok()?;
-    v.get("projectPath")?.as_str().map(|s| s.to_string())
+fn get_app_project_path() -> Result<Option<String>, String> {
+    Ok(projects::load()?.project_path)
 }
 
 #[tauri::command]
 fn set_app_project_path(project_path: String) -> Result<(), String> {
-    let hw_dir = PathBuf::from(&project_path).join(".hello-world");
-    if !hw_dir.exists() {
-        return Err(format!(
-            "Not a Hello World project — .hello-world/ not found at {}",
-            project_path
-        ));
+    projects::validate_project_dir(&project_path)?;
+    let mut config = projects::load()?;
+    config.open(&project_path);
+    projects::save(&config)
+}
+
+#[tauri::command]
+fn list_projects() -> Result<Vec<ProjectEntry>, String> {
+    Ok(projects::load()?.sorted())
+}
+
+#[tauri::command]
+fn add_project(project_path: String, name: Option<String>) -> Result<ProjectEntry, String> {
+    projects::validate_project_dir(&project_path)?;
+    let mut config = projects::load()?;
+    let entry = config.add(&project_path, name);
+    projects::save(&config)?;
+    Ok(entry)
+}
+
+#[tauri::command]
+fn remove_project(project_path: String) -> Result<(), String> {
+    let mut config = projects::load()?;
+    config.remove(&project_path)?;
+    projects::save(&config)
+}
+
+#[tauri::command]
+fn rename_project(project_path: String, name: String) -> Result<ProjectEntry, String> {
+    let mut config = projects::load()?;
+    let entry = config.rename(&project_path, &name)?;
+    projects::save(&config)?;
+    Ok(entry)
+}
+
+#[tauri::command]
+fn set_project_pinned(project_path: String, pinned: bool) -> Result<ProjectEntry, String> {
+    let mut config = projects::load()?;
+    let entry = config.set_pinned(&project_path, pinned)?;
+    projects::save(&config)?;
+    Ok(entry)
+}
+
+/// Create (or repair) the .hello-world/ layout in `project_path`. Existing
+/// files are validated and reported, never overwritten.
+#[tauri::command]
+fn init_project(project_path: String, name: Option<String>, template: Option<String>) -> Result<scaffold::InitReport, String> {
+    scaffold::init_project(&project_path, name, template.as_deref())
+}
+
+#[tauri::command]
+fn migrate_project(project_path: String) -> Result<migrate::MigrationReport, String> {
+    migrate::migrate_project(&project_path)
+}
+
+/// Make another registered (or new) project active: tear down the file
+/// watcher, notify listener and PTY of the current one and bring them back
+/// up for the new one.
+#[tauri::command]
+fn switch_project(app: tauri::AppHandle, project_path: String) -> Result<ProjectEntry, String> {
+    projects::validate_project_dir(&project_path)?;
+    let mut config = projects::load()?;
+    let entry = config.open(&project_path);
+
+    // Bring the new project up before committing to it: if that fails, the
+    // current project keeps its watcher, listener and PTY and stays active
+    start_watching(app.clone(), entry.path.clone())?;
+    projects::save(&config)?;
+
+    if stop_pty_session()? {
+        start_pty_session(app, Some(entry.path.clone()))?;
     }
-    let config = serde_json::json!({ "projectPath": project_path });
-    let contents = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Serialize error: {}", e))?;
-    fs::write(app_config_path(), contents)
-        .map_err(|e| format!("Write error: {}", e))
+    Ok(entry)
 }
 
 // ── Project data commands ────────────────────────────────────────
+//
+// Commands read and write documents through FsStore (store.rs). The path
+// helpers below are what it and the storage layers under it are built on.
 
 fn hw_path(project_path: &str, file_name: &str) -> PathBuf {
     PathBuf::from(project_path).join(".hello-world").join(file_name)
@@ -173,108 +320,377 @@ fn write_json_file(project_path: &str, file_name: &str, data: &Value) -> Result<
     let path = hw_path(project_path, file_name);
     let contents = serde_json::to_string_pretty(data)
         .map_err(|e| format!("Failed to serialize: {}", e))?;
-    fs::write(&path, contents)
-        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    storage::write_atomic_with_backup(&path, contents.as_bytes())?;
+    if sqlite::is_collection(file_name) && storage_backend(project_path) == StorageBackend::Sqlite {
+        // The JSON is written either way; until the mirror catches up, reads fall back to it
+        sqlite::sync_file(project_path, file_name)
+            .map_err(|e| format!("Saved {} but failed to update {}: {}", file_name, sqlite::DB_FILE, e))?;
+    }
+    Ok(())
+}
+
+/// Read and validate a typed document along with its content revision.
+/// Malformed files are rejected with the JSON path of the offending field
+/// instead of turning into nulls.
+fn read_versioned<T: DeserializeOwned>(project_path: &str, file_name: &str) -> Result<Versioned<T>, String> {
+    let path = hw_path(project_path, file_name);
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(Versioned {
+        revision: storage::content_revision(contents.as_bytes()),
+        data: model::parse_document(file_name, &contents)?,
+    })
+}
+
+/// Like `read_versioned`, but a missing file yields the empty document.
+fn read_versioned_or_default<T: DeserializeOwned + Default>(project_path: &str, file_name: &str) -> Result<Versioned<T>, String> {
+    if !hw_path(project_path, file_name).exists() {
+        return Ok(Versioned { revision: storage::MISSING_REVISION.to_string(), data: T::default() });
+    }
+    read_versioned(project_path, file_name)
+}
+
+fn read_document<T: DeserializeOwned>(project_path: &str, file_name: &str) -> Result<T, String> {
+    read_versioned(project_path, file_name).map(|v| v.data)
+}
+
+fn write_document<T: Serialize>(project_path: &str, file_name: &str, data: &T) -> Result<(), String> {
+    let value = serde_json::to_value(data)
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+    write_json_file(project_path, file_name, &value)
+}
+
+/// Reject a write if the file changed since the caller read it at `expected`.
+fn check_revision(file_name: &str, expected: Option<&str>, current: &str) -> Result<(), String> {
+    match expected {
+        Some(expected) if expected != current => Err(format!(
+            "Conflict: {} was modified by another writer (expected revision {}, found {}). Reload and try again.",
+            file_name, expected, current
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn current_revision(project_path: &str, file_name: &str) -> Result<String, String> {
+    let path = hw_path(project_path, file_name);
+    match fs::read(&path) {
+        Ok(bytes) => Ok(storage::content_revision(&bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(storage::MISSING_REVISION.to_string()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Run `f` while holding the cross-process lock on `file_name`, so the MCP
+/// server, sentinel and watchers can't interleave their writes with ours.
+fn with_file_lock<R>(project_path: &str, file_name: &str, f: impl FnOnce() -> Result<R, String>) -> Result<R, String> {
+    let _lock = lock::FileLock::acquire(&hw_path(project_path, file_name))?;
+    f()
+}
+
+/// Locked read-modify-write of a typed document. Nothing is written if
+/// `mutate` fails or the file no longer matches `expected_revision`.
+fn update_document<T, R>(
+    project_path: &str,
+    file_name: &str,
+    expected_revision: Option<&str>,
+    mutate: impl FnOnce(&mut T) -> Result<R, String>,
+) -> Result<R, String>
+where
+    T: DeserializeOwned + Serialize,
+{
+    with_file_lock(project_path, file_name, || {
+        let mut doc: Versioned<T> = read_versioned(project_path, file_name)?;
+        check_revision(file_name, expected_revision, &doc.revision)?;
+        let result = mutate(&mut doc.data)?;
+        write_document(project_path, file_name, &doc.data)?;
+        Ok(result)
+    })
+}
+
+fn storage_backend(project_path: &str) -> StorageBackend {
+    FsStore::new(project_path).read::<ConfigFile>("config.json")
+        .ok()
+        .and_then(|c| c.data.config.storage)
+        .unwrap_or_default()
 }
 
 #[tauri::command]
-fn get_config(project_path: &str) -> Result<Value, String> {
-    read_json_file(project_path, "config.json")
+fn get_config(project_path: &str) -> Result<Versioned<ConfigFile>, String> {
+    FsStore::new(project_path).read("config.json")
 }
 
 #[tauri::command]
-fn save_config(project_path: &str, config: Value) -> Result<Value, String> {
-    write_json_file(project_path, "config.json", &config)?;
+fn save_config(project_path: &str, config: ConfigFile, expected_revision: Option<String>) -> Result<ConfigFile, String> {
+    let previous_backend = storage_backend(project_path);
+    let doc = serde_json::to_value(&config).map_err(|e| format!("Failed to serialize: {}", e))?;
+    FsStore::new(project_path).put("config.json", &doc, expected_revision.as_deref())?;
+    // Switching to SQLite starts from a full import so reads never see an empty database
+    if config.config.storage == Some(StorageBackend::Sqlite) && previous_backend != StorageBackend::Sqlite {
+        sqlite::import_project(project_path)?;
+    }
     Ok(config)
 }
 
+/// (Re)load the SQLite mirror from the JSON files.
 #[tauri::command]
-fn get_state(project_path: &str) -> Result<Value, String> {
+fn import_sqlite(project_path: &str) -> Result<sqlite::SqliteReport, String> {
+    sqlite::import_project(project_path)
+}
+
+/// Rewrite the JSON files from the SQLite mirror, e.g. after restoring the
+/// database, so the project can be committed as plain JSON again.
+#[tauri::command]
+fn export_sqlite(project_path: &str) -> Result<sqlite::SqliteReport, String> {
+    sqlite::export_project(project_path)
+}
+
+#[tauri::command]
+fn get_state(project_path: &str) -> Result<ProjectState, String> {
     // state.json was split into tasks.json, decisions.json, questions.json
     // Merge them back into one response for the frontend
-    let tasks_data = read_json_file(project_path, "tasks.json")
-        .unwrap_or_else(|_| serde_json::json!({"tasks": []}));
-    let decisions_data = read_json_file(project_path, "decisions.json")
-        .unwrap_or_else(|_| serde_json::json!({"decisions": []}));
-    let questions_data = read_json_file(project_path, "questions.json")
-        .unwrap_or_else(|_| serde_json::json!({"questions": []}));
+    let store = FsStore::new(project_path);
+    let tasks_data: Versioned<TasksFile> = store.read_or_default("tasks.json")?;
+    let decisions_data: Versioned<DecisionsFile> = store.read_or_default("decisions.json")?;
+    let questions_data: Versioned<QuestionsFile> = store.read_or_default("questions.json")?;
+
+    Ok(ProjectState {
+        tasks: tasks_data.data.tasks,
+        decisions: decisions_data.data.decisions,
+        questions: questions_data.data.questions,
+        revisions: StateRevisions {
+            tasks: tasks_data.revision,
+            decisions: decisions_data.revision,
+            questions: questions_data.revision,
+        },
+    })
+}
 
-    Ok(serde_json::json!({
-        "tasks": tasks_data["tasks"],
-        "decisions": decisions_data["decisions"],
-        "questions": questions_data["questions"],
-    }))
+#[tauri::command]
+fn get_memories(project_path: &str) -> Result<MemoriesFile, String> {
+    Ok(FsStore::new(project_path).read("memories.json")?.data)
+}
+
+/// One page of memories; see memories::MemoryQuery for the filters.
+#[tauri::command]
+fn list_memories(project_path: &str, query: Option<memories::MemoryQuery>) -> Result<memories::MemoryPage, String> {
+    let doc: Versioned<MemoriesFile> = FsStore::new(project_path).read_or_default("memories.json")?;
+    Ok(memories::list(&doc.data, &query.unwrap_or_default(), doc.revision))
+}
+
+/// Rank memories for `query` and, unless `record_access` is false, count the
+/// hits as accesses in memories.json and brain-state.json.
+#[tauri::command]
+fn recall_memories(
+    project_path: &str,
+    query: String,
+    limit: Option<usize>,
+    record_access: Option<bool>,
+) -> Result<Vec<recall::RecalledMemory>, String> {
+    let limit = limit.unwrap_or(recall::DEFAULT_LIMIT);
+    let store = FsStore::new(project_path);
+    let brain: Option<BrainStateFile> = store.read("brain-state.json").ok().map(|v| v.data);
+    let rank = |memories: &[Memory]| recall::rank(memories, brain.as_ref().map(|b| &b.state), &query, limit, epoch_ms());
+
+    // Rank read-only first so a miss (or a preview) never rewrites the file
+    let data: MemoriesFile = store.read_or_default("memories.json")?.data;
+    let results = rank(&data.memories);
+    if results.is_empty() || record_access == Some(false) {
+        return Ok(results);
+    }
+
+    let timestamp = utc_now_iso();
+    let results = store.update("memories.json", None, |data: &mut MemoriesFile| {
+        let mut results = rank(&data.memories);
+        recall::record_access(&mut data.memories, &mut results, &timestamp);
+        Ok(results)
+    })?;
+    if brain.is_some() {
+        // Best effort: the brain state is bookkeeping, the recall already succeeded
+        let _ = store.update("brain-state.json", None, |b: &mut BrainStateFile| {
+            recall::record_brain_activity(&mut b.state, &results, &timestamp);
+            Ok(())
+        });
+    }
+    Ok(results)
+}
+
+#[tauri::command]
+fn update_memory(project_path: &str, id: String, patch: memories::MemoryPatch, expected_revision: Option<String>) -> Result<Memory, String> {
+    FsStore::new(project_path).update("memories.json", expected_revision.as_deref(), |data: &mut MemoriesFile| {
+        memories::update(data, &id, patch)
+    })
+}
+
+#[tauri::command]
+fn merge_memories(project_path: &str, keep_id: String, merge_ids: Vec<String>, expected_revision: Option<String>) -> Result<Memory, String> {
+    FsStore::new(project_path).update("memories.json", expected_revision.as_deref(), |data: &mut MemoriesFile| {
+        memories::merge(data, &keep_id, &merge_ids)
+    })
+}
+
+#[tauri::command]
+fn delete_memory(project_path: &str, id: String, expected_revision: Option<String>) -> Result<Memory, String> {
+    FsStore::new(project_path).update("memories.json", expected_revision.as_deref(), |data: &mut MemoriesFile| {
+        memories::delete(data, &id)
+    })
+}
+
+/// Decay, archive and duplicate detection over memories.json; see
+/// consolidate.rs. A dry run reports what would happen without writing.
+#[tauri::command]
+fn consolidate_memories(project_path: &str, dry_run: Option<bool>) -> Result<consolidate::ConsolidationReport, String> {
+    run_consolidation(project_path, dry_run.unwrap_or(false))
+}
+
+fn run_consolidation(project_path: &str, dry_run: bool) -> Result<consolidate::ConsolidationReport, String> {
+    let now_ms = epoch_ms();
+    let now = iso_from_millis(now_ms);
+    let store = FsStore::new(project_path);
+    if dry_run {
+        let mut data: MemoriesFile = store.read_or_default("memories.json")?.data;
+        let mut report = consolidate::consolidate(&mut data, now_ms, &now);
+        report.dry_run = true;
+        return Ok(report);
+    }
+
+    let report = store.update("memories.json", None, |data: &mut MemoriesFile| {
+        let report = consolidate::consolidate(data, now_ms, &now);
+        if !report.archived.is_empty() {
+            // Written before memories.json, so a failure here loses nothing
+            store.update_or_default("memories-archive.json", None, |archive: &mut MemoryArchiveFile| {
+                archive.archived.extend(report.archived.iter().cloned());
+                archive.total_archived += report.archived.len() as u64;
+                archive.last_pruned = now.clone();
+                Ok(())
+            })?;
+        }
+        Ok(report)
+    })?;
+    store.update_or_default(consolidate::STATE_FILE, None, |state: &mut ConsolidationState| {
+        state.last_consolidated_at = Some(now.clone());
+        Ok(())
+    })?;
+    // Best effort: the consolidation itself already succeeded
+    let _ = activity::append(project_path, consolidate::ACTIVITY_TYPE, report.summary(), report.details());
+    Ok(report)
+}
+
+/// Consolidate at most once per consolidate::INTERVAL_MS while a project is
+/// open, judged by consolidate.json so restarts don't reset the clock.
+/// Opening another project bumps the generation, which retires this loop.
+fn spawn_consolidation_schedule(app: tauri::AppHandle, project_path: String) {
+    use std::sync::atomic::Ordering;
+    let generation = CONSOLIDATION_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    std::thread::spawn(move || {
+        // Let the project finish opening before competing for its files
+        let mut delay = Duration::from_secs(60);
+        loop {
+            std::thread::sleep(delay);
+            delay = Duration::from_secs(3600);
+            if CONSOLIDATION_GENERATION.load(Ordering::Relaxed) != generation {
+                break;
+            }
+            let store = FsStore::new(&project_path);
+            let last_run = store.read_or_default::<ConsolidationState>(consolidate::STATE_FILE)
+                .ok()
+                .and_then(|s| consolidate::last_run(&s.data))
+                .or_else(|| {
+                    let activity = store.read_or_default::<ActivityFile>("activity.json").ok()?;
+                    consolidate::last_logged_run(&activity.data)
+                });
+            if !consolidate::is_due(last_run, epoch_ms()) {
+                continue;
+            }
+            if let Ok(report) = run_consolidation(&project_path, false) {
+                let mut files = vec![
+                    "memories.json".to_string(),
+                    "activity.json".to_string(),
+                    consolidate::STATE_FILE.to_string(),
+                ];
+                if !report.archived.is_empty() {
+                    files.push("memories-archive.json".to_string());
+                }
+                events::emit(&app, events::FilesChanged(files));
+            }
+        }
+    });
 }
 
+static CONSOLIDATION_GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
 #[tauri::command]
-fn get_memories(project_path: &str) -> Result<Value, String> {
-    read_json_file(project_path, "memories.json")
+fn get_sessions(project_path: &str) -> Result<SessionsFile, String> {
+    Ok(FsStore::new(project_path).read("sessions.json")?.data)
 }
 
 #[tauri::command]
-fn get_sessions(project_path: &str) -> Result<Value, String> {
-    read_json_file(project_path, "sessions.json")
+fn get_brain_state(project_path: &str) -> Result<BrainStateFile, String> {
+    Ok(FsStore::new(project_path).read("brain-state.json")?.data)
 }
 
 #[tauri::command]
-fn get_brain_state(project_path: &str) -> Result<Value, String> {
-    read_json_file(project_path, "brain-state.json")
+fn get_activity(project_path: &str) -> Result<ActivityFile, String> {
+    Ok(FsStore::new(project_path).read_or_default("activity.json")?.data)
 }
 
+/// Newest-first page across activity.json and its rotated segments. Pass the
+/// returned `nextCursor` back to continue; `types` limits the event types.
 #[tauri::command]
-fn get_activity(project_path: &str) -> Result<Value, String> {
-    read_json_file(project_path, "activity.json")
+fn get_activity_page(
+    project_path: &str,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    types: Option<Vec<String>>,
+) -> Result<activity::ActivityPage, String> {
+    activity::page(project_path, cursor.as_deref(), limit, &types.unwrap_or_default())
 }
 
 #[tauri::command]
-fn get_approvals(project_path: &str) -> Result<Value, String> {
-    read_json_file(project_path, "approvals.json")
+fn get_approvals(project_path: &str) -> Result<Versioned<ApprovalsFile>, String> {
+    FsStore::new(project_path).read("approvals.json")
 }
 
 #[tauri::command]
-fn get_workflow(project_path: &str) -> Result<Value, String> {
-    read_json_file(project_path, "workflow.json")
+fn get_workflow(project_path: &str) -> Result<WorkflowState, String> {
+    Ok(FsStore::new(project_path).read("workflow.json")?.data)
 }
 
 #[tauri::command]
-fn get_direction(project_path: &str) -> Result<Value, String> {
-    read_json_file(project_path, "direction.json")
+fn get_direction(project_path: &str) -> Result<Versioned<Direction>, String> {
+    FsStore::new(project_path).read("direction.json")
 }
 
 #[tauri::command]
-fn mark_direction_note_read(project_path: &str, note_id: String) -> Result<(), String> {
-    let mut data = read_json_file(project_path, "direction.json")?;
-    let notes = data["notes"]
-        .as_array_mut()
-        .ok_or("direction.json missing notes array")?;
-    for note in notes.iter_mut() {
-        if note["id"].as_str() == Some(note_id.as_str()) {
-            note["read"] = serde_json::json!(true);
-            break;
+fn mark_direction_note_read(project_path: &str, note_id: String, expected_revision: Option<String>) -> Result<(), String> {
+    mark_note_read(&FsStore::new(project_path), &note_id, expected_revision.as_deref())
+}
+
+fn mark_note_read(store: &dyn ProjectStore, note_id: &str, expected_revision: Option<&str>) -> Result<(), String> {
+    store.update("direction.json", expected_revision, |data: &mut Direction| {
+        if let Some(note) = data.notes.iter_mut().find(|n| n.id == note_id) {
+            note.read = true;
         }
-    }
-    write_json_file(project_path, "direction.json", &data)
+        Ok(())
+    })
 }
 
 #[tauri::command]
-fn get_mode(project_path: &str) -> Result<Value, String> {
-    read_json_file(project_path, "mode.json")
-        .or_else(|_| Ok(serde_json::json!({"overdrive": false})))
+fn get_mode(project_path: &str) -> Result<Versioned<ModeState>, String> {
+    FsStore::new(project_path).read_or_default("mode.json")
 }
 
 #[tauri::command]
-fn set_mode(project_path: &str, overdrive: bool) -> Result<Value, String> {
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis();
-    let data = serde_json::json!({
-        "overdrive": overdrive,
-        "toggledAt": now,
-        "toggledBy": "pat",
-    });
-    write_json_file(project_path, "mode.json", &data)?;
-    Ok(data)
+fn set_mode(project_path: &str, overdrive: bool, expected_revision: Option<String>) -> Result<ModeState, String> {
+    toggle_mode(&FsStore::new(project_path), overdrive, expected_revision.as_deref())
+}
+
+fn toggle_mode(store: &dyn ProjectStore, overdrive: bool, expected_revision: Option<&str>) -> Result<ModeState, String> {
+    store.update_or_default("mode.json", expected_revision, |data: &mut ModeState| {
+        data.overdrive = overdrive;
+        data.toggled_at = Some(Timestamp::now());
+        data.toggled_by = Some("pat".to_string());
+        Ok(data.clone())
+    })
 }
 
 #[tauri::command]
@@ -361,7 +777,8 @@ fn spawn_sentinel(project_path: String) -> Result<Value, String> {
 
 #[tauri::command]
 fn get_sentinel_status(project_path: &str) -> Result<Value, String> {
-    let data = read_json_file(project_path, "sentinel.json")
+    let data = FsStore::new(project_path).read::<Value>("sentinel.json")
+        .map(|v| v.data)
         .unwrap_or_else(|_| serde_json::json!({"status": "not_running"}));
 
     // Verify the sentinel PID is actually alive
@@ -395,48 +812,39 @@ fn get_sentinel_status(project_path: &str) -> Result<Value, String> {
 }
 
 #[tauri::command]
-fn get_watchers(project_path: &str) -> Result<Value, String> {
-    read_json_file(project_path, "watchers.json")
+fn get_watchers(project_path: &str) -> Result<Versioned<WatchersFile>, String> {
+    FsStore::new(project_path).read("watchers.json")
 }
 
 #[tauri::command]
-fn kill_watcher(project_path: &str, watcher_id: String) -> Result<(), String> {
-    let mut data = read_json_file(project_path, "watchers.json")?;
-
-    let active = data["active"]
-        .as_array_mut()
-        .ok_or("watchers.json missing active array")?;
+fn kill_watcher(project_path: &str, watcher_id: String, expected_revision: Option<String>) -> Result<(), String> {
+    FsStore::new(project_path).update("watchers.json", expected_revision.as_deref(), |data: &mut WatchersFile| {
+        let idx = data.active
+            .iter()
+            .position(|w| w.id == watcher_id)
+            .ok_or_else(|| format!("Watcher {} not found in active list", watcher_id))?;
 
-    let idx = active
-        .iter()
-        .position(|w| w["id"].as_str() == Some(watcher_id.as_str()))
-        .ok_or_else(|| format!("Watcher {} not found in active list", watcher_id))?;
+        let pid = data.active[idx].pid;
 
-    let pid = active[idx]["pid"]
-        .as_u64()
-        .ok_or("Watcher missing pid")?;
-
-    #[cfg(windows)]
-    std::process::Command::new("taskkill")
-        .args(["/F", "/PID", &pid.to_string()])
-        .output()
-        .map_err(|e| format!("taskkill failed: {}", e))?;
-
-    #[cfg(not(windows))]
-    std::process::Command::new("kill")
-        .args(["-9", &pid.to_string()])
-        .output()
-        .map_err(|e| format!("kill failed: {}", e))?;
+        #[cfg(windows)]
+        std::process::Command::new("taskkill")
+            .args(["/F", "/PID", &pid.to_string()])
+            .output()
+            .map_err(|e| format!("taskkill failed: {}", e))?;
 
-    let mut watcher = active.remove(idx);
-    watcher["status"] = serde_json::json!("killed");
+        #[cfg(not(windows))]
+        std::process::Command::new("kill")
+            .args(["-9", &pid.to_string()])
+            .output()
+            .map_err(|e| format!("kill failed: {}", e))?;
 
-    data["completed"]
-        .as_array_mut()
-        .ok_or("watchers.json missing completed array")?
-        .push(watcher);
+        let mut watcher = data.active.remove(idx);
+        watcher.status = WatcherStatus::Killed;
+        watcher.completed_at = Some(utc_now_iso());
+        data.completed.push(watcher);
 
-    write_json_file(project_path, "watchers.json", &data)
+        Ok(())
+    })
 }
 
 #[tauri::command]
@@ -469,49 +877,33 @@ fn get_timeline(project_path: &str) -> Result<String, String> {
 }
 
 #[tauri::command]
-fn get_chatroom(project_path: &str) -> Result<String, String> {
-    let path = std::path::Path::new(project_path)
-        .join(".hello-world")
-        .join("chatroom.json");
-    if !path.exists() {
-        return Ok(r#"{"session":{"id":"","topic":"","status":"idle","startedAt":"","startedBy":"claude","waitingForInput":false,"roundNumber":0},"agents":[],"messages":[]}"#.to_string());
-    }
-    std::fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read chatroom.json: {}", e))
+fn get_chatroom(project_path: &str) -> Result<Versioned<ChatroomState>, String> {
+    FsStore::new(project_path).read_or_default("chatroom.json")
 }
 
 #[tauri::command]
-fn post_pat_chatroom_message(project_path: &str, message: String) -> Result<(), String> {
-    let path = std::path::Path::new(project_path)
-        .join(".hello-world")
-        .join("chatroom.json");
-    let raw = std::fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read chatroom.json: {}", e))?;
-    let mut data: serde_json::Value = serde_json::from_str(&raw)
-        .map_err(|e| format!("Failed to parse chatroom.json: {}", e))?;
+fn post_pat_chatroom_message(project_path: &str, message: String, expected_revision: Option<String>) -> Result<(), String> {
+    post_chatroom_message(&FsStore::new(project_path), &message, expected_revision.as_deref())
+}
+
+fn post_chatroom_message(store: &dyn ProjectStore, message: &str, expected_revision: Option<&str>) -> Result<(), String> {
     // Set pendingPatMessage on the session
-    if let Some(session) = data.get_mut("session") {
-        session["pendingPatMessage"] = serde_json::Value::String(message.clone());
-        session["waitingForInput"] = serde_json::Value::Bool(false);
-    }
-    // Also append to messages directly so Pat sees it immediately
-    if let Some(messages) = data.get_mut("messages").and_then(|m| m.as_array_mut()) {
-        let ms = epoch_ms();
-        let now = ms.to_string(); // epoch ms — new Date(ms) works fine in JS
-        let id = format!("msg_{}", ms);
-        messages.push(serde_json::json!({
-            "id": id,
-            "agentId": "pat",
-            "text": message,
-            "timestamp": now,
-            "type": "pat"
-        }));
-    }
-    let out = serde_json::to_string_pretty(&data)
-        .map_err(|e| format!("Serialize error: {}", e))?;
-    std::fs::write(&path, out)
-        .map_err(|e| format!("Write error: {}", e))?;
-    Ok(())
+    let session_id = store.update("chatroom.json", expected_revision, |data: &mut ChatroomState| {
+        data.session.pending_pat_message = Some(message.to_string());
+        data.session.waiting_for_input = false;
+        Ok(data.session.id.clone())
+    })?;
+    // Also journal it as a message so Pat sees it immediately
+    let mut extra = serde_json::Map::new();
+    extra.insert("sessionId".to_string(), Value::String(session_id));
+    store.append_entry("chatroom.json", &ChatMessage {
+        id: format!("msg_{}", epoch_ms()),
+        agent_id: "pat".to_string(),
+        text: message.to_string(),
+        timestamp: utc_now_iso(),
+        message_type: ChatMessageType::Pat,
+        extra,
+    })
 }
 
 fn epoch_ms() -> u64 {
@@ -521,85 +913,257 @@ fn epoch_ms() -> u64 {
         .as_millis() as u64
 }
 
+/// `<prefix>_` plus 8 random hex chars, the same shape as generateId in
+/// packages/core. RandomState is seeded per process from the OS, which is
+/// plenty for ids that only need to be unique within one file.
+fn generate_id(prefix: &str) -> String {
+    use std::hash::{BuildHasher, Hasher};
+    static COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    hasher.write_u64(COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
+    format!("{}_{:08x}", prefix, hasher.finish() as u32)
+}
+
 // ── Approval resolution ──────────────────────────────────────────
 
 #[tauri::command]
-fn resolve_approval(project_path: &str, request_id: String, decision: String) -> Result<(), String> {
-    let mut data = read_json_file(project_path, "approvals.json")?;
+fn resolve_approval(
+    project_path: &str,
+    request_id: String,
+    decision: String,
+    expected_revision: Option<String>,
+) -> Result<(), String> {
+    apply_approval_decision(&FsStore::new(project_path), &request_id, &decision, expected_revision.as_deref())
+}
 
-    let pending = data["pending"]
-        .as_array_mut()
-        .ok_or("approvals.json missing pending array")?;
+fn apply_approval_decision(
+    store: &dyn ProjectStore,
+    request_id: &str,
+    decision: &str,
+    expected_revision: Option<&str>,
+) -> Result<(), String> {
+    let status = match decision {
+        "approved" => ApprovalStatus::Approved,
+        "rejected" => ApprovalStatus::Rejected,
+        other => return Err(format!("Invalid approval decision: {} (expected approved or rejected)", other)),
+    };
+
+    store.update("approvals.json", expected_revision, |data: &mut ApprovalsFile| {
+        let pos = data.pending.iter().position(|r| r.id == request_id);
+        let idx = pos.ok_or_else(|| format!("Approval request not found: {}", request_id))?;
+        let mut resolved = data.pending.remove(idx);
+
+        resolved.status = status;
+        resolved.resolved_at = Some(Timestamp::now());
+
+        data.resolved.push(resolved);
+        Ok(())
+    })
+}
 
-    let pos = pending.iter().position(|r| r["id"].as_str() == Some(request_id.as_str()));
-    let idx = pos.ok_or_else(|| format!("Approval request not found: {}", request_id))?;
-    let mut resolved = pending.remove(idx);
+// ── Questions ────────────────────────────────────────────────────
 
-    resolved["status"] = serde_json::json!(decision);
-    resolved["resolvedAt"] = serde_json::json!(epoch_ms());
+// The link checks run inside the questions.json update, so a question is
+// never linked against a stale read. Deleting a task takes only the
+// tasks.json lock, though, so one deleted mid-link can still leave a
+// dangling id — accepted, as delete_task already leaves earlier links alone.
 
-    data["resolved"]
-        .as_array_mut()
-        .ok_or("approvals.json missing resolved array")?
-        .push(resolved);
+fn check_task_exists(project_path: &str, task_id: &str) -> Result<(), String> {
+    let data: TasksFile = FsStore::new(project_path).read_or_default("tasks.json")?.data;
+    if !data.tasks.iter().any(|t| t.id == task_id) {
+        return Err(format!("Task not found: {}", task_id));
+    }
+    Ok(())
+}
 
-    write_json_file(project_path, "approvals.json", &data)
+fn check_decision_exists(project_path: &str, decision_id: &str) -> Result<(), String> {
+    let data: DecisionsFile = FsStore::new(project_path).read_or_default("decisions.json")?.data;
+    if !data.decisions.iter().any(|d| d.id == decision_id) {
+        return Err(format!("Decision not found: {}", decision_id));
+    }
+    Ok(())
 }
 
-// ── Question answering ───────────────────────────────────────────
+#[tauri::command]
+fn ask_question(
+    project_path: &str,
+    question: String,
+    context: Option<String>,
+    linked_task_id: Option<String>,
+    expected_revision: Option<String>,
+) -> Result<Question, String> {
+    FsStore::new(project_path).update_or_default("questions.json", expected_revision.as_deref(), |data: &mut QuestionsFile| {
+        if let Some(task_id) = &linked_task_id {
+            check_task_exists(project_path, task_id)?;
+        }
+        questions::ask(data, &question, context.unwrap_or_default(), linked_task_id)
+    })
+}
 
+/// Answers from the app are Pat's unless the caller says otherwise.
 #[tauri::command]
-fn answer_question(project_path: &str, id: String, answer: String) -> Result<Value, String> {
-    let mut data = read_json_file(project_path, "questions.json")?;
+fn answer_question(
+    project_path: &str,
+    id: String,
+    answer: String,
+    answered_by: Option<DecidedBy>,
+    expected_revision: Option<String>,
+) -> Result<Question, String> {
+    FsStore::new(project_path).update("questions.json", expected_revision.as_deref(), |data: &mut QuestionsFile| {
+        questions::answer(data, &id, &answer, answered_by.unwrap_or(DecidedBy::Pat))
+    })
+}
 
-    let questions = data["questions"]
-        .as_array_mut()
-        .ok_or("questions.json missing questions array")?;
+#[tauri::command]
+fn defer_question(project_path: &str, id: String, expected_revision: Option<String>) -> Result<Question, String> {
+    FsStore::new(project_path).update("questions.json", expected_revision.as_deref(), |data: &mut QuestionsFile| {
+        questions::defer(data, &id)
+    })
+}
 
-    let q = questions
-        .iter_mut()
-        .find(|q| q["id"].as_str() == Some(id.as_str()))
-        .ok_or_else(|| format!("Question not found: {}", id))?;
+#[tauri::command]
+fn dismiss_question(project_path: &str, id: String, reason: Option<String>, expected_revision: Option<String>) -> Result<Question, String> {
+    FsStore::new(project_path).update("questions.json", expected_revision.as_deref(), |data: &mut QuestionsFile| {
+        questions::dismiss(data, &id, reason)
+    })
+}
 
-    q["status"] = serde_json::json!("answered");
-    q["answer"] = serde_json::json!(answer);
-    q["answeredAt"] = serde_json::json!(epoch_ms());
+#[tauri::command]
+fn reopen_question(project_path: &str, id: String, expected_revision: Option<String>) -> Result<Question, String> {
+    FsStore::new(project_path).update("questions.json", expected_revision.as_deref(), |data: &mut QuestionsFile| {
+        questions::reopen(data, &id)
+    })
+}
 
-    let result = q.clone();
-    write_json_file(project_path, "questions.json", &data)?;
-    Ok(result)
+/// An empty id clears that link; an omitted one leaves it unchanged.
+#[tauri::command]
+fn link_question(
+    project_path: &str,
+    id: String,
+    task_id: Option<String>,
+    decision_id: Option<String>,
+    expected_revision: Option<String>,
+) -> Result<Question, String> {
+    FsStore::new(project_path).update("questions.json", expected_revision.as_deref(), |data: &mut QuestionsFile| {
+        if let Some(task_id) = task_id.as_deref().filter(|t| !t.is_empty()) {
+            check_task_exists(project_path, task_id)?;
+        }
+        if let Some(decision_id) = decision_id.as_deref().filter(|d| !d.is_empty()) {
+            check_decision_exists(project_path, decision_id)?;
+        }
+        questions::link(data, &id, task_id, decision_id)
+    })
 }
 
-// ── Chat history ─────────────────────────────────────────────────
+// ── Task editing ─────────────────────────────────────────────────
 
-fn append_chat_message_internal(project_path: &str, role: &str, text: &str) -> Result<(), String> {
-    let mut history = read_json_file(project_path, "chat-out.json")
-        .unwrap_or_else(|_| serde_json::json!({ "messages": [] }));
+#[tauri::command]
+fn add_task(project_path: &str, task: tasks::NewTask, expected_revision: Option<String>) -> Result<Task, String> {
+    FsStore::new(project_path).update_or_default("tasks.json", expected_revision.as_deref(), |data: &mut TasksFile| {
+        tasks::add(data, task)
+    })
+}
 
-    let timestamp_ms = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64;
+#[tauri::command]
+fn update_task(project_path: &str, id: String, patch: tasks::TaskPatch, expected_revision: Option<String>) -> Result<Task, String> {
+    FsStore::new(project_path).update("tasks.json", expected_revision.as_deref(), |data: &mut TasksFile| {
+        tasks::update(data, &id, patch)
+    })
+}
 
-    let new_msg = serde_json::json!({
-        "id": format!("msg_{}", timestamp_ms),
-        "role": role,
-        "text": text,
-        "timestamp": timestamp_ms,
-    });
+#[tauri::command]
+fn delete_task(project_path: &str, id: String, expected_revision: Option<String>) -> Result<Task, String> {
+    FsStore::new(project_path).update("tasks.json", expected_revision.as_deref(), |data: &mut TasksFile| {
+        tasks::delete(data, &id)
+    })
+}
 
-    history["messages"]
-        .as_array_mut()
-        .ok_or("messages is not an array")?
-        .push(new_msg);
+#[tauri::command]
+fn reorder_tasks(project_path: &str, ordered_ids: Vec<String>, expected_revision: Option<String>) -> Result<Vec<Task>, String> {
+    FsStore::new(project_path).update("tasks.json", expected_revision.as_deref(), |data: &mut TasksFile| {
+        tasks::reorder(data, &ordered_ids)?;
+        Ok(data.tasks.clone())
+    })
+}
 
-    write_json_file(project_path, "chat-out.json", &history)
+#[tauri::command]
+fn get_task_graph(project_path: &str) -> Result<graph::TaskGraph, String> {
+    let data: TasksFile = FsStore::new(project_path).read_or_default("tasks.json")?.data;
+    Ok(graph::build(&data.tasks))
 }
 
+// ── Decision log ─────────────────────────────────────────────────
+
 #[tauri::command]
-fn get_chat_history(project_path: &str) -> Value {
-    read_json_file(project_path, "chat-out.json")
-        .unwrap_or_else(|_| serde_json::json!({ "messages": [] }))
+fn record_decision(project_path: &str, decision: decisions::NewDecision, expected_revision: Option<String>) -> Result<Decision, String> {
+    FsStore::new(project_path).update_or_default("decisions.json", expected_revision.as_deref(), |data: &mut DecisionsFile| {
+        decisions::record(data, decision)
+    })
+}
+
+#[tauri::command]
+fn amend_decision(project_path: &str, id: String, patch: decisions::DecisionPatch, expected_revision: Option<String>) -> Result<Decision, String> {
+    FsStore::new(project_path).update("decisions.json", expected_revision.as_deref(), |data: &mut DecisionsFile| {
+        decisions::amend(data, &id, patch)
+    })
+}
+
+#[tauri::command]
+fn supersede_decision(
+    project_path: &str,
+    id: String,
+    replacement: decisions::NewDecision,
+    expected_revision: Option<String>,
+) -> Result<Decision, String> {
+    FsStore::new(project_path).update("decisions.json", expected_revision.as_deref(), |data: &mut DecisionsFile| {
+        decisions::supersede(data, &id, replacement)
+    })
+}
+
+#[tauri::command]
+fn get_decision_history(project_path: &str, id: String) -> Result<Vec<Decision>, String> {
+    let data: DecisionsFile = FsStore::new(project_path).read_or_default("decisions.json")?.data;
+    decisions::chain(&data, &id)
+}
+
+#[tauri::command]
+fn query_decisions(
+    project_path: &str,
+    text: Option<String>,
+    tag: Option<String>,
+    include_superseded: Option<bool>,
+) -> Result<Vec<Decision>, String> {
+    let data: DecisionsFile = FsStore::new(project_path).read_or_default("decisions.json")?.data;
+    Ok(decisions::query(&data, text.as_deref(), tag.as_deref(), include_superseded.unwrap_or(false)))
+}
+
+// ── Chat history ─────────────────────────────────────────────────
+
+fn append_chat_message_internal(project_path: &str, role: &str, text: &str) -> Result<(), String> {
+    record_chat_message(&FsStore::new(project_path), role, text)
+}
+
+fn record_chat_message(store: &dyn ProjectStore, role: &str, text: &str) -> Result<(), String> {
+    // Random ids: the journal deduplicates by id, and two messages can share a millisecond
+    let message = ChatHistoryMessage {
+        id: generate_id("msg"),
+        role: role.to_string(),
+        text: text.to_string(),
+        timestamp: Timestamp::now(),
+        extra: Default::default(),
+    };
+    store.append_entry("chat-out.json", &message)
+}
+
+#[tauri::command]
+fn get_chat_history(project_path: &str) -> Result<ChatHistory, String> {
+    Ok(FsStore::new(project_path).read_or_default("chat-out.json")?.data)
 }
 
 #[tauri::command]
@@ -612,13 +1176,6 @@ fn append_chat_message(project_path: &str, role: String, text: String) -> Result
 // Persists the active chat session ID across messages for conversation continuity
 static CHAT_SESSION_ID: Mutex<Option<String>> = Mutex::new(None);
 
-// Emitted to frontend as text chunks arrive
-#[derive(Clone, serde::Serialize)]
-struct ChatChunkPayload {
-    text: String,
-    done: bool,
-}
-
 #[tauri::command]
 async fn send_claude_message(
     app: tauri::AppHandle,
@@ -686,10 +1243,7 @@ async fn send_claude_message(
                     session_id_out = Some(sid.to_string());
                 }
                 // Final result — done
-                let _ = app_clone.emit("hw-chat-chunk", ChatChunkPayload {
-                    text: String::new(),
-                    done: true,
-                });
+                events::emit(&app_clone, events::ChatChunk { text: String::new(), done: true });
                 break;
             }
 
@@ -700,10 +1254,7 @@ async fn send_claude_message(
                         if block["type"].as_str() == Some("text") {
                             if let Some(text) = block["text"].as_str() {
                                 full_text.push_str(text);
-                                let _ = app_clone.emit("hw-chat-chunk", ChatChunkPayload {
-                                    text: text.to_string(),
-                                    done: false,
-                                });
+                                events::emit(&app_clone, events::ChatChunk { text: text.to_string(), done: false });
                             }
                         }
                     }
@@ -729,7 +1280,7 @@ async fn send_claude_message(
         *CHAT_SESSION_ID.lock().map_err(|_| "Lock poisoned")? = Some(sid);
     }
 
-    // Write complete response to chat-out.json (file watcher fires → UI refetches full history)
+    // Journal the complete response (file watcher fires → UI refetches full history)
     append_chat_message_internal(&proj, "assistant", &response_text)
 }
 
@@ -793,7 +1344,7 @@ fn strip_ansi(input: &[u8]) -> String {
 fn should_emit_pty_line(line: &str) -> bool {
     let t = line.trim();
     let char_len = t.chars().count();
-    if char_len < 4 || char_len > 150 { return false; }
+    if !(4..=150).contains(&char_len) { return false; }
     if t.starts_with('{') || t.starts_with('[') { return false; }
     if t.contains("<tool_") || t.contains("</") { return false; }
     if !t.chars().any(|c| c.is_alphanumeric()) { return false; }
@@ -808,66 +1359,69 @@ fn should_emit_pty_line(line: &str) -> bool {
 }
 
 struct PtyState {
+    // Bumped per spawn so a dying reader thread only clears its own session
+    generation: u64,
     writer: Box<dyn Write + Send>,
     master: Box<dyn portable_pty::MasterPty + Send>,
+    child: Box<dyn portable_pty::Child + Send + Sync>,
 }
 
 static PTY_STATE: Mutex<Option<PtyState>> = Mutex::new(None);
 
-fn build_project_context(project_path: &str) -> String {
-    let config = read_json_file(project_path, "config.json").ok();
-    let state = read_json_file(project_path, "state.json").ok();
-    let workflow = read_json_file(project_path, "workflow.json").ok();
-
-    let name = config.as_ref()
-        .and_then(|c| c["config"]["name"].as_str())
-        .unwrap_or("Unknown Project");
-
-    let phase = workflow.as_ref()
-        .and_then(|w| w["phase"].as_str())
-        .unwrap_or("idle");
-
-    let active_tasks: Vec<String> = state.as_ref()
-        .and_then(|s| s["tasks"].as_array())
-        .map(|tasks| {
-            tasks.iter()
-                .filter(|t| matches!(t["status"].as_str(), Some("in_progress") | Some("todo")))
-                .take(5)
-                .filter_map(|t| {
-                    t["title"].as_str().map(|title| {
-                        format!("- [{}] {}", t["status"].as_str().unwrap_or("?"), title)
-                    })
-                })
-                .collect()
-        })
-        .unwrap_or_default();
+/// System prompt for a terminal opened without a project.
+const PTY_SYSTEM_PROMPT: &str = "You are Claude, the autonomous AI CEO. You have access to hw_* MCP tools. Act autonomously. Report outcomes to Pat.";
+static PTY_GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
 
-    let open_questions: Vec<String> = state.as_ref()
-        .and_then(|s| s["questions"].as_array())
-        .map(|qs| {
-            qs.iter()
-                .filter(|q| q["status"].as_str() == Some("open"))
-                .take(3)
-                .filter_map(|q| q["question"].as_str().map(|s| format!("- {}", s)))
-                .collect()
-        })
-        .unwrap_or_default();
+/// Load everything the context builder needs. Unreadable files just leave
+/// their section empty — a briefing with gaps beats no session.
+fn gather_context_sources(project_path: &str) -> (context::ContextSources, ContextSettings) {
+    fn load<T: DeserializeOwned + Default>(store: &FsStore, file_name: &str) -> T {
+        store.read_or_default(file_name).map(|v| v.data).unwrap_or_default()
+    }
 
-    let mut ctx = format!(
-        "You are Claude, the autonomous AI CEO. Project: '{}' at {}. Workflow phase: {}.",
-        name, project_path, phase
-    );
+    let store = FsStore::new(project_path);
 
-    if !active_tasks.is_empty() {
-        ctx.push_str(&format!("\n\nActive tasks:\n{}", active_tasks.join("\n")));
-    }
+    let config = store.read::<ConfigFile>("config.json").ok().map(|c| c.data.config);
+    let workflow: WorkflowState = load(&store, "workflow.json");
+    let handoff = store.read::<Value>("restart-handoff.json")
+        .ok()
+        .and_then(|v| v.data["message"].as_str().map(String::from));
+
+    let sources = context::ContextSources {
+        project_name: config.as_ref().map(|c| c.name.clone()).unwrap_or_else(|| "Unknown Project".to_string()),
+        project_path: project_path.to_string(),
+        phase: serde_json::to_value(workflow.phase)
+            .ok()
+            .and_then(|v| v.as_str().map(String::from))
+            .unwrap_or_else(|| "idle".to_string()),
+        tasks: load::<TasksFile>(&store, "tasks.json").tasks,
+        decisions: load::<DecisionsFile>(&store, "decisions.json").decisions,
+        questions: load::<QuestionsFile>(&store, "questions.json").questions,
+        direction: load(&store, "direction.json"),
+        pending_approvals: load::<ApprovalsFile>(&store, "approvals.json").pending,
+        memories: load::<MemoriesFile>(&store, "memories.json").memories,
+        handoff,
+    };
+    (sources, config.and_then(|c| c.context).unwrap_or_default())
+}
 
-    if !open_questions.is_empty() {
-        ctx.push_str(&format!("\n\nOpen questions:\n{}", open_questions.join("\n")));
+/// Preview the briefing new sessions get (the terminal caps the budget at
+/// context::COMMAND_LINE_TOKEN_LIMIT). `token_budget` / `truncation`
+/// override config.context for this call only.
+#[tauri::command]
+fn get_project_context(
+    project_path: &str,
+    token_budget: Option<usize>,
+    truncation: Option<TruncationStrategy>,
+) -> context::ProjectContext {
+    let (sources, mut settings) = gather_context_sources(project_path);
+    if let Some(budget) = token_budget {
+        settings.token_budget = budget;
     }
-
-    ctx.push_str("\n\nYou have access to hw_* MCP tools. Act autonomously. Report outcomes to Pat.");
-    ctx
+    if let Some(strategy) = truncation {
+        settings.truncation = strategy;
+    }
+    context::build(&sources, &settings)
 }
 
 #[tauri::command]
@@ -888,12 +1442,22 @@ fn start_pty_session(app: tauri::AppHandle, project_path: Option<String>) -> Res
 
     let mut cmd = CommandBuilder::new("cmd");
 
-    // helloworld.cmd already injects the CEO system prompt — don't double up
-    cmd.args(["/c", "helloworld"]);
+    // Same delivery as send_claude_message: the briefing is appended to the
+    // system prompt, and `cmd /c` finds npm's claude shim on PATH
+    let prompt = match project_path.as_deref() {
+        Some(path) => {
+            let (sources, mut settings) = gather_context_sources(path);
+            settings.token_budget = settings.token_budget.min(context::COMMAND_LINE_TOKEN_LIMIT);
+            context::build(&sources, &settings).text
+        }
+        None => PTY_SYSTEM_PROMPT.to_string(),
+    };
+    cmd.args(["/c", "claude", "--append-system-prompt"]);
+    cmd.arg(context::command_line_arg(&prompt));
 
-    cmd.cwd(&home);
+    cmd.cwd(project_path.as_deref().unwrap_or(&home));
 
-    pty_pair.slave
+    let child = pty_pair.slave
         .spawn_command(cmd)
         .map_err(|e| format!("Spawn failed: {e}"))?;
 
@@ -907,9 +1471,12 @@ fn start_pty_session(app: tauri::AppHandle, project_path: Option<String>) -> Res
 
     // Set state BEFORE spawning thread — prevents race where thread clears state
     // before we've written it, causing respawn checks to fail
+    let generation = PTY_GENERATION.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
     *PTY_STATE.lock().map_err(|_| "Lock poisoned")? = Some(PtyState {
+        generation,
         writer,
         master: pty_pair.master,
+        child,
     });
 
     // Background thread: stream raw PTY output to frontend + extract lines for Buddy feed
@@ -923,16 +1490,24 @@ fn start_pty_session(app: tauri::AppHandle, project_path: Option<String>) -> Res
         loop {
             match reader.read(&mut buf) {
                 Ok(0) | Err(_) => {
-                    if let Ok(mut guard) = PTY_STATE.lock() {
-                        *guard = None;
+                    // A session torn down by stop_pty_session is already gone
+                    // (or replaced) — only report deaths nobody asked for
+                    let died_on_its_own = match PTY_STATE.lock() {
+                        Ok(mut guard) if guard.as_ref().is_some_and(|s| s.generation == generation) => {
+                            *guard = None;
+                            true
+                        }
+                        _ => false,
+                    };
+                    if died_on_its_own {
+                        events::emit(&app, events::PtyDied);
                     }
-                    let _ = app.emit("pty-died", ());
                     break;
                 }
                 Ok(n) => {
                     // Emit raw bytes to terminal view (unchanged)
                     let encoded = base64_encode(&buf[..n]);
-                    let _ = app.emit("pty-data", encoded);
+                    events::emit(&app, events::PtyData(encoded));
 
                     // Extract clean lines for Buddy feed
                     for &byte in &buf[..n] {
@@ -945,7 +1520,7 @@ fn start_pty_session(app: tauri::AppHandle, project_path: Option<String>) -> Res
                                     let elapsed = now.duration_since(last_emit).as_millis();
                                     let is_dup = display == last_line && elapsed < 500;
                                     if !is_dup && elapsed >= 30 {
-                                        let _ = app.emit("hw-pty-line", &display);
+                                        events::emit(&app, events::PtyLine(display.clone()));
                                         last_line = display;
                                         last_emit = now;
                                     }
@@ -964,6 +1539,18 @@ fn start_pty_session(app: tauri::AppHandle, project_path: Option<String>) -> Res
     Ok(true)
 }
 
+/// Kill the running PTY session, if any. Returns whether one was running.
+fn stop_pty_session() -> Result<bool, String> {
+    let state = PTY_STATE.lock().map_err(|_| "Lock poisoned")?.take();
+    match state {
+        Some(mut state) => {
+            let _ = state.child.kill();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
 #[tauri::command]
 fn write_pty_input(data: String) -> Result<(), String> {
     let mut guard = PTY_STATE.lock().map_err(|_| "Lock poisoned")?;
@@ -988,12 +1575,12 @@ fn resize_pty(rows: u16, cols: u16) -> Result<(), String> {
 // Minimal base64 encoder (avoids adding a dep)
 fn base64_encode(data: &[u8]) -> String {
     const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
     for chunk in data.chunks(3) {
         let b0 = chunk[0] as usize;
         let b1 = if chunk.len() > 1 { chunk[1] as usize } else { 0 };
         let b2 = if chunk.len() > 2 { chunk[2] as usize } else { 0 };
-        out.push(CHARS[(b0 >> 2)] as char);
+        out.push(CHARS[b0 >> 2] as char);
         out.push(CHARS[((b0 & 3) << 4) | (b1 >> 4)] as char);
         out.push(if chunk.len() > 1 { CHARS[((b1 & 15) << 2) | (b2 >> 6)] as char } else { '=' });
         out.push(if chunk.len() > 2 { CHARS[b2 & 63] as char } else { '=' });
@@ -1001,114 +1588,17 @@ fn base64_encode(data: &[u8]) -> String {
     out
 }
 
-// ── Loopback HTTP notify listener ────────────────────────────────
-//
-// MCP server POSTs to http://127.0.0.1:<port>/notify after every tool call.
-// Body: { "files": ["state.json", ...], "tool": "hw_add_task", "summary": "..." }
-// We emit hw-files-changed (for tab refresh) and hw-tool-summary (for buddy).
-// Port is written to .hello-world/sync.json so the MCP server can discover it.
-
-fn start_notify_listener(app: tauri::AppHandle, project_path: String) {
-    use std::net::TcpListener;
-    use std::io::{BufRead, BufReader, Read, Write};
-
-    std::thread::spawn(move || {
-        let listener = match TcpListener::bind("127.0.0.1:0") {
-            Ok(l) => l,
-            Err(_) => return,
-        };
-
-        let port = match listener.local_addr() {
-            Ok(addr) => addr.port(),
-            Err(_) => return,
-        };
-
-        // Write port + pid to sync.json so MCP server can discover us
-        let pid = std::process::id();
-        let sync = serde_json::json!({ "port": port, "pid": pid });
-        let sync_path = PathBuf::from(&project_path).join(".hello-world").join("sync.json");
-        if let Ok(contents) = serde_json::to_string_pretty(&sync) {
-            let _ = fs::write(&sync_path, contents);
-        }
-
-        for stream in listener.incoming() {
-            let Ok(mut stream) = stream else { continue };
-            let app_handle = app.clone();
-
-            std::thread::spawn(move || {
-                let mut reader = BufReader::new(&stream);
-
-                // Read HTTP request line: "POST /path HTTP/1.1"
-                let mut request_line = String::new();
-                if reader.read_line(&mut request_line).is_err() { return; }
-                let request_line = request_line.trim().to_string();
-
-                let method = request_line
-                    .split_whitespace()
-                    .next()
-                    .unwrap_or("POST")
-                    .to_uppercase();
-                let path = request_line
-                    .split_whitespace()
-                    .nth(1)
-                    .unwrap_or("/")
-                    .to_string();
-
-                // Handle CORS preflight (OPTIONS)
-                if method == "OPTIONS" {
-                    let resp = "HTTP/1.1 204 No Content\r\n\
-                        Access-Control-Allow-Origin: *\r\n\
-                        Access-Control-Allow-Methods: POST, OPTIONS\r\n\
-                        Access-Control-Allow-Headers: Content-Type\r\n\
-                        Access-Control-Max-Age: 86400\r\n\r\n";
-                    let _ = stream.write_all(resp.as_bytes());
-                    return;
-                }
-
-                // Read headers until blank line
-                let mut content_length: usize = 0;
-                loop {
-                    let mut line = String::new();
-                    if reader.read_line(&mut line).is_err() { return; }
-                    let line = line.trim_end_matches(|c| c == '\r' || c == '\n').to_string();
-                    if line.is_empty() { break; }
-                    let lower = line.to_lowercase();
-                    if lower.starts_with("content-length:") {
-                        content_length = lower["content-length:".len()..].trim().parse().unwrap_or(0);
-                    }
-                }
+// ── File watcher ─────────────────────────────────────────────────
 
-                // Read body
-                let payload: Value = if content_length > 0 {
-                    let mut body = vec![0u8; content_length];
-                    if reader.read_exact(&mut body).is_err() {
-                        return;
-                    }
-                    serde_json::from_slice(&body).unwrap_or(serde_json::json!({}))
-                } else {
-                    serde_json::json!({})
-                };
-
-                // ── Route by path ──────────────────────────────
-                // Default: notify handler
-                if let Some(files) = payload["files"].as_array() {
-                    let names: Vec<String> = files.iter()
-                        .filter_map(|f| f.as_str().map(String::from))
-                        .collect();
-                    if !names.is_empty() {
-                        let _ = app_handle.emit("hw-files-changed", &names);
-                    }
-                }
-                if payload["summary"].is_string() {
-                    let _ = app_handle.emit("hw-tool-summary", &payload);
-                }
-                let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
-            });
-        }
-    });
+/// Everything tied to the open project: dropping the watch stops change
+/// events, `listener.stop()` ends the notify server.
+struct ProjectRuntime {
+    project_path: String,
+    _watch: store::Watch,
+    listener: notify_server::NotifyListener,
 }
 
-// ── File watcher ─────────────────────────────────────────────────
+static PROJECT_RUNTIME: Mutex<Option<ProjectRuntime>> = Mutex::new(None);
 
 #[tauri::command]
 fn start_watching(app: tauri::AppHandle, project_path: String) -> Result<(), String> {
@@ -1118,51 +1608,77 @@ fn start_watching(app: tauri::AppHandle, project_path: String) -> Result<(), Str
         return Err(format!("{} does not exist", watch_path.display()));
     }
 
-    // Start the loopback HTTP listener for MCP server notifications
-    start_notify_listener(app.clone(), project_path.clone());
+    let mut runtime = PROJECT_RUNTIME.lock().map_err(|_| "Lock poisoned")?;
+    // Idempotent for the current project. Any other project is only torn
+    // down once the new one is fully up, so a failed switch changes nothing.
+    if runtime.as_ref().is_some_and(|r| r.project_path == project_path) {
+        return Ok(());
+    }
+    let next = open_runtime(&app, project_path.clone())?;
+    if let Some(old) = runtime.replace(next) {
+        old.listener.stop();
+    }
+    drop(runtime);
 
+    spawn_consolidation_schedule(app, project_path.clone());
     std::thread::spawn(move || {
-        let app_handle = app.clone();
-        let (tx, rx) = std::sync::mpsc::channel();
-
-        let mut debouncer = match new_debouncer(Duration::from_millis(500), tx) {
-            Ok(d) => d,
-            Err(_) => return,
-        };
-
-        if debouncer
-            .watcher()
-            .watch(&watch_path, notify::RecursiveMode::NonRecursive)
-            .is_err()
-        {
-            return;
+        // Catch up on anything appended while the app was closed
+        let _ = activity::rotate(&project_path);
+    });
+    Ok(())
+}
+
+fn open_runtime(app: &tauri::AppHandle, project_path: String) -> Result<ProjectRuntime, String> {
+    // Upgrade older layouts before anything reads them
+    let migration = migrate::migrate_project(&project_path)?;
+    if !migration.applied.is_empty() {
+        events::emit(app, migration.clone());
+        events::emit(app, events::FilesChanged(migration.files));
+    }
+
+    // Journals left over from the last run; activity.json is compacted by rotation
+    let _ = journal::compact::<ChatHistory>(&project_path, "chat-out.json");
+    let _ = journal::compact::<ChatroomState>(&project_path, "chatroom.json");
+    if storage_backend(&project_path) == StorageBackend::Sqlite {
+        // Catch up on anything the MCP server wrote while the app was closed.
+        // Reads fall back to the JSON if this fails, so the project still opens.
+        if let Err(e) = sqlite::import_project(&project_path) {
+            events::emit(app, events::StorageError(e));
         }
+    }
 
-        loop {
-            match rx.recv() {
-                Ok(Ok(events)) => {
-                    let mut changed_files: Vec<String> = Vec::new();
-                    for event in events {
-                        if event.kind == DebouncedEventKind::Any {
-                            if let Some(name) = event.path.file_name() {
-                                let name_str = name.to_string_lossy().to_string();
-                                if (name_str.ends_with(".json") || name_str.ends_with(".md")) && !changed_files.contains(&name_str) {
-                                    changed_files.push(name_str);
-                                }
-                            }
-                        }
-                    }
-                    if !changed_files.is_empty() {
-                        let _ = app_handle.emit("hw-files-changed", &changed_files);
-                    }
+    let watch_project = project_path.clone();
+    let watch_app = app.clone();
+    let watch = FsStore::new(&project_path).watch(Box::new(move |changed_files| {
+        if changed_files.iter().any(|f| f == "activity.json") {
+            // Best effort: an unrotated tail is only slower to page
+            let _ = activity::rotate(&watch_project);
+        }
+        if storage_backend(&watch_project) == StorageBackend::Sqlite {
+            for file in changed_files.iter().filter(|f| sqlite::is_collection(f)) {
+                if let Err(e) = sqlite::sync_file(&watch_project, file) {
+                    events::emit(&watch_app, events::StorageError(e));
                 }
-                Ok(Err(_)) => {}
-                Err(_) => break,
             }
         }
-    });
+        events::emit(&watch_app, events::FilesChanged(changed_files.to_vec()));
+    }))?;
 
-    Ok(())
+    // Start the loopback HTTP listener for MCP server notifications
+    let listener = notify_server::start(app.clone(), project_path.clone())?;
+    Ok(ProjectRuntime { project_path, _watch: watch, listener })
+}
+
+/// Stamp endedAt on the latest session so sentinel knows this was a clean exit.
+fn stamp_session_end(project_path: &str) -> Result<(), String> {
+    FsStore::new(project_path).update("sessions.json", None, |data: &mut SessionsFile| {
+        if let Some(latest) = data.sessions.last_mut() {
+            if latest.ended_at.is_none() {
+                latest.ended_at = Some(utc_now_iso());
+            }
+        }
+        Ok(())
+    })
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -1173,13 +1689,21 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             get_app_project_path,
             set_app_project_path,
+            get_event_contract_version,
             get_config,
             save_config,
             get_state,
             get_memories,
+            list_memories,
+            recall_memories,
+            update_memory,
+            merge_memories,
+            delete_memory,
+            consolidate_memories,
             get_sessions,
             get_brain_state,
             get_activity,
+            get_activity_page,
             get_approvals,
             get_workflow,
             get_direction,
@@ -1204,30 +1728,39 @@ pub fn run() {
             start_watching,
             get_capabilities,
             resolve_approval,
+            ask_question,
             answer_question,
+            defer_question,
+            dismiss_question,
+            reopen_question,
+            link_question,
+            add_task,
+            update_task,
+            delete_task,
+            reorder_tasks,
+            get_task_graph,
+            record_decision,
+            amend_decision,
+            supersede_decision,
+            get_decision_history,
+            query_decisions,
+            list_projects,
+            add_project,
+            remove_project,
+            rename_project,
+            set_project_pinned,
+            switch_project,
+            init_project,
+            migrate_project,
+            import_sqlite,
+            export_sqlite,
+            get_project_context,
         ])
         .on_window_event(|window, event| {
             if window.label() == "main" {
                 if let tauri::WindowEvent::CloseRequested { .. } = event {
-                    // Stamp endedAt on the latest session so sentinel knows this was a clean exit
-                    if let Some(project_path) = get_app_project_path() {
-                        let sessions_path = PathBuf::from(&project_path)
-                            .join(".hello-world")
-                            .join("sessions.json");
-                        if let Ok(contents) = fs::read_to_string(&sessions_path) {
-                            if let Ok(mut data) = serde_json::from_str::<Value>(&contents) {
-                                if let Some(sessions) = data.get_mut("sessions").and_then(|s| s.as_array_mut()) {
-                                    if let Some(latest) = sessions.last_mut() {
-                                        if latest.get("endedAt").and_then(|v| v.as_str()).is_none() {
-                                            latest["endedAt"] = Value::String(utc_now_iso());
-                                            if let Ok(out) = serde_json::to_string_pretty(&data) {
-                                                let _ = fs::write(&sessions_path, out);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+                    if let Ok(Some(project_path)) = get_app_project_path() {
+                        let _ = stamp_session_end(&project_path);
                     }
                 }
                 if let tauri::WindowEvent::Destroyed = event {
@@ -0,0 +1,240 @@
+// ── Cross-process advisory locks for .hello-world files ──────────
+//
+// Protocol (shared with JsonStore.update in packages/core, via
+// packages/core/src/lock.ts, which is what the MCP server writes through):
+// - To read-modify-write `<file>`, exclusively create `<file>.lock`
+//   (O_CREAT|O_EXCL — `fs.openSync(path, 'wx')` in Node).
+// - The lock file holds `{ pid, token, acquiredAt }` for diagnostics.
+// - Delete it when done. Holders must finish well within STALE_AFTER.
+// - A lock whose mtime is older than STALE_AFTER belongs to a crashed
+//   writer and may be broken by anyone — by renaming it to a unique name and
+//   re-checking the renamed file, never by unlinking `<file>.lock` directly.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long a mutating command waits for a lock before giving up.
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+/// Age after which a lock is assumed abandoned.
+pub const STALE_AFTER: Duration = Duration::from_secs(30);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Held lock; released when dropped.
+pub struct FileLock {
+    path: PathBuf,
+    token: String,
+}
+
+pub fn lock_path(target: &Path) -> PathBuf {
+    let mut name = target.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    target.with_file_name(name)
+}
+
+impl FileLock {
+    pub fn acquire(target: &Path) -> Result<FileLock, String> {
+        Self::acquire_with(target, LOCK_TIMEOUT, STALE_AFTER)
+    }
+
+    pub fn acquire_with(target: &Path, timeout: Duration, stale_after: Duration) -> Result<FileLock, String> {
+        let path = lock_path(target);
+        let token = format!("{}-{}", std::process::id(), unique_suffix());
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let info = serde_json::json!({
+                        "pid": std::process::id(),
+                        "token": token,
+                        "acquiredAt": crate::utc_now_iso(),
+                    });
+                    let _ = file.write_all(info.to_string().as_bytes());
+                    return Ok(FileLock { path, token });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    if is_stale(&path, stale_after) {
+                        // Crashed holder — take the lock file aside, then make
+                        // sure what we took is the stale one and not a lock
+                        // someone acquired in the meantime
+                        if let Some(claimed) = claim(&path, &token) {
+                            if is_stale(&claimed, stale_after) {
+                                let _ = fs::remove_file(&claimed);
+                            } else {
+                                restore(&claimed, &path);
+                            }
+                        }
+                        continue;
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(format!(
+                            "Timed out after {}ms waiting for lock on {}{}",
+                            timeout.as_millis(),
+                            target.display(),
+                            holder_description(&path),
+                        ));
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+                Err(e) => {
+                    return Err(format!("Failed to create lock {}: {}", path.display(), e));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // Take the lock file aside before checking it: if ours was broken as
+        // stale and re-acquired, deleting it would let a third writer in.
+        let Some(claimed) = claim(&self.path, &self.token) else { return };
+        if read_info(&claimed)["token"].as_str() == Some(self.token.as_str()) {
+            let _ = fs::remove_file(&claimed);
+        } else {
+            restore(&claimed, &self.path);
+        }
+    }
+}
+
+/// Atomically move the lock to a name only this caller knows.
+fn claim(path: &Path, token: &str) -> Option<PathBuf> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", token));
+    let claimed = path.with_file_name(name);
+    fs::rename(path, &claimed).ok().map(|_| claimed)
+}
+
+/// Put back a lock we shouldn't have taken, unless the slot was refilled.
+fn restore(claimed: &Path, path: &Path) {
+    // hard_link fails if `path` exists, so a newer lock is never replaced
+    let _ = fs::hard_link(claimed, path);
+    let _ = fs::remove_file(claimed);
+}
+
+fn read_info(path: &Path) -> serde_json::Value {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn is_stale(path: &Path, stale_after: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map(|age| age > stale_after)
+        .unwrap_or(false)
+}
+
+fn holder_description(path: &Path) -> String {
+    read_info(path)["pid"]
+        .as_u64()
+        .map(|pid| format!(" (held by pid {})", pid))
+        .unwrap_or_default()
+}
+
+fn unique_suffix() -> u128 {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    nanos ^ COUNTER.fetch_add(1, Ordering::Relaxed) as u128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempProject;
+    use std::sync::Arc;
+
+    #[test]
+    fn concurrent_writers_never_lose_updates() {
+        let project = TempProject::new("lock-hammer");
+        let target = Arc::new(project.file("counter.json"));
+        fs::write(&*target, "0").unwrap();
+
+        const THREADS: usize = 8;
+        const ROUNDS: usize = 50;
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let target = Arc::clone(&target);
+                std::thread::spawn(move || {
+                    for _ in 0..ROUNDS {
+                        let _lock = FileLock::acquire_with(&target, Duration::from_secs(30), STALE_AFTER).unwrap();
+                        let n: usize = fs::read_to_string(&*target).unwrap().trim().parse().unwrap();
+                        // Widen the read-modify-write window to provoke lost updates
+                        std::thread::yield_now();
+                        crate::storage::write_atomic(&target, (n + 1).to_string().as_bytes()).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        let total: usize = fs::read_to_string(&*target).unwrap().trim().parse().unwrap();
+        assert_eq!(total, THREADS * ROUNDS);
+        assert!(!lock_path(&target).exists());
+    }
+
+    #[test]
+    fn times_out_while_lock_is_held() {
+        let project = TempProject::new("lock-timeout");
+        let target = project.file("approvals.json");
+        let _held = FileLock::acquire(&target).unwrap();
+
+        let err = FileLock::acquire_with(&target, Duration::from_millis(50), STALE_AFTER)
+            .err()
+            .expect("second acquire should time out");
+        assert!(err.contains("Timed out"), "{}", err);
+        assert!(err.contains(&format!("pid {}", std::process::id())), "{}", err);
+    }
+
+    #[test]
+    fn breaks_stale_lock() {
+        let project = TempProject::new("lock-stale");
+        let target = project.file("questions.json");
+        fs::write(lock_path(&target), r#"{"pid":1,"token":"crashed"}"#).unwrap();
+        std::thread::sleep(Duration::from_millis(30));
+
+        let lock = FileLock::acquire_with(&target, Duration::from_millis(200), Duration::from_millis(10));
+        assert!(lock.is_ok());
+        drop(lock);
+        assert!(!lock_path(&target).exists());
+    }
+
+    #[test]
+    fn does_not_break_a_fresh_lock() {
+        let project = TempProject::new("lock-fresh");
+        let target = project.file("tasks.json");
+        let _held = FileLock::acquire(&target).unwrap();
+
+        let second = FileLock::acquire_with(&target, Duration::from_millis(50), STALE_AFTER);
+        assert!(second.is_err());
+        assert!(lock_path(&target).exists());
+    }
+
+    #[test]
+    fn drop_leaves_someone_elses_lock_alone() {
+        let project = TempProject::new("lock-drop");
+        let target = project.file("tasks.json");
+        let lock = FileLock::acquire(&target).unwrap();
+        // Our lock was broken as stale and another writer took over
+        fs::write(lock_path(&target), r#"{"pid":1,"token":"other"}"#).unwrap();
+
+        drop(lock);
+        let info = read_info(&lock_path(&target));
+        assert_eq!(info["token"], "other");
+        // Nothing left behind under a claimed name
+        assert_eq!(fs::read_dir(project.file("")).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,331 @@
+// ── Memory store management (memories.json) ──────────────────────
+//
+// The Memory tab asks for one page at a time instead of the whole file.
+// Merging follows the brain's own convention: duplicates are folded into the
+// kept memory and marked `supersededBy`, so the pruner archives them later
+// rather than the app deleting them outright.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{LinkRelationship, MemoriesFile, Memory, MemoryLink, MemorySeverity, MemoryType};
+
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryQuery {
+    /// Every word must appear in the title, content, rule or tags.
+    pub text: Option<String>,
+    #[serde(default)]
+    pub types: Vec<MemoryType>,
+    #[serde(default)]
+    pub severities: Vec<MemorySeverity>,
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub include_superseded: bool,
+    #[serde(default)]
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryPage {
+    pub memories: Vec<Memory>,
+    /// Matches across all pages.
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    /// Matches per type ignoring the type filter, for the filter chips.
+    pub type_counts: BTreeMap<String, usize>,
+    pub revision: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryPatch {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub rule: Option<String>,
+    pub tags: Option<Vec<String>>,
+    #[serde(rename = "type")]
+    pub memory_type: Option<MemoryType>,
+    pub severity: Option<MemorySeverity>,
+}
+
+fn type_label(memory_type: MemoryType) -> String {
+    serde_json::to_value(memory_type)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+fn matches_text(memory: &Memory, terms: &[String]) -> bool {
+    if terms.is_empty() {
+        return true;
+    }
+    let haystack = format!("{}\n{}\n{}\n{}", memory.title, memory.content, memory.rule, memory.tags.join(" "))
+        .to_lowercase();
+    terms.iter().all(|term| haystack.contains(term.as_str()))
+}
+
+/// Newest first, matching how the Memory tab has always listed them.
+pub fn list(file: &MemoriesFile, query: &MemoryQuery, revision: String) -> MemoryPage {
+    let terms: Vec<String> = query.text.as_deref().unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    let tag = query.tag.as_deref().map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty());
+
+    let candidates: Vec<&Memory> = file.memories.iter()
+        .rev()
+        .filter(|m| query.include_superseded || m.superseded_by.is_none())
+        .filter(|m| query.severities.is_empty() || query.severities.contains(&m.severity))
+        .filter(|m| tag.as_ref().is_none_or(|tag| m.tags.iter().any(|t| t.to_lowercase() == *tag)))
+        .filter(|m| matches_text(m, &terms))
+        .collect();
+
+    let mut type_counts = BTreeMap::new();
+    for m in &candidates {
+        *type_counts.entry(type_label(m.memory_type)).or_insert(0) += 1;
+    }
+
+    let matching: Vec<&Memory> = candidates.into_iter()
+        .filter(|m| query.types.is_empty() || query.types.contains(&m.memory_type))
+        .collect();
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    MemoryPage {
+        total: matching.len(),
+        memories: matching.into_iter().skip(query.offset).take(limit).cloned().collect(),
+        offset: query.offset,
+        limit,
+        type_counts,
+        revision,
+    }
+}
+
+fn find_mut<'a>(file: &'a mut MemoriesFile, id: &str) -> Result<&'a mut Memory, String> {
+    file.memories.iter_mut().find(|m| m.id == id).ok_or_else(|| format!("Memory not found: {}", id))
+}
+
+pub fn update(file: &mut MemoriesFile, id: &str, patch: MemoryPatch) -> Result<Memory, String> {
+    let memory = find_mut(file, id)?;
+    if let Some(title) = patch.title {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err("Memory title cannot be empty".to_string());
+        }
+        memory.title = title.to_string();
+    }
+    let mut reworded = false;
+    if let Some(content) = patch.content.filter(|c| *c != memory.content) {
+        memory.content = content;
+        reworded = true;
+    }
+    if let Some(rule) = patch.rule.filter(|r| *r != memory.rule) {
+        memory.rule = rule;
+        reworded = true;
+    }
+    if let Some(tags) = patch.tags {
+        let mut seen = HashSet::new();
+        memory.tags = tags.into_iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty() && seen.insert(t.to_lowercase()))
+            .collect();
+    }
+    if let Some(memory_type) = patch.memory_type {
+        memory.memory_type = memory_type;
+    }
+    if let Some(severity) = patch.severity {
+        memory.severity = severity;
+    }
+    // The fingerprint describes the wording; retitling or retagging keeps it
+    if reworded {
+        memory.fingerprint = None;
+    }
+    Ok(memory.clone())
+}
+
+/// Fold `merge_ids` into `keep_id`: tags, links and access history are
+/// combined, text the kept memory doesn't already contain is appended, and
+/// the duplicates are marked superseded with a link back from the survivor.
+pub fn merge(file: &mut MemoriesFile, keep_id: &str, merge_ids: &[String]) -> Result<Memory, String> {
+    if merge_ids.is_empty() {
+        return Err("Nothing to merge".to_string());
+    }
+    let mut unique = HashSet::new();
+    for id in merge_ids {
+        if id == keep_id {
+            return Err(format!("Cannot merge memory {} into itself", id));
+        }
+        if !unique.insert(id.as_str()) {
+            return Err(format!("Memory listed twice: {}", id));
+        }
+    }
+    let keep = find_mut(file, keep_id)?.clone();
+    if let Some(by) = &keep.superseded_by {
+        return Err(format!("Memory {} was already superseded by {}", keep_id, by));
+    }
+    let mut duplicates = Vec::new();
+    for id in merge_ids {
+        let m = find_mut(file, id)?;
+        if let Some(by) = &m.superseded_by {
+            return Err(format!("Memory {} was already superseded by {}", id, by));
+        }
+        duplicates.push(m.clone());
+    }
+
+    let now = crate::utc_now_iso();
+    let mut merged = keep;
+    for dup in &duplicates {
+        for tag in &dup.tags {
+            if !merged.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                merged.tags.push(tag.clone());
+            }
+        }
+        let content = dup.content.trim();
+        if !content.is_empty() && !merged.content.contains(content) {
+            if !merged.content.trim().is_empty() {
+                merged.content.push_str("\n\n");
+            }
+            merged.content.push_str(content);
+        }
+        if merged.rule.trim().is_empty() {
+            merged.rule = dup.rule.clone();
+        }
+        merged.severity = merged.severity.max(dup.severity);
+        merged.synaptic_strength = merged.synaptic_strength.max(dup.synaptic_strength);
+        merged.access_count += dup.access_count;
+        merged.last_accessed = merged.last_accessed.clone().max(dup.last_accessed.clone());
+        for link in &dup.links {
+            let points_inward = link.target_id == merged.id || unique.contains(link.target_id.as_str());
+            if !points_inward && !merged.links.iter().any(|l| l.target_id == link.target_id) {
+                merged.links.push(link.clone());
+            }
+        }
+        merged.links.push(MemoryLink {
+            target_id: dup.id.clone(),
+            relationship: LinkRelationship::Supersedes,
+            created_at: now.clone(),
+        });
+    }
+    merged.fingerprint = None;
+
+    for m in file.memories.iter_mut() {
+        if m.id == merged.id {
+            *m = merged.clone();
+        } else if unique.contains(m.id.as_str()) {
+            m.superseded_by = Some(merged.id.clone());
+        }
+    }
+    Ok(merged)
+}
+
+/// Remove a memory and any links other memories hold to it.
+pub fn delete(file: &mut MemoriesFile, id: &str) -> Result<Memory, String> {
+    let index = file.memories.iter()
+        .position(|m| m.id == id)
+        .ok_or_else(|| format!("Memory not found: {}", id))?;
+    let removed = file.memories.remove(index);
+    for m in file.memories.iter_mut() {
+        m.links.retain(|l| l.target_id != id);
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn memory(id: &str, fields: serde_json::Value) -> Memory {
+        let mut m = json!({
+            "id": id, "projectId": "p", "type": "fact", "title": id, "createdAt": "2025-01-01T00:00:00.000Z",
+        });
+        m.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(m).unwrap()
+    }
+
+    fn file(memories: Vec<Memory>) -> MemoriesFile {
+        MemoriesFile { memories, extra: Default::default() }
+    }
+
+    fn query(value: serde_json::Value) -> MemoryQuery {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn patch(value: serde_json::Value) -> MemoryPatch {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn ids(page: &MemoryPage) -> Vec<&str> {
+        page.memories.iter().map(|m| m.id.as_str()).collect()
+    }
+
+    #[test]
+    fn list_filters_and_counts_types_before_the_type_filter() {
+        let data = file(vec![
+            memory("m_1", json!({ "type": "pain", "content": "Build cache breaks", "tags": ["CI"] })),
+            memory("m_2", json!({ "type": "win", "content": "Build got faster", "severity": "high" })),
+            memory("m_3", json!({ "type": "pain", "content": "Build flakes", "supersededBy": "m_1" })),
+            memory("m_4", json!({ "type": "fact", "content": "Docs live in docs/" })),
+        ]);
+
+        let page = list(&data, &query(json!({ "text": "build", "types": ["pain"] })), "r".to_string());
+        assert_eq!(ids(&page), vec!["m_1"]);
+        assert_eq!(page.total, 1);
+        assert_eq!(page.type_counts.get("pain"), Some(&1));
+        assert_eq!(page.type_counts.get("win"), Some(&1));
+        assert_eq!(page.type_counts.get("fact"), None);
+
+        let page = list(&data, &query(json!({ "text": "build", "includeSuperseded": true })), "r".to_string());
+        assert_eq!(ids(&page), vec!["m_3", "m_2", "m_1"]);
+        assert_eq!(ids(&list(&data, &query(json!({ "tag": "ci" })), "r".to_string())), vec!["m_1"]);
+        assert_eq!(ids(&list(&data, &query(json!({ "severities": ["high"] })), "r".to_string())), vec!["m_2"]);
+
+        let page = list(&data, &query(json!({ "offset": 1, "limit": 1 })), "r".to_string());
+        assert_eq!((ids(&page), page.total), (vec!["m_2"], 3));
+    }
+
+    #[test]
+    fn update_applies_the_patch() {
+        let mut data = file(vec![memory("m_1", json!({ "tags": ["a"] }))]);
+        let updated = update(&mut data, "m_1", patch(json!({
+            "title": "  Renamed  ", "tags": ["x", " X ", "", "y"], "type": "pain", "severity": "high",
+        })))
+        .unwrap();
+        assert_eq!(updated.title, "Renamed");
+        assert_eq!(updated.tags, vec!["x", "y"]);
+        assert_eq!(updated.memory_type, MemoryType::Pain);
+        assert_eq!(updated.severity, MemorySeverity::High);
+
+        assert!(update(&mut data, "m_1", patch(json!({ "title": " " }))).is_err());
+        assert_eq!(update(&mut data, "m_9", MemoryPatch::default()).unwrap_err(), "Memory not found: m_9");
+    }
+
+    #[test]
+    fn update_keeps_the_fingerprint_unless_content_or_rule_changes() {
+        let mut data = file(vec![memory("m_1", json!({ "content": "c", "rule": "r", "fingerprint": "abc" }))]);
+
+        let kept = update(&mut data, "m_1", patch(json!({ "title": "New", "tags": ["t"], "content": "c" }))).unwrap();
+        assert_eq!(kept.fingerprint.as_deref(), Some("abc"));
+
+        let cleared = update(&mut data, "m_1", patch(json!({ "rule": "r2" }))).unwrap();
+        assert_eq!(cleared.fingerprint, None);
+    }
+
+    #[test]
+    fn delete_removes_links_to_the_memory() {
+        let link = json!([{ "targetId": "m_2", "relationship": "related", "createdAt": "2025-01-01T00:00:00.000Z" }]);
+        let mut data = file(vec![memory("m_1", json!({ "links": link })), memory("m_2", json!({}))]);
+
+        assert_eq!(delete(&mut data, "m_2").unwrap().id, "m_2");
+        assert_eq!(data.memories.len(), 1);
+        assert!(data.memories[0].links.is_empty());
+        assert!(delete(&mut data, "m_2").is_err());
+    }
+}
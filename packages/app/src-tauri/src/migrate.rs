@@ -0,0 +1,339 @@
+// ── Schema migrations for .hello-world/ ──────────────────────────
+//
+// config.json carries `config.schemaVersion` (missing = 1). On project open
+// every migration newer than that version runs in order, then the version is
+// stamped — one step at a time, so a crash mid-way resumes where it stopped.
+// Each file is copied to .hello-world/backups/<timestamp>/ before its first
+// change.
+//
+// Migrations work on raw JSON rather than the typed model: old layouts are
+// exactly the ones the model no longer describes.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::lock::FileLock;
+
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
+
+struct Migration {
+    /// Version the project is at after this migration.
+    to: u32,
+    description: &'static str,
+    run: fn(&mut MigrationContext) -> Result<Vec<String>, String>,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        to: 2,
+        description: "Split state.json into tasks.json, decisions.json and questions.json",
+        run: split_state_json,
+    },
+    Migration {
+        to: 3,
+        description: "Store approval timestamps as ISO strings",
+        run: iso_approval_timestamps,
+    },
+];
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub applied: Vec<AppliedMigration>,
+    /// Files written or removed, for hw-files-changed.
+    pub files: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(test, ts(optional))]
+    pub backup_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub description: String,
+    pub changes: Vec<String>,
+}
+
+struct MigrationContext {
+    hw_dir: PathBuf,
+    backup_dir: PathBuf,
+    touched: Vec<String>,
+}
+
+impl MigrationContext {
+    fn path(&self, name: &str) -> PathBuf {
+        self.hw_dir.join(name)
+    }
+
+    fn read(&self, name: &str) -> Result<Option<Value>, String> {
+        let path = self.path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse {}: {}", name, e))
+    }
+
+    /// Copy the original into the backup dir the first time a file is touched.
+    fn backup(&mut self, name: &str) -> Result<(), String> {
+        if self.touched.iter().any(|t| t == name) {
+            return Ok(());
+        }
+        let path = self.path(name);
+        if path.exists() {
+            fs::create_dir_all(&self.backup_dir)
+                .map_err(|e| format!("Failed to create {}: {}", self.backup_dir.display(), e))?;
+            fs::copy(&path, self.backup_dir.join(name))
+                .map_err(|e| format!("Failed to back up {}: {}", name, e))?;
+        }
+        self.touched.push(name.to_string());
+        Ok(())
+    }
+
+    /// Locked read-modify-write; a missing file starts as `{}`. Nothing is
+    /// written unless `f` actually changed the document.
+    fn update<R>(&mut self, name: &str, f: impl FnOnce(&mut Value) -> R) -> Result<R, String> {
+        let _lock = FileLock::acquire(&self.path(name))?;
+        let original = self.read(name)?.unwrap_or_else(|| Value::Object(Default::default()));
+        let mut doc = original.clone();
+        let result = f(&mut doc);
+        if doc != original {
+            self.backup(name)?;
+            let contents = serde_json::to_string_pretty(&doc)
+                .map_err(|e| format!("Failed to serialize: {}", e))?;
+            crate::storage::write_atomic(&self.path(name), contents.as_bytes())?;
+        }
+        Ok(result)
+    }
+
+    fn remove(&mut self, name: &str) -> Result<(), String> {
+        self.backup(name)?;
+        fs::remove_file(self.path(name)).map_err(|e| format!("Failed to remove {}: {}", name, e))
+    }
+}
+
+fn schema_version(config: &Value) -> u32 {
+    config["config"]["schemaVersion"].as_u64().map(|v| v as u32).unwrap_or(1)
+}
+
+fn read_schema_version(hw_dir: &Path) -> Result<u32, String> {
+    let path = hw_dir.join("config.json");
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let config: Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse config.json: {}", e))?;
+    if !config["config"].is_object() {
+        return Err("Failed to parse config.json: missing \"config\" object".to_string());
+    }
+    Ok(schema_version(&config))
+}
+
+/// Bring the project at `project_path` up to CURRENT_SCHEMA_VERSION.
+pub fn migrate_project(project_path: &str) -> Result<MigrationReport, String> {
+    let hw_dir = Path::new(project_path).join(".hello-world");
+    let version = read_schema_version(&hw_dir)?;
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(format!(
+            "{} uses schema version {}, but this app only understands up to {}. Update Hello World.",
+            project_path, version, CURRENT_SCHEMA_VERSION
+        ));
+    }
+
+    let mut report = MigrationReport { from_version: version, to_version: version, ..Default::default() };
+    if version == CURRENT_SCHEMA_VERSION {
+        return Ok(report);
+    }
+
+    // One migrator at a time; re-check in case another instance just finished
+    let _migration_lock = FileLock::acquire(&hw_dir.join("migration"))?;
+    let version = read_schema_version(&hw_dir)?;
+    report.from_version = version;
+    report.to_version = version;
+
+    let stamp = crate::utc_now_iso().replace(':', "-");
+    let mut ctx = MigrationContext {
+        backup_dir: hw_dir.join("backups").join(stamp),
+        hw_dir,
+        touched: Vec::new(),
+    };
+
+    for migration in MIGRATIONS.iter().filter(|m| m.to > version) {
+        let changes = (migration.run)(&mut ctx)
+            .map_err(|e| format!("Migration to schema version {} failed: {}", migration.to, e))?;
+        ctx.update("config.json", |config| {
+            config["config"]["schemaVersion"] = Value::from(migration.to);
+        })?;
+        report.to_version = migration.to;
+        report.applied.push(AppliedMigration {
+            version: migration.to,
+            description: migration.description.to_string(),
+            changes,
+        });
+    }
+
+    if ctx.backup_dir.exists() {
+        report.backup_dir = Some(ctx.backup_dir.to_string_lossy().replace('\\', "/"));
+    }
+    report.files = ctx.touched;
+    Ok(report)
+}
+
+// ── v1 → v2: state.json split ────────────────────────────────────
+//
+// Same rules as StateManager.migrateFromStateJson in packages/core: a split
+// file that already has data wins over state.json.
+
+fn split_state_json(ctx: &mut MigrationContext) -> Result<Vec<String>, String> {
+    // An unreadable state.json fails the migration, so the schema version
+    // stays at 1 and the split is retried once the file is fixed
+    let Some(old) = ctx.read("state.json")? else { return Ok(Vec::new()) };
+
+    let targets: [(&str, &str, &[&str]); 3] = [
+        ("tasks.json", "tasks", &["tasks", "milestones"]),
+        ("decisions.json", "decisions", &["decisions"]),
+        ("questions.json", "questions", &["questions"]),
+    ];
+
+    let mut changes = Vec::new();
+    for (file, primary, keys) in targets {
+        let Some(items) = old[primary].as_array() else { continue };
+        let count = items.len();
+        let change = ctx.update(file, |doc| {
+            if !doc.is_object() {
+                return format!("Skipped {}: not a JSON object", file);
+            }
+            let existing = doc[primary].as_array().map(|a| a.len()).unwrap_or(0);
+            if existing > 0 {
+                return format!("Kept existing {} ({} {}); ignored {} from state.json", file, existing, primary, count);
+            }
+            for key in keys {
+                doc[*key] = old.get(*key).cloned().unwrap_or_else(|| Value::Array(Vec::new()));
+            }
+            format!("Moved {} {} into {}", count, primary, file)
+        })?;
+        changes.push(change);
+    }
+
+    ctx.remove("state.json")?;
+    changes.push("Removed state.json".to_string());
+    Ok(changes)
+}
+
+// ── v2 → v3: ISO approval timestamps ─────────────────────────────
+//
+// Older app builds wrote resolvedAt as epoch milliseconds; the Zod schema
+// (and every other file) uses ISO strings.
+
+fn iso_approval_timestamps(ctx: &mut MigrationContext) -> Result<Vec<String>, String> {
+    if !ctx.path("approvals.json").exists() {
+        return Ok(Vec::new());
+    }
+    let converted = ctx.update("approvals.json", |doc| {
+        let mut converted = 0;
+        for list in ["pending", "resolved"] {
+            let Some(requests) = doc[list].as_array_mut() else { continue };
+            for request in requests.iter_mut().filter(|r| r.is_object()) {
+                for field in ["createdAt", "resolvedAt"] {
+                    if let Some(ms) = request[field].as_u64() {
+                        request[field] = Value::String(crate::iso_from_millis(ms));
+                        converted += 1;
+                    }
+                }
+            }
+        }
+        converted
+    })?;
+    Ok(if converted > 0 {
+        vec![format!("Converted {} approval timestamps in approvals.json", converted)]
+    } else {
+        Vec::new()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempProject;
+
+    fn v1_project(name: &str) -> TempProject {
+        let project = TempProject::new(name);
+        project.write("config.json", r#"{"config":{"name":"demo"}}"#);
+        project
+    }
+
+    fn read(project: &TempProject, name: &str) -> Value {
+        serde_json::from_str(&fs::read_to_string(project.file(name)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn splits_state_json_and_converts_approvals() {
+        let project = v1_project("migrate-full");
+        project.write("state.json", r#"{
+            "tasks": [{"id": "t_1"}], "milestones": [{"id": "m_1"}],
+            "decisions": [{"id": "d_1"}], "questions": []
+        }"#);
+        project.write("approvals.json", r#"{"pending": [], "resolved": [{"id": "a_1", "createdAt": 0, "resolvedAt": 1700000000000}]}"#);
+
+        let report = migrate_project(project.path()).unwrap();
+        assert_eq!((report.from_version, report.to_version), (1, CURRENT_SCHEMA_VERSION));
+        assert_eq!(report.applied.len(), 2);
+
+        assert!(!project.file("state.json").exists());
+        assert_eq!(read(&project, "tasks.json")["milestones"][0]["id"], "m_1");
+        assert_eq!(read(&project, "decisions.json")["decisions"][0]["id"], "d_1");
+        assert_eq!(read(&project, "questions.json")["questions"], serde_json::json!([]));
+        assert_eq!(read(&project, "approvals.json")["resolved"][0]["resolvedAt"], "2023-11-14T22:13:20.000Z");
+        assert_eq!(read(&project, "config.json")["config"]["schemaVersion"], CURRENT_SCHEMA_VERSION);
+        assert_eq!(read(&project, "config.json")["config"]["name"], "demo");
+
+        // Originals are kept in the backup dir
+        let backup_dir = PathBuf::from(report.backup_dir.unwrap());
+        assert!(backup_dir.join("state.json").exists());
+        assert!(backup_dir.join("approvals.json").exists());
+    }
+
+    #[test]
+    fn existing_split_file_wins_over_state_json() {
+        let project = v1_project("migrate-existing");
+        project.write("state.json", r#"{"tasks": [{"id": "t_old"}]}"#);
+        project.write("tasks.json", r#"{"tasks": [{"id": "t_new"}], "milestones": []}"#);
+
+        let report = migrate_project(project.path()).unwrap();
+        assert_eq!(read(&project, "tasks.json")["tasks"][0]["id"], "t_new");
+        assert!(report.applied[0].changes[0].starts_with("Kept existing tasks.json"));
+    }
+
+    #[test]
+    fn unparsable_state_json_keeps_the_schema_version() {
+        let project = v1_project("migrate-broken");
+        project.write("state.json", "{\"tasks\": [");
+
+        let err = migrate_project(project.path()).unwrap_err();
+        assert!(err.contains("schema version 2"), "{}", err);
+        assert!(project.file("state.json").exists());
+        assert!(read(&project, "config.json")["config"].get("schemaVersion").is_none());
+    }
+
+    #[test]
+    fn current_and_newer_projects_are_left_alone() {
+        let project = TempProject::new("migrate-current");
+        project.write("config.json", &format!(r#"{{"config":{{"schemaVersion":{}}}}}"#, CURRENT_SCHEMA_VERSION));
+        let report = migrate_project(project.path()).unwrap();
+        assert!(report.applied.is_empty());
+        assert!(report.files.is_empty());
+
+        project.write("config.json", &format!(r#"{{"config":{{"schemaVersion":{}}}}}"#, CURRENT_SCHEMA_VERSION + 1));
+        assert!(migrate_project(project.path()).unwrap_err().contains("Update Hello World"));
+    }
+}
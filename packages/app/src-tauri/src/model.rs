@@ -0,0 +1,1057 @@
+// ── Typed data model for .hello-world/*.json ─────────────────────
+//
+// Mirrors the Zod schemas in packages/core/src/types.ts (plus the chatroom,
+// watcher and direction shapes the MCP server writes). Every record carries an
+// `extra` map so fields we don't model yet survive a read-modify-write from
+// the desktop app instead of being silently dropped.
+
+use std::collections::BTreeMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+type Extra = Map<String, Value>;
+
+// JS has a single number type, so the Node side writes `1`, not `1.0`.
+// Write integral floats back as integers to keep round-trips diff-free.
+
+fn js_number<S: Serializer>(v: &f64, s: S) -> Result<S::Ok, S::Error> {
+    if v.fract() == 0.0 && v.abs() < 9.007_199_254_740_992e15 {
+        s.serialize_i64(*v as i64)
+    } else {
+        s.serialize_f64(*v)
+    }
+}
+
+fn js_number_opt<S: Serializer>(v: &Option<f64>, s: S) -> Result<S::Ok, S::Error> {
+    match v {
+        Some(n) => js_number(n, s),
+        None => s.serialize_none(),
+    }
+}
+
+fn js_number_map<S: Serializer>(v: &BTreeMap<String, f64>, s: S) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+    let mut map = s.serialize_map(Some(v.len()))?;
+    for (k, n) in v {
+        map.serialize_entry(k, &JsNumber(*n))?;
+    }
+    map.end()
+}
+
+struct JsNumber(f64);
+
+impl Serialize for JsNumber {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        js_number(&self.0, s)
+    }
+}
+
+/// Timestamps in the wild are either ISO 8601 strings (Node side) or epoch
+/// milliseconds (older Rust writes). New writes always use ISO strings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Timestamp {
+    Iso(String),
+    Millis(u64),
+}
+
+impl Timestamp {
+    pub fn now() -> Self {
+        Timestamp::Iso(crate::utc_now_iso())
+    }
+}
+
+/// Parse a document with a path-precise error, e.g.
+/// `tasks.json: tasks[3].status: unknown variant "doing", expected ...`.
+pub fn parse_document<T: DeserializeOwned>(file_name: &str, contents: &str) -> Result<T, String> {
+    let de = &mut serde_json::Deserializer::from_str(contents);
+    serde_path_to_error::deserialize(de).map_err(|e| parse_error(file_name, e))
+}
+
+/// `parse_document` for a document that is already a JSON value.
+pub fn parse_value<T: DeserializeOwned>(file_name: &str, value: Value) -> Result<T, String> {
+    serde_path_to_error::deserialize(value).map_err(|e| parse_error(file_name, e))
+}
+
+fn parse_error(file_name: &str, e: serde_path_to_error::Error<serde_json::Error>) -> String {
+    let path = e.path().to_string();
+    if path == "." {
+        format!("Failed to parse {}: {}", file_name, e.inner())
+    } else {
+        format!("Failed to parse {}: {}: {}", file_name, path, e.inner())
+    }
+}
+
+// ── Config ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigFile {
+    pub config: ProjectConfig,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectConfig {
+    pub name: String,
+    #[serde(default = "default_version")]
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_true")]
+    pub git_integration: bool,
+    #[serde(default = "default_model")]
+    pub default_model: String,
+    #[serde(default = "default_budget", serialize_with = "js_number")]
+    pub daily_budget_usd: f64,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Layout version of the whole .hello-world/ directory; see migrate.rs.
+    /// Absent in projects created before versioning (treated as 1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<ContextSettings>,
+    /// Where the app keeps tasks, decisions, questions, memories and
+    /// activity; see sqlite.rs. Absent means JSON only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<StorageBackend>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Json,
+    Sqlite,
+}
+
+/// `config.context` — how much project state is injected into new sessions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextSettings {
+    #[serde(default = "default_token_budget")]
+    pub token_budget: usize,
+    #[serde(default)]
+    pub truncation: TruncationStrategy,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl Default for ContextSettings {
+    fn default() -> Self {
+        ContextSettings {
+            token_budget: default_token_budget(),
+            truncation: TruncationStrategy::default(),
+            extra: Extra::new(),
+        }
+    }
+}
+
+fn default_token_budget() -> usize {
+    2000
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationStrategy {
+    /// Fill sections in priority order; later sections get what's left.
+    #[default]
+    Priority,
+    /// Split the budget evenly across sections, handing unused share on.
+    Proportional,
+}
+
+fn default_version() -> String {
+    "0.1.0".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_model() -> String {
+    "claude-sonnet-4-6".to_string()
+}
+
+fn default_budget() -> f64 {
+    5.0
+}
+
+// ── Tasks ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    #[default]
+    Todo,
+    InProgress,
+    Done,
+    Blocked,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskSize {
+    S,
+    M,
+    L,
+    Epic,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub status: TaskStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<TaskSize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone_id: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Milestone {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub completed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<String>,
+    pub created_at: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TasksFile {
+    #[serde(default)]
+    pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub milestones: Vec<Milestone>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Decisions ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DecidedBy {
+    Pat,
+    Claude,
+    Both,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alternative {
+    pub option: String,
+    pub tradeoff: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Decision {
+    pub id: String,
+    pub title: String,
+    pub context: String,
+    pub chosen: String,
+    #[serde(default)]
+    pub alternatives: Vec<Alternative>,
+    pub rationale: String,
+    pub decided_at: String,
+    pub decided_by: DecidedBy,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Id of the decision this one replaced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supersedes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_at: Option<String>,
+    /// Earlier versions of this decision, oldest first. Only ever appended to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<DecisionRevision>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// The content of a decision as it stood before an amendment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecisionRevision {
+    pub title: String,
+    pub context: String,
+    pub chosen: String,
+    #[serde(default)]
+    pub alternatives: Vec<Alternative>,
+    pub rationale: String,
+    pub decided_by: DecidedBy,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// When this version was replaced by the amendment.
+    pub amended_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecisionsFile {
+    #[serde(default)]
+    pub decisions: Vec<Decision>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Questions ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionStatus {
+    #[default]
+    Open,
+    Answered,
+    Deferred,
+    Dismissed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Question {
+    pub id: String,
+    pub question: String,
+    #[serde(default)]
+    pub context: String,
+    #[serde(default)]
+    pub status: QuestionStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answered_at: Option<Timestamp>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answered_by: Option<DecidedBy>,
+    /// Answers this question had before being re-answered or reopened, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub answer_history: Vec<PreviousAnswer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dismissed_reason: Option<String>,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linked_task_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linked_decision_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviousAnswer {
+    pub answer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answered_at: Option<Timestamp>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answered_by: Option<DecidedBy>,
+    pub replaced_at: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuestionsFile {
+    #[serde(default)]
+    pub questions: Vec<Question>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Memories ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryType {
+    Pain,
+    Win,
+    Fact,
+    Decision,
+    Architecture,
+    Reflection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemorySeverity {
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkRelationship {
+    Resolves,
+    Supersedes,
+    Extends,
+    Contradicts,
+    Related,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryLink {
+    pub target_id: String,
+    pub relationship: LinkRelationship,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Memory {
+    pub id: String,
+    pub project_id: String,
+    #[serde(rename = "type")]
+    pub memory_type: MemoryType,
+    pub title: String,
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub rule: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub severity: MemorySeverity,
+    #[serde(default = "default_strength", serialize_with = "js_number")]
+    pub synaptic_strength: f64,
+    #[serde(default)]
+    pub access_count: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_accessed: Option<String>,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<MemoryLink>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", serialize_with = "js_number_opt")]
+    pub quality_score: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    /// When consolidation last decayed `synapticStrength`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_decayed_at: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+fn default_strength() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoriesFile {
+    #[serde(default)]
+    pub memories: Vec<Memory>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// consolidate.json: when the app last consolidated memories. Kept out of
+/// activity.json, which rotation trims.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsolidationState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_consolidated_at: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// memories-archive.json, shared with the pruner in packages/core/src/brain/pruner.ts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedMemory {
+    pub memory: Memory,
+    pub reason: String,
+    pub archived_at: String,
+    #[serde(serialize_with = "js_number")]
+    pub score_at_archive: f64,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryArchiveFile {
+    #[serde(default)]
+    pub archived: Vec<ArchivedMemory>,
+    #[serde(default)]
+    pub total_archived: u64,
+    #[serde(default)]
+    pub last_pruned: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Brain state ──────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SynapticHit {
+    pub count: u64,
+    pub last_hit: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryTrace {
+    pub count: u64,
+    pub last_accessed: String,
+    #[serde(serialize_with = "js_number")]
+    pub synaptic_strength: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrainState {
+    pub session_start: String,
+    #[serde(default)]
+    pub message_count: u64,
+    #[serde(default = "default_context_phase")]
+    pub context_phase: String,
+    #[serde(default)]
+    pub synaptic_activity: BTreeMap<String, SynapticHit>,
+    #[serde(default)]
+    pub memory_traces: BTreeMap<String, MemoryTrace>,
+    #[serde(default, serialize_with = "js_number_map")]
+    pub firing_frequency: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub active_traces: Vec<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+fn default_context_phase() -> String {
+    "early".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrainStateFile {
+    pub state: BrainState,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Sessions ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub id: String,
+    pub started_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<String>,
+    #[serde(default)]
+    pub tasks_completed: Vec<String>,
+    #[serde(default)]
+    pub decisions_made: Vec<String>,
+    #[serde(default, serialize_with = "js_number")]
+    pub cost_usd: f64,
+    #[serde(default)]
+    pub tokens_used: u64,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionsFile {
+    #[serde(default)]
+    pub sessions: Vec<Session>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Approvals ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalTier {
+    Auto,
+    Notify,
+    Block,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalStatus {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequest {
+    pub id: String,
+    pub action: String,
+    pub description: String,
+    pub tier: ApprovalTier,
+    #[serde(default)]
+    pub status: ApprovalStatus,
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub context: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<Timestamp>,
+    pub created_at: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApprovalsFile {
+    #[serde(default)]
+    pub pending: Vec<ApprovalRequest>,
+    #[serde(default)]
+    pub resolved: Vec<ApprovalRequest>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Watchers ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatcherStatus {
+    Active,
+    Completed,
+    Failed,
+    Killed,
+    TimedOut,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopySpec {
+    pub from: String,
+    pub to: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatcherConfig {
+    #[serde(default)]
+    pub copies: Vec<CopySpec>,
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub timeout_minutes: u64,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Watcher {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub watcher_type: String,
+    pub label: String,
+    pub pid: u64,
+    pub spawned_at: String,
+    pub status: WatcherStatus,
+    pub config: WatcherConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_summary: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchersFile {
+    #[serde(default)]
+    pub active: Vec<Watcher>,
+    #[serde(default)]
+    pub completed: Vec<Watcher>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Direction ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeEntry {
+    pub area: String,
+    pub decision: String,
+    #[serde(default)]
+    pub rationale: String,
+    pub captured_at: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectionNote {
+    pub id: String,
+    pub text: String,
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub read: bool,
+    pub captured_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_taken: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Direction {
+    #[serde(default)]
+    pub vision: String,
+    #[serde(default)]
+    pub scope: Vec<ScopeEntry>,
+    #[serde(default)]
+    pub notes: Vec<DirectionNote>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Workflow + mode ──────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowPhase {
+    #[default]
+    Idle,
+    Scope,
+    Plan,
+    Build,
+    Verify,
+    Ship,
+    WaitingApproval,
+    Blocked,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowState {
+    #[serde(default)]
+    pub phase: WorkflowPhase,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_task_id: Option<String>,
+    #[serde(default)]
+    pub strikes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_strike_error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autonomous_started_at: Option<String>,
+    #[serde(default, serialize_with = "js_number")]
+    pub context_usage_percent: f64,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModeState {
+    #[serde(default)]
+    pub overdrive: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toggled_at: Option<Timestamp>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toggled_by: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Activity ─────────────────────────────────────────────────────
+
+/// `type` stays a free-form string: the MCP server and chatroom runner emit
+/// many more event kinds than the original ActivityType enum lists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityEvent {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub description: String,
+    #[serde(default)]
+    pub details: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub timestamp: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityFile {
+    #[serde(default)]
+    pub activities: Vec<ActivityEvent>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// activity/index.json: one entry per rotated day segment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityIndex {
+    #[serde(default)]
+    pub segments: Vec<ActivitySegment>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivitySegment {
+    /// File name inside activity/, e.g. `2026-02-25.json`.
+    pub file: String,
+    pub count: usize,
+    pub oldest: String,
+    pub newest: String,
+    #[serde(default)]
+    pub type_counts: BTreeMap<String, usize>,
+}
+
+// ── Chat history (chat-out.json) ─────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatHistoryMessage {
+    pub id: String,
+    pub role: String,
+    pub text: String,
+    pub timestamp: Timestamp,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatHistory {
+    #[serde(default)]
+    pub messages: Vec<ChatHistoryMessage>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Chatroom (deliberations) ─────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatSessionStatus {
+    #[default]
+    Idle,
+    Active,
+    Paused,
+    Concluded,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatParticipant {
+    #[default]
+    Claude,
+    Pat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliberationPhase {
+    Frame,
+    Deliberate,
+    Synthesis,
+    Patinput,
+    Decision,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubQuestion {
+    pub id: u32,
+    pub text: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addressed_by: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceNote {
+    pub agent_id: String,
+    pub risk: String,
+    pub counterbalance: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliberationPlan {
+    #[serde(default)]
+    pub sub_questions: Vec<SubQuestion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance_notes: Option<Vec<BalanceNote>>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatroomSession {
+    pub id: String,
+    pub topic: String,
+    pub status: ChatSessionStatus,
+    pub started_at: String,
+    pub started_by: ChatParticipant,
+    pub waiting_for_input: bool,
+    pub round_number: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_pat_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deliberation_phase: Option<DeliberationPhase>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intro_revealed_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan: Option<DeliberationPlan>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatAgentStatus {
+    Idle,
+    Thinking,
+    Responding,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatAgent {
+    pub id: String,
+    pub name: String,
+    pub color: String,
+    pub status: ChatAgentStatus,
+    #[serde(default)]
+    pub current_thought: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatMessageType {
+    Message,
+    Thinking,
+    System,
+    Pat,
+    Claude,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessage {
+    pub id: String,
+    pub agent_id: String,
+    pub text: String,
+    pub timestamp: String,
+    #[serde(rename = "type")]
+    pub message_type: ChatMessageType,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatReaction {
+    pub id: String,
+    pub agent_id: String,
+    pub emoji: String,
+    pub timestamp: String,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatroomState {
+    pub session: ChatroomSession,
+    #[serde(default)]
+    pub agents: Vec<ChatAgent>,
+    #[serde(default)]
+    pub messages: Vec<ChatMessage>,
+    #[serde(default)]
+    pub reactions: Vec<ChatReaction>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+// ── Revisions ────────────────────────────────────────────────────
+
+/// A document plus the revision it was read at. Pass the revision back as
+/// `expectedRevision` to mutating commands to detect concurrent writes.
+#[derive(Debug, Clone, Serialize)]
+pub struct Versioned<T> {
+    pub revision: String,
+    #[serde(flatten)]
+    pub data: T,
+}
+
+// ── Merged view returned by get_state ────────────────────────────
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectState {
+    pub tasks: Vec<Task>,
+    pub decisions: Vec<Decision>,
+    pub questions: Vec<Question>,
+    pub revisions: StateRevisions,
+}
+
+/// One revision per backing file, since each is written independently.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StateRevisions {
+    pub tasks: String,
+    pub decisions: String,
+    pub questions: String,
+}
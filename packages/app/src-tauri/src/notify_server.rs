@@ -0,0 +1,804 @@
+// ── Loopback HTTP notify listener ────────────────────────────────
+//
+// The MCP server and Claude Code hooks talk to the app over
+// http://127.0.0.1:<port>. The port and a per-launch token are written to
+// .hello-world/sync.json (owner-only) so they can discover it; every request
+// must carry `Authorization: Bearer <token>` or gets a 401. CORS answers only
+// the app's own webview origins, so a web page can't even preflight.
+//
+//   POST /notify             { files, events, summary } after a tool call;
+//                            emits hw-files-changed and hw-tool-summary.
+//   GET  /health             { ok, pid, projectPath, contractVersion }
+//   GET  /state/<name>       { collection, revision, data } for one of
+//                            STATE_COLLECTIONS, read-only.
+//   POST /approval           { action, description, tier?, context?, options? }
+//                            records an approval request, 201 with it.
+//   GET  /events             server-sent events: every streamed event (see
+//                            events.rs) since this listener started, as
+//                            `id`, `event` and JSON `data`.
+//                            With Last-Event-ID, what was missed is replayed
+//                            first, or `hw-events-missed` is sent if it's
+//                            no longer buffered.
+//
+// Anything else is 404, or 405 with an `Allow` header if only the method is
+// wrong. http.rs does the parsing and holds the limits on what clients send.
+
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::events::{self, EventSink, EventStream};
+use crate::http::{self, Request, Response};
+use crate::model::{ApprovalRequest, ApprovalStatus, ApprovalTier, ApprovalsFile, Timestamp};
+use crate::store::{Documents, FsStore, ProjectStore};
+
+/// Collections `GET /state/<name>` will serve. Config and sync.json stay out.
+const STATE_COLLECTIONS: &[&str] = &[
+    "tasks", "decisions", "questions", "approvals", "direction", "memories",
+    "workflow", "sessions", "chatroom", "mode", "watchers",
+];
+
+/// How often an idle event stream gets a comment, which is also how soon
+/// it notices a client that went away.
+const STREAM_PING: Duration = Duration::from_secs(15);
+
+/// The webview's origin per platform, plus the dev server in debug builds.
+fn allowed_origin(origin: &str) -> bool {
+    matches!(origin, "tauri://localhost" | "http://tauri.localhost" | "https://tauri.localhost")
+        || (cfg!(debug_assertions) && origin == "http://localhost:1420")
+}
+
+pub struct NotifyListener {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    stream: Arc<EventStream>,
+    project_path: String,
+}
+
+impl NotifyListener {
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake the blocking accept() so the thread sees the flag and exits
+        let _ = TcpStream::connect(("127.0.0.1", self.port));
+        // ...and any event stream subscribers, likewise
+        self.stream.close();
+        // Don't leave the MCP server pointing at a port nobody listens on
+        let ours = crate::read_json_file(&self.project_path, "sync.json")
+            .map(|v| v["pid"].as_u64() == Some(std::process::id() as u64))
+            .unwrap_or(false);
+        if ours {
+            let _ = std::fs::remove_file(crate::hw_path(&self.project_path, "sync.json"));
+        }
+    }
+}
+
+struct Context {
+    sink: Arc<dyn EventSink>,
+    project_path: String,
+    token: String,
+    stop: Arc<AtomicBool>,
+    /// What `GET /events` serves.
+    stream: Arc<EventStream>,
+}
+
+/// 256 random bits, hex encoded.
+fn new_token() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).map_err(|e| format!("Failed to generate notify token: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+pub fn start(sink: impl EventSink, project_path: String) -> Result<NotifyListener, String> {
+    listen(sink, project_path, http::Limits::default())
+}
+
+fn listen(sink: impl EventSink, project_path: String, limits: http::Limits) -> Result<NotifyListener, String> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .map_err(|e| format!("Failed to start notify listener: {}", e))?;
+    let port = listener.local_addr()
+        .map_err(|e| format!("Failed to start notify listener: {}", e))?
+        .port();
+
+    // Write port + pid + token to sync.json so MCP server can discover us
+    let token = new_token()?;
+    let sync = json!({ "port": port, "pid": std::process::id(), "token": token });
+    let contents = serde_json::to_string_pretty(&sync)
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+    crate::storage::write_private(&crate::hw_path(&project_path, "sync.json"), contents.as_bytes())?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stream = events::open_stream();
+    let ctx = Context {
+        sink: Arc::new(sink),
+        project_path: project_path.clone(),
+        token,
+        stop: stop.clone(),
+        stream: stream.clone(),
+    };
+    http::start(listener, limits, stop.clone(), Arc::new(move |req: &Request| route(&ctx, req)));
+
+    Ok(NotifyListener { port, stop, stream, project_path })
+}
+
+// ── Routes ───────────────────────────────────────────────────────
+
+type Handler = fn(&Context, &Request, &str) -> Response;
+
+fn route(ctx: &Context, req: &Request) -> Response {
+    let origin = req.header("Origin").filter(|o| allowed_origin(o));
+
+    // CORS preflight carries no credentials; the origin is all there is to check
+    if req.method == "OPTIONS" {
+        let Some(origin) = origin else {
+            return Response::error(403, "Origin not allowed");
+        };
+        return Response::empty(204)
+            .with_header("Access-Control-Allow-Origin", origin)
+            .with_header("Access-Control-Allow-Methods", "GET, POST, OPTIONS")
+            .with_header("Access-Control-Allow-Headers", "Authorization, Content-Type")
+            .with_header("Access-Control-Max-Age", "86400")
+            .with_header("Vary", "Origin");
+    }
+
+    let response = if authorized(ctx, req) {
+        dispatch(ctx, req)
+    } else {
+        Response::error(401, "Missing or invalid token").with_header("WWW-Authenticate", "Bearer")
+    };
+    match origin {
+        Some(origin) => response
+            .with_header("Access-Control-Allow-Origin", origin)
+            .with_header("Vary", "Origin"),
+        None => response,
+    }
+}
+
+fn authorized(ctx: &Context, req: &Request) -> bool {
+    let Some(token) = req.header("Authorization").and_then(|v| v.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    // Constant time, so the token can't be guessed a byte at a time
+    token.len() == ctx.token.len()
+        && token.bytes().zip(ctx.token.bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn dispatch(ctx: &Context, req: &Request) -> Response {
+    let path = req.path.split('?').next().unwrap_or("/");
+    let (method, handler): (&str, Handler) = match path {
+        "/notify" => ("POST", notify),
+        "/health" => ("GET", health),
+        "/approval" => ("POST", raise_approval),
+        "/events" => ("GET", event_stream),
+        p if p.starts_with("/state/") => ("GET", state),
+        _ => return Response::error(404, &format!("No route for {}", path)),
+    };
+    if req.method != method {
+        return Response::error(405, &format!("{} does not accept {}", path, req.method))
+            .with_header("Allow", format!("{}, OPTIONS", method));
+    }
+    handler(ctx, req, path)
+}
+
+fn notify(ctx: &Context, req: &Request, _path: &str) -> Response {
+    // Hooks post whatever they have; a body we can't read is just no news
+    let payload: Value = serde_json::from_slice(&req.body).unwrap_or(json!({}));
+    if let Some(files) = payload["files"].as_array() {
+        let names: Vec<String> = files.iter()
+            .filter_map(|f| f.as_str().map(String::from))
+            .collect();
+        if !names.is_empty() {
+            events::emit(&*ctx.sink, events::FilesChanged(names));
+        }
+    }
+    if let Ok(summary) = serde_json::from_value::<events::ToolSummary>(payload) {
+        events::emit(&*ctx.sink, summary);
+    }
+    Response::empty(200)
+}
+
+fn health(ctx: &Context, _req: &Request, _path: &str) -> Response {
+    Response::json(200, &json!({
+        "ok": true,
+        "pid": std::process::id(),
+        "projectPath": ctx.project_path,
+        "contractVersion": events::CONTRACT_VERSION,
+    }))
+}
+
+fn state(ctx: &Context, _req: &Request, path: &str) -> Response {
+    let name = &path["/state/".len()..];
+    if !STATE_COLLECTIONS.contains(&name) {
+        return Response::error(404, &format!("Unknown collection: {}", name));
+    }
+    let collection = format!("{}.json", name);
+    match FsStore::new(&ctx.project_path).get(&collection) {
+        Ok(Some(doc)) => Response::json(200, &json!({
+            "collection": name,
+            "revision": doc.revision,
+            "data": doc.data,
+        })),
+        Ok(None) => Response::error(404, &format!("{} does not exist", collection)),
+        Err(e) => Response::error(500, &e),
+    }
+}
+
+fn event_stream(ctx: &Context, req: &Request, _path: &str) -> Response {
+    // Without Last-Event-ID a subscriber starts from now
+    let mut after = match req.header("Last-Event-ID") {
+        None => ctx.stream.last_id(),
+        Some(id) => match id.parse::<u64>() {
+            Ok(id) => id,
+            Err(_) => return Response::error(400, &format!("Invalid Last-Event-ID: {}", id)),
+        },
+    };
+    let stop = ctx.stop.clone();
+    let stream = ctx.stream.clone();
+
+    Response::stream(200, move |out| {
+        out.write_all(b": connected\n\n")?;
+        out.flush()?;
+        while !stop.load(Ordering::SeqCst) {
+            let since = stream.since(after, STREAM_PING);
+            if since.missed {
+                out.write_all(b"event: hw-events-missed\ndata: {}\n\n")?;
+            }
+            if since.events.is_empty() && !since.missed {
+                out.write_all(b": ping\n\n")?;
+            }
+            for event in &since.events {
+                write!(out, "id: {}\nevent: {}\ndata: {}\n\n", event.id, event.name, event.data)?;
+            }
+            out.flush()?;
+            after = since.latest;
+        }
+        Ok(())
+    })
+    .with_header("Content-Type", "text/event-stream")
+    .with_header("Cache-Control", "no-cache")
+}
+
+#[derive(Deserialize)]
+struct NewApproval {
+    action: String,
+    description: String,
+    /// The MCP server classifies the action; anything unclassified notifies.
+    #[serde(default)]
+    tier: Option<ApprovalTier>,
+    #[serde(default)]
+    context: String,
+    #[serde(default)]
+    options: Vec<String>,
+}
+
+fn raise_approval(ctx: &Context, req: &Request, _path: &str) -> Response {
+    let new: NewApproval = match serde_json::from_slice(&req.body) {
+        Ok(new) => new,
+        Err(e) => return Response::error(400, &format!("Invalid approval request: {}", e)),
+    };
+    if new.action.trim().is_empty() || new.description.trim().is_empty() {
+        return Response::error(400, "Invalid approval request: action and description are required");
+    }
+
+    match record_approval(&FsStore::new(&ctx.project_path), new) {
+        Ok(request) => {
+            events::emit(&*ctx.sink, events::FilesChanged(vec!["approvals.json".to_string()]));
+            Response::json(201, &serde_json::to_value(&request).unwrap_or_default())
+        }
+        Err(e) => Response::error(500, &e),
+    }
+}
+
+/// Same shape as ApprovalGates.requestApproval in core: auto-tier actions
+/// are resolved on the spot, everything else waits in `pending`.
+fn record_approval(store: &dyn ProjectStore, new: NewApproval) -> Result<ApprovalRequest, String> {
+    let tier = new.tier.unwrap_or(ApprovalTier::Notify);
+    let mut request = ApprovalRequest {
+        id: crate::generate_id("apr"),
+        action: new.action,
+        description: new.description,
+        tier,
+        status: ApprovalStatus::Pending,
+        options: new.options,
+        context: new.context,
+        resolution: None,
+        resolved_at: None,
+        created_at: crate::utc_now_iso(),
+        extra: Default::default(),
+    };
+    if tier == ApprovalTier::Auto {
+        request.status = ApprovalStatus::Approved;
+        request.resolution = Some("auto-approved".to_string());
+        request.resolved_at = Some(Timestamp::now());
+    }
+
+    store.update_or_default("approvals.json", None, |data: &mut ApprovalsFile| {
+        if tier == ApprovalTier::Auto {
+            data.resolved.push(request.clone());
+        } else {
+            data.pending.push(request.clone());
+        }
+        Ok(())
+    })?;
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempProject;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<(&'static str, Value)>>);
+
+    impl EventSink for Arc<Recorder> {
+        fn send(&self, name: &'static str, payload: Value) {
+            self.0.lock().unwrap().push((name, payload));
+        }
+    }
+
+    /// A running listener on a temp project, stopped and removed on drop.
+    struct Server {
+        project: TempProject,
+        port: u16,
+        token: String,
+        events: Arc<Recorder>,
+        listener: Option<NotifyListener>,
+    }
+
+    impl Server {
+        fn start(name: &str) -> Self {
+            Server::start_with(name, http::Limits::default())
+        }
+
+        fn start_with(name: &str, limits: http::Limits) -> Self {
+            let project = TempProject::new(name);
+            project.write("tasks.json", r#"{ "tasks": [{ "id": "t_1", "title": "Ship", "status": "todo" }] }"#);
+            let events = Arc::new(Recorder::default());
+            let listener = listen(events.clone(), project.path().to_string(), limits).unwrap();
+            let sync = crate::read_json_file(project.path(), "sync.json").unwrap();
+            let token = sync["token"].as_str().unwrap().to_string();
+            Server { port: listener.port, token, project, events, listener: Some(listener) }
+        }
+
+        fn path(&self) -> &str {
+            self.project.path()
+        }
+
+        fn connect(&self) -> BufReader<TcpStream> {
+            let stream = TcpStream::connect(("127.0.0.1", self.port)).unwrap();
+            stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+            BufReader::new(stream)
+        }
+
+        /// Send a raw request on a new connection and return the status,
+        /// head and body of the response.
+        fn send(&self, raw: &str) -> (u16, String, String) {
+            let mut conn = self.connect();
+            conn.get_mut().write_all(raw.as_bytes()).unwrap();
+            read_response(&mut conn)
+        }
+
+        /// An authorized request.
+        fn request(&self, method: &str, path: &str, body: &str) -> (u16, String, String) {
+            self.send(&self.raw(method, path, "", body))
+        }
+
+        /// A request with exactly `headers` (each ending in CRLF) besides Host.
+        fn request_with(&self, method: &str, path: &str, headers: &str, body: &str) -> (u16, String, String) {
+            self.send(&format!(
+                "{} {} HTTP/1.1\r\nHost: 127.0.0.1\r\n{}Content-Length: {}\r\n\r\n{}",
+                method, path, headers, body.len(), body
+            ))
+        }
+
+        /// An authorized request with `headers` added, as text.
+        fn raw(&self, method: &str, path: &str, headers: &str, body: &str) -> String {
+            format!(
+                "{} {} HTTP/1.1\r\nHost: 127.0.0.1\r\nAuthorization: Bearer {}\r\n{}Content-Length: {}\r\n\r\n{}",
+                method, path, self.token, headers, body.len(), body
+            )
+        }
+
+        fn events(&self) -> Vec<(&'static str, Value)> {
+            self.events.0.lock().unwrap().clone()
+        }
+    }
+
+    fn read_response(conn: &mut BufReader<TcpStream>) -> (u16, String, String) {
+        let mut head = String::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            conn.read_line(&mut line).unwrap();
+            if line.trim().is_empty() { break; }
+            if let Some(length) = line.strip_prefix("Content-Length: ") {
+                content_length = length.trim().parse().unwrap();
+            }
+            head.push_str(&line);
+        }
+        let mut body = vec![0u8; content_length];
+        conn.read_exact(&mut body).unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, head, String::from_utf8(body).unwrap())
+    }
+
+    /// An open `GET /events` stream.
+    struct Subscriber(BufReader<TcpStream>);
+
+    impl Subscriber {
+        fn open(server: &Server, last_event_id: Option<u64>) -> Self {
+            let mut conn = server.connect();
+            let header = last_event_id.map(|id| format!("Last-Event-ID: {}\r\n", id)).unwrap_or_default();
+            conn.get_mut().write_all(server.raw("GET", "/events", &header, "").as_bytes()).unwrap();
+            let mut head = String::new();
+            while !head.ends_with("\r\n\r\n") {
+                assert!(conn.read_line(&mut head).unwrap() > 0, "{}", head);
+            }
+            assert!(head.starts_with("HTTP/1.1 200"), "{}", head);
+            assert!(head.contains("Content-Type: text/event-stream"), "{}", head);
+            let mut subscriber = Subscriber(conn);
+            assert_eq!(subscriber.line(), ": connected");
+            subscriber.line();
+            subscriber
+        }
+
+        fn line(&mut self) -> String {
+            let mut line = String::new();
+            assert!(self.0.read_line(&mut line).unwrap() > 0, "stream closed");
+            line.trim_end_matches('\n').to_string()
+        }
+
+        /// The next event as (id, name, data), skipping comments.
+        fn next(&mut self) -> (Option<u64>, String, Value) {
+            let (mut id, mut name, mut data) = (None, String::new(), Value::Null);
+            loop {
+                let line = self.line();
+                if line.is_empty() && !name.is_empty() {
+                    return (id, name, data);
+                }
+                if let Some(value) = line.strip_prefix("id: ") {
+                    id = Some(value.parse().unwrap());
+                } else if let Some(value) = line.strip_prefix("event: ") {
+                    name = value.to_string();
+                } else if let Some(value) = line.strip_prefix("data: ") {
+                    data = serde_json::from_str(value).unwrap();
+                }
+            }
+        }
+
+        /// Skip to the tool summary saying `summary`; other tests publish too.
+        fn summary(&mut self, summary: &str) -> u64 {
+            loop {
+                let (id, name, data) = self.next();
+                if name == "hw-tool-summary" && data["summary"] == summary {
+                    return id.unwrap();
+                }
+            }
+        }
+    }
+
+    impl Drop for Server {
+        fn drop(&mut self) {
+            if let Some(listener) = self.listener.take() {
+                listener.stop();
+            }
+        }
+    }
+
+    #[test]
+    fn writes_port_to_sync_file() {
+        let server = Server::start("sync");
+        let sync = crate::read_json_file(server.path(), "sync.json").unwrap();
+        assert_eq!(sync["port"], server.port);
+        assert_eq!(server.token.len(), 64);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(crate::hw_path(server.path(), "sync.json")).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let listener = server.listener.as_ref().unwrap();
+        assert_eq!(listener.project_path, server.path());
+    }
+
+    #[test]
+    fn notify_emits_files_and_summary() {
+        let server = Server::start("notify");
+        let body = r#"{"files":["tasks.json"],"summary":"Added task","events":[{"tool":"hw_add_task","summary":"Ship"}]}"#;
+        let (status, _, _) = server.request("POST", "/notify", body);
+        assert_eq!(status, 200);
+
+        let events = server.events();
+        assert_eq!(events[0], ("hw-files-changed", json!(["tasks.json"])));
+        assert_eq!(events[1].0, "hw-tool-summary");
+        assert_eq!(events[1].1["summary"], "Added task");
+    }
+
+    #[test]
+    fn health_reports_project() {
+        let server = Server::start("health");
+        let (status, _, body) = server.request("GET", "/health", "");
+        assert_eq!(status, 200);
+        let body: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["ok"], true);
+        assert_eq!(body["projectPath"], server.path());
+        assert_eq!(body["contractVersion"], events::CONTRACT_VERSION);
+    }
+
+    #[test]
+    fn state_serves_known_collections() {
+        let server = Server::start("state");
+        let (status, _, body) = server.request("GET", "/state/tasks", "");
+        assert_eq!(status, 200);
+        let body: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["data"]["tasks"][0]["id"], "t_1");
+        assert_eq!(body["revision"], crate::current_revision(server.path(), "tasks.json").unwrap());
+
+        assert_eq!(server.request("GET", "/state/decisions", "").0, 404);
+        assert_eq!(server.request("GET", "/state/sync", "").0, 404);
+        assert_eq!(server.request("GET", "/state/../config", "").0, 404);
+    }
+
+    #[test]
+    fn approval_is_recorded_pending() {
+        let server = Server::start("approval");
+        let body = r#"{"action":"git_push","description":"Push to main","tier":"block","options":["yes","no"]}"#;
+        let (status, _, response) = server.request("POST", "/approval", body);
+        assert_eq!(status, 201);
+        let request: ApprovalRequest = serde_json::from_str(&response).unwrap();
+        assert_eq!(request.status, ApprovalStatus::Pending);
+        assert!(request.id.starts_with("apr"));
+
+        let data: ApprovalsFile = crate::read_document(server.path(), "approvals.json").unwrap();
+        assert_eq!(data.pending.len(), 1);
+        assert_eq!(data.pending[0].id, request.id);
+        assert_eq!(data.pending[0].tier, ApprovalTier::Block);
+        assert_eq!(server.events(), vec![("hw-files-changed", json!(["approvals.json"]))]);
+    }
+
+    #[test]
+    fn auto_approval_is_resolved_immediately() {
+        let server = Server::start("auto");
+        let body = r#"{"action":"read_file","description":"Read README","tier":"auto"}"#;
+        assert_eq!(server.request("POST", "/approval", body).0, 201);
+
+        let data: ApprovalsFile = crate::read_document(server.path(), "approvals.json").unwrap();
+        assert!(data.pending.is_empty());
+        assert_eq!(data.resolved[0].status, ApprovalStatus::Approved);
+        assert_eq!(data.resolved[0].resolution.as_deref(), Some("auto-approved"));
+    }
+
+    #[test]
+    fn approval_rejects_bad_bodies() {
+        let server = Server::start("bad-approval");
+        assert_eq!(server.request("POST", "/approval", "not json").0, 400);
+        assert_eq!(server.request("POST", "/approval", r#"{"action":"git_push"}"#).0, 400);
+        assert_eq!(server.request("POST", "/approval", r#"{"action":" ","description":"x"}"#).0, 400);
+        assert!(!crate::hw_path(server.path(), "approvals.json").exists());
+        assert!(server.events().is_empty());
+    }
+
+    #[test]
+    fn unknown_routes_and_methods() {
+        let server = Server::start("routes");
+        assert_eq!(server.request("GET", "/nope", "").0, 404);
+
+        let (status, head, _) = server.request("GET", "/notify", "");
+        assert_eq!(status, 405);
+        assert!(head.contains("Allow: POST, OPTIONS"), "{}", head);
+
+        let (status, head, _) = server.request("DELETE", "/state/tasks", "");
+        assert_eq!(status, 405);
+        assert!(head.contains("Allow: GET, OPTIONS"), "{}", head);
+    }
+
+    #[test]
+    fn requests_need_the_token() {
+        let server = Server::start("token");
+        let body = r#"{"files":["tasks.json"],"summary":"Forged"}"#;
+        let (status, head, _) = server.request_with("POST", "/notify", "", body);
+        assert_eq!(status, 401);
+        assert!(head.contains("WWW-Authenticate: Bearer"), "{}", head);
+
+        let wrong = format!("Authorization: Bearer {}\r\n", "0".repeat(64));
+        assert_eq!(server.request_with("POST", "/notify", &wrong, body).0, 401);
+        assert_eq!(server.request_with("GET", "/health", "Authorization: Bearer \r\n", "").0, 401);
+        assert_eq!(server.request_with("GET", "/state/tasks", "", "").0, 401);
+        assert_eq!(server.request_with("GET", "/events", "", "").0, 401);
+        assert!(server.events().is_empty());
+
+        // A fresh listener gets a fresh token
+        let other = Server::start("token-other");
+        assert_ne!(server.token, other.token);
+    }
+
+    #[test]
+    fn cors_only_answers_the_app() {
+        let server = Server::start("cors");
+        let (status, head, _) = server.request_with("OPTIONS", "/notify", "Origin: tauri://localhost\r\n", "");
+        assert_eq!(status, 204);
+        assert!(head.contains("Access-Control-Allow-Origin: tauri://localhost"), "{}", head);
+        assert!(head.contains("Access-Control-Allow-Headers: Authorization, Content-Type"), "{}", head);
+
+        let (status, head, _) = server.request_with("OPTIONS", "/notify", "Origin: https://example.com\r\n", "");
+        assert_eq!(status, 403);
+        assert!(!head.contains("Access-Control-Allow-Origin"), "{}", head);
+        assert_eq!(server.request_with("OPTIONS", "/notify", "", "").0, 403);
+
+        let auth = format!("Authorization: Bearer {}\r\nOrigin: https://example.com\r\n", server.token);
+        let (status, head, _) = server.request_with("GET", "/health", &auth, "");
+        assert_eq!(status, 200);
+        assert!(!head.contains("Access-Control-Allow-Origin"), "{}", head);
+    }
+
+    #[test]
+    fn malformed_request_line_is_rejected() {
+        let server = Server::start("malformed");
+        assert_eq!(server.send("garbage\r\n\r\n").0, 400);
+        assert_eq!(server.send("POST /notify HTTP/1.1\r\nContent-Length: lots\r\n\r\n").0, 400);
+    }
+
+    #[test]
+    fn keep_alive_serves_several_requests() {
+        let server = Server::start("keep-alive");
+        let mut conn = server.connect();
+        conn.get_mut().write_all(server.raw("GET", "/health", "", "").as_bytes()).unwrap();
+        let (status, head, _) = read_response(&mut conn);
+        assert_eq!(status, 200);
+        assert!(head.contains("Connection: keep-alive"), "{}", head);
+
+        // Two at once, answered in order
+        let pipelined = server.raw("GET", "/state/tasks", "", "") + &server.raw("GET", "/nope", "", "");
+        conn.get_mut().write_all(pipelined.as_bytes()).unwrap();
+        assert_eq!(read_response(&mut conn).0, 200);
+        assert_eq!(read_response(&mut conn).0, 404);
+
+        conn.get_mut().write_all(server.raw("GET", "/health", "Connection: close\r\n", "").as_bytes()).unwrap();
+        let (_, head, _) = read_response(&mut conn);
+        assert!(head.contains("Connection: close"), "{}", head);
+        assert_eq!(conn.read(&mut [0u8; 1]).unwrap(), 0);
+    }
+
+    #[test]
+    fn chunked_notify_is_read() {
+        let server = Server::start("chunked");
+        let body = r#"{"files":["tasks.json"],"summary":"Chunked"}"#;
+        let (first, rest) = body.split_at(10);
+        let raw = format!(
+            "POST /notify HTTP/1.1\r\nAuthorization: Bearer {}\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            server.token, first.len(), first, rest.len(), rest
+        );
+        assert_eq!(server.send(&raw).0, 200);
+        assert_eq!(server.events()[0], ("hw-files-changed", json!(["tasks.json"])));
+    }
+
+    #[test]
+    fn oversized_body_is_refused_unread() {
+        let server = Server::start_with("oversized", http::Limits { max_body: 1024, ..Default::default() });
+        // Only the headers are sent; the answer can't depend on reading the body
+        let raw = format!("POST /notify HTTP/1.1\r\nAuthorization: Bearer {}\r\nContent-Length: 1000000\r\n\r\n", server.token);
+        let (status, head, _) = server.send(&raw);
+        assert_eq!(status, 413);
+        assert!(head.contains("Connection: close"), "{}", head);
+        assert!(server.events().is_empty());
+    }
+
+    #[test]
+    fn slow_request_times_out() {
+        let timeout = Duration::from_millis(200);
+        let server = Server::start_with("slow", http::Limits { request_timeout: timeout, ..Default::default() });
+        let mut conn = server.connect();
+        let started = Instant::now();
+        conn.get_mut().write_all(b"GET /health HTTP/1.1\r\n").unwrap();
+        std::thread::sleep(timeout / 2);
+        conn.get_mut().write_all(b"Host: 127.0.0.1\r\n").unwrap();
+
+        // Closed without an answer once the request's time is up
+        let mut rest = Vec::new();
+        conn.read_to_end(&mut rest).unwrap();
+        assert!(rest.is_empty());
+        assert!(started.elapsed() < timeout * 5, "{:?}", started.elapsed());
+    }
+
+    #[test]
+    fn connections_past_the_queue_get_503() {
+        let limits = http::Limits { workers: 1, queue: 1, request_timeout: Duration::from_secs(2), ..Default::default() };
+        let server = Server::start_with("pool", limits);
+
+        // One connection holds the only worker, the next waits in the queue
+        let mut busy = server.connect();
+        busy.get_mut().write_all(b"GET /health HTTP/1.1\r\n").unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        let mut queued = server.connect();
+        queued.get_mut().write_all(server.raw("GET", "/health", "Connection: close\r\n", "").as_bytes()).unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+
+        let mut rejected = server.connect();
+        assert_eq!(read_response(&mut rejected).0, 503);
+
+        // Both earlier connections are still served once the worker frees up
+        let rest = format!("Authorization: Bearer {}\r\nConnection: close\r\n\r\n", server.token);
+        busy.get_mut().write_all(rest.as_bytes()).unwrap();
+        assert_eq!(read_response(&mut busy).0, 200);
+        assert_eq!(read_response(&mut queued).0, 200);
+    }
+
+    #[test]
+    fn events_stream_live_updates() {
+        let server = Server::start("events");
+        let mut subscriber = Subscriber::open(&server, None);
+        server.request("POST", "/notify", r#"{"files":["events-live.json"],"summary":"events-live"}"#);
+
+        let (files_id, name, _) = loop {
+            let event = subscriber.next();
+            if event.2 == json!(["events-live.json"]) { break event; }
+        };
+        assert_eq!(name, "hw-files-changed");
+        assert!(subscriber.summary("events-live") > files_id.unwrap());
+    }
+
+    #[test]
+    fn events_replay_after_last_event_id() {
+        let server = Server::start("replay");
+        let mut subscriber = Subscriber::open(&server, None);
+        server.request("POST", "/notify", r#"{"summary":"replay-a"}"#);
+        let seen = subscriber.summary("replay-a");
+        drop(subscriber);
+
+        // Published while nobody was listening
+        server.request("POST", "/notify", r#"{"summary":"replay-b"}"#);
+        server.request("POST", "/notify", r#"{"summary":"replay-c"}"#);
+
+        let mut subscriber = Subscriber::open(&server, Some(seen));
+        let mut replayed = Vec::new();
+        while replayed.last().map(String::as_str) != Some("replay-c") {
+            let (id, name, data) = subscriber.next();
+            assert!(id.unwrap() > seen);
+            if name == "hw-tool-summary" && data["summary"].as_str().unwrap_or("").starts_with("replay-") {
+                replayed.push(data["summary"].as_str().unwrap().to_string());
+            }
+        }
+        assert_eq!(replayed, ["replay-b", "replay-c"]);
+    }
+
+    #[test]
+    fn events_from_another_launch_are_reported_missed() {
+        let server = Server::start("missed");
+        let mut subscriber = Subscriber::open(&server, Some(u64::MAX));
+        assert_eq!(subscriber.next().1, "hw-events-missed");
+
+        assert_eq!(server.send(&server.raw("GET", "/events", "Last-Event-ID: soon\r\n", "")).0, 400);
+    }
+
+    #[test]
+    fn stopping_ends_event_streams() {
+        let mut server = Server::start("events-stop");
+        let mut subscriber = Subscriber::open(&server, None);
+        server.listener.take().unwrap().stop();
+
+        // Ends well before the next ping would have woken it
+        subscriber.0.get_ref().set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let mut rest = String::new();
+        subscriber.0.read_to_string(&mut rest).unwrap();
+    }
+
+    #[test]
+    fn event_streams_leave_the_pool_free() {
+        let limits = http::Limits { workers: 1, max_streams: 1, ..Default::default() };
+        let server = Server::start_with("stream-pool", limits);
+        let _subscriber = Subscriber::open(&server, None);
+        assert_eq!(server.request("GET", "/health", "").0, 200);
+
+        // Past max_streams
+        assert_eq!(server.request("GET", "/events", "").0, 503);
+    }
+}
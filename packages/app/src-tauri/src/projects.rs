@@ -0,0 +1,229 @@
+// ── Project registry (~/.hello-world-app.json) ───────────────────
+//
+// {
+//   "projectPath": "C:/code/app",          ← active project (kept for older builds)
+//   "projects": [
+//     { "path": "C:/code/app", "name": "app", "pinned": true, "lastOpenedAt": "..." }
+//   ]
+// }
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    #[serde(default)]
+    pub projects: Vec<ProjectEntry>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectEntry {
+    pub path: String,
+    pub name: String,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_opened_at: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+pub fn app_config_path() -> PathBuf {
+    let home = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".hello-world-app.json")
+}
+
+/// Load the app config. A missing file is an empty registry; a config from
+/// before the registry existed gets its single project registered. A file
+/// that can't be read or parsed is an error, so `save` never overwrites a
+/// registry the user could still repair.
+pub fn load() -> Result<AppConfig, String> {
+    load_from(&app_config_path())
+}
+
+fn load_from(path: &Path) -> Result<AppConfig, String> {
+    let mut config: AppConfig = match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => AppConfig::default(),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    if let Some(active) = config.project_path.clone() {
+        if config.find(&active).is_none() {
+            config.projects.push(ProjectEntry::new(&active));
+        }
+    }
+    Ok(config)
+}
+
+pub fn save(config: &AppConfig) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Serialize error: {}", e))?;
+    crate::storage::write_atomic(&app_config_path(), contents.as_bytes())
+}
+
+pub fn validate_project_dir(project_path: &str) -> Result<(), String> {
+    if !Path::new(project_path).join(".hello-world").exists() {
+        return Err(format!(
+            "Not a Hello World project — .hello-world/ not found at {}",
+            project_path
+        ));
+    }
+    Ok(())
+}
+
+/// Paths are compared without trailing separators and with `\` and `/`
+/// treated alike, so the same folder picked twice isn't registered twice.
+fn same_path(a: &str, b: &str) -> bool {
+    let norm = |p: &str| p.replace('\\', "/").trim_end_matches('/').to_string();
+    if cfg!(windows) {
+        norm(a).eq_ignore_ascii_case(&norm(b))
+    } else {
+        norm(a) == norm(b)
+    }
+}
+
+impl ProjectEntry {
+    pub fn new(path: &str) -> Self {
+        ProjectEntry {
+            path: path.to_string(),
+            name: default_name(path),
+            pinned: false,
+            last_opened_at: None,
+            extra: Map::new(),
+        }
+    }
+}
+
+/// Project name from .hello-world/config.json, falling back to the folder name.
+fn default_name(path: &str) -> String {
+    let config_path = Path::new(path).join(".hello-world").join("config.json");
+    fs::read_to_string(config_path)
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .and_then(|v| v["config"]["name"].as_str().map(String::from))
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| {
+            Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string())
+        })
+}
+
+impl AppConfig {
+    pub fn find(&self, path: &str) -> Option<&ProjectEntry> {
+        self.projects.iter().find(|p| same_path(&p.path, path))
+    }
+
+    fn find_mut(&mut self, path: &str) -> Result<&mut ProjectEntry, String> {
+        self.projects
+            .iter_mut()
+            .find(|p| same_path(&p.path, path))
+            .ok_or_else(|| format!("Project not registered: {}", path))
+    }
+
+    /// Register a project (no-op if already registered) and return its entry.
+    pub fn add(&mut self, path: &str, name: Option<String>) -> ProjectEntry {
+        if self.find(path).is_none() {
+            self.projects.push(ProjectEntry::new(path));
+        }
+        let entry = self.find_mut(path).expect("just registered");
+        if let Some(name) = name.filter(|n| !n.trim().is_empty()) {
+            entry.name = name.trim().to_string();
+        }
+        entry.clone()
+    }
+
+    pub fn remove(&mut self, path: &str) -> Result<(), String> {
+        let before = self.projects.len();
+        self.projects.retain(|p| !same_path(&p.path, path));
+        if self.projects.len() == before {
+            return Err(format!("Project not registered: {}", path));
+        }
+        if self.project_path.as_deref().is_some_and(|active| same_path(active, path)) {
+            self.project_path = None;
+        }
+        Ok(())
+    }
+
+    pub fn rename(&mut self, path: &str, name: &str) -> Result<ProjectEntry, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Project name cannot be empty".to_string());
+        }
+        let entry = self.find_mut(path)?;
+        entry.name = name.to_string();
+        Ok(entry.clone())
+    }
+
+    pub fn set_pinned(&mut self, path: &str, pinned: bool) -> Result<ProjectEntry, String> {
+        let entry = self.find_mut(path)?;
+        entry.pinned = pinned;
+        Ok(entry.clone())
+    }
+
+    /// Make `path` the active project, registering it if needed.
+    pub fn open(&mut self, path: &str) -> ProjectEntry {
+        let mut entry = self.add(path, None);
+        entry.last_opened_at = Some(crate::utc_now_iso());
+        *self.find_mut(path).expect("just registered") = entry.clone();
+        self.project_path = Some(entry.path.clone());
+        entry
+    }
+
+    /// Pinned first, then most recently opened.
+    pub fn sorted(&self) -> Vec<ProjectEntry> {
+        let mut list = self.projects.clone();
+        list.sort_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then_with(|| b.last_opened_at.cmp(&a.last_opened_at))
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempProject;
+
+    #[test]
+    fn missing_registry_is_empty() {
+        let project = TempProject::new("projects-missing");
+        let config = load_from(&project.file("app.json")).unwrap();
+        assert!(config.projects.is_empty());
+        assert!(config.project_path.is_none());
+    }
+
+    #[test]
+    fn malformed_registry_is_an_error() {
+        let project = TempProject::new("projects-malformed");
+        project.write("app.json", r#"{"projects": [{"path": "C:/code/app""#);
+        let err = load_from(&project.file("app.json")).unwrap_err();
+        assert!(err.contains("Failed to parse"), "{}", err);
+    }
+
+    #[test]
+    fn legacy_active_project_is_registered() {
+        let project = TempProject::new("projects-legacy");
+        project.write("app.json", r#"{"projectPath": "C:/code/app", "theme": "dark"}"#);
+        let config = load_from(&project.file("app.json")).unwrap();
+        assert_eq!(config.projects.len(), 1);
+        assert_eq!(config.projects[0].name, "app");
+        assert_eq!(config.extra["theme"], "dark");
+    }
+}
@@ -0,0 +1,185 @@
+// ── Question lifecycle (questions.json) ──────────────────────────
+//
+//   open ──answer──▶ answered ──answer──▶ answered (previous kept)
+//    │  ▲                │
+//    │  └────reopen──────┤
+//    ├──defer──▶ deferred ──answer / dismiss──▶ …
+//    └──dismiss──▶ dismissed ──reopen──▶ open
+//
+// An answer is never thrown away: re-answering or reopening moves the
+// current one into `answerHistory` first.
+
+use crate::model::{DecidedBy, PreviousAnswer, Question, QuestionStatus, QuestionsFile, Timestamp};
+
+fn find_mut<'a>(file: &'a mut QuestionsFile, id: &str) -> Result<&'a mut Question, String> {
+    file.questions.iter_mut().find(|q| q.id == id).ok_or_else(|| format!("Question not found: {}", id))
+}
+
+fn status_label(status: QuestionStatus) -> &'static str {
+    match status {
+        QuestionStatus::Open => "open",
+        QuestionStatus::Answered => "answered",
+        QuestionStatus::Deferred => "deferred",
+        QuestionStatus::Dismissed => "dismissed",
+    }
+}
+
+fn require(q: &Question, allowed: &[QuestionStatus], action: &str) -> Result<(), String> {
+    if allowed.contains(&q.status) {
+        return Ok(());
+    }
+    Err(format!("Cannot {} question {}: it is {}", action, q.id, status_label(q.status)))
+}
+
+/// Move the current answer (if any) into the history.
+fn archive_answer(q: &mut Question) {
+    if let Some(answer) = q.answer.take() {
+        q.answer_history.push(PreviousAnswer {
+            answer,
+            answered_at: q.answered_at.take(),
+            answered_by: q.answered_by.take(),
+            replaced_at: crate::utc_now_iso(),
+            extra: Default::default(),
+        });
+    }
+}
+
+pub fn ask(file: &mut QuestionsFile, question: &str, context: String, linked_task_id: Option<String>) -> Result<Question, String> {
+    let question = question.trim();
+    if question.is_empty() {
+        return Err("Question cannot be empty".to_string());
+    }
+    let id = loop {
+        let id = crate::generate_id("q");
+        if !file.questions.iter().any(|q| q.id == id) {
+            break id;
+        }
+    };
+    let q = Question {
+        id,
+        question: question.to_string(),
+        context,
+        status: QuestionStatus::Open,
+        answer: None,
+        answered_at: None,
+        answered_by: None,
+        answer_history: Vec::new(),
+        dismissed_reason: None,
+        created_at: crate::utc_now_iso(),
+        linked_task_id,
+        linked_decision_id: None,
+        extra: Default::default(),
+    };
+    file.questions.push(q.clone());
+    Ok(q)
+}
+
+pub fn answer(file: &mut QuestionsFile, id: &str, answer: &str, answered_by: DecidedBy) -> Result<Question, String> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Err("Answer cannot be empty".to_string());
+    }
+    let q = find_mut(file, id)?;
+    require(q, &[QuestionStatus::Open, QuestionStatus::Deferred, QuestionStatus::Answered], "answer")?;
+    archive_answer(q);
+    q.status = QuestionStatus::Answered;
+    q.answer = Some(answer.to_string());
+    q.answered_at = Some(Timestamp::now());
+    q.answered_by = Some(answered_by);
+    Ok(q.clone())
+}
+
+pub fn defer(file: &mut QuestionsFile, id: &str) -> Result<Question, String> {
+    let q = find_mut(file, id)?;
+    require(q, &[QuestionStatus::Open, QuestionStatus::Deferred], "defer")?;
+    q.status = QuestionStatus::Deferred;
+    Ok(q.clone())
+}
+
+pub fn dismiss(file: &mut QuestionsFile, id: &str, reason: Option<String>) -> Result<Question, String> {
+    let q = find_mut(file, id)?;
+    require(q, &[QuestionStatus::Open, QuestionStatus::Deferred, QuestionStatus::Dismissed], "dismiss")?;
+    q.status = QuestionStatus::Dismissed;
+    q.dismissed_reason = reason.filter(|r| !r.trim().is_empty());
+    Ok(q.clone())
+}
+
+pub fn reopen(file: &mut QuestionsFile, id: &str) -> Result<Question, String> {
+    let q = find_mut(file, id)?;
+    require(q, &[QuestionStatus::Answered, QuestionStatus::Deferred, QuestionStatus::Dismissed], "reopen")?;
+    archive_answer(q);
+    q.status = QuestionStatus::Open;
+    q.dismissed_reason = None;
+    Ok(q.clone())
+}
+
+/// Record the task or decision that came out of a question. `None` leaves a
+/// link alone and `Some("")` clears it; ids are checked by the caller, which
+/// has the other files loaded.
+pub fn link(
+    file: &mut QuestionsFile,
+    id: &str,
+    task_id: Option<String>,
+    decision_id: Option<String>,
+) -> Result<Question, String> {
+    let q = find_mut(file, id)?;
+    if let Some(task_id) = task_id {
+        q.linked_task_id = Some(task_id).filter(|t| !t.is_empty());
+    }
+    if let Some(decision_id) = decision_id {
+        q.linked_decision_id = Some(decision_id).filter(|d| !d.is_empty());
+    }
+    Ok(q.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asked(file: &mut QuestionsFile) -> String {
+        ask(file, "Which database?", String::new(), None).unwrap().id
+    }
+
+    #[test]
+    fn reanswering_keeps_every_earlier_answer() {
+        let mut file = QuestionsFile::default();
+        let id = asked(&mut file);
+
+        let first = answer(&mut file, &id, " SQLite ", DecidedBy::Pat).unwrap();
+        assert_eq!((first.status, first.answer.as_deref()), (QuestionStatus::Answered, Some("SQLite")));
+        assert!(first.answer_history.is_empty());
+
+        let second = answer(&mut file, &id, "Postgres", DecidedBy::Claude).unwrap();
+        assert_eq!(second.answer.as_deref(), Some("Postgres"));
+        assert_eq!(second.answered_by, Some(DecidedBy::Claude));
+        assert_eq!(second.answer_history.len(), 1);
+        assert_eq!(second.answer_history[0].answer, "SQLite");
+        assert_eq!(second.answer_history[0].answered_by, Some(DecidedBy::Pat));
+
+        let reopened = reopen(&mut file, &id).unwrap();
+        assert_eq!(reopened.status, QuestionStatus::Open);
+        assert_eq!(reopened.answer, None);
+        let history: Vec<&str> = reopened.answer_history.iter().map(|a| a.answer.as_str()).collect();
+        assert_eq!(history, vec!["SQLite", "Postgres"]);
+    }
+
+    #[test]
+    fn closing_without_an_answer() {
+        let mut file = QuestionsFile::default();
+        let id = asked(&mut file);
+
+        assert_eq!(defer(&mut file, &id).unwrap().status, QuestionStatus::Deferred);
+        let dismissed = dismiss(&mut file, &id, Some("Out of scope".to_string())).unwrap();
+        assert_eq!(dismissed.status, QuestionStatus::Dismissed);
+        assert_eq!(dismissed.dismissed_reason.as_deref(), Some("Out of scope"));
+        assert_eq!(dismissed.answer, None);
+
+        let err = defer(&mut file, &id).unwrap_err();
+        assert_eq!(err, format!("Cannot defer question {}: it is dismissed", id));
+        assert!(answer(&mut file, &id, "Yes", DecidedBy::Pat).is_err());
+
+        let reopened = reopen(&mut file, &id).unwrap();
+        assert_eq!((reopened.status, reopened.dismissed_reason), (QuestionStatus::Open, None));
+        assert!(reopened.answer_history.is_empty());
+    }
+}
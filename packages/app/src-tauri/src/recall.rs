@@ -0,0 +1,322 @@
+// ── Memory recall ranking ────────────────────────────────────────
+//
+// Ranks memories for a free-text query without embeddings:
+//
+//   score = BM25(query, memory text) × strength × (VITALITY_FLOOR + vitality)
+//
+// BM25 handles relevance; `strength` is the synaptic strength (the brain
+// state's trace wins over the stored value, as in the Node engine);
+// `vitality` is scoreMemory from packages/core/src/brain/scoring.ts — type
+// specific exponential decay plus recency and frequency of access, scaled by
+// severity. The floor keeps an old but exactly-matching memory findable.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::model::{BrainState, Memory, MemorySeverity, MemoryTrace, MemoryType, SynapticHit};
+
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+/// Title and tags count this many times over body text.
+const FIELD_BOOST: usize = 2;
+const VITALITY_FLOOR: f64 = 0.25;
+pub const DEFAULT_LIMIT: usize = 10;
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "do", "for", "from", "has", "have", "how",
+    "i", "if", "in", "into", "is", "it", "its", "not", "of", "on", "or", "so", "that", "the", "this",
+    "to", "was", "we", "what", "when", "which", "with", "you",
+];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecalledMemory {
+    pub memory: Memory,
+    pub score: f64,
+    pub text_score: f64,
+    pub vitality: f64,
+    pub strength: f64,
+    pub matched_terms: Vec<String>,
+}
+
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() >= 2)
+        .map(str::to_lowercase)
+        .filter(|w| !STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+fn document_terms(m: &Memory) -> Vec<String> {
+    let mut terms = Vec::new();
+    for _ in 0..FIELD_BOOST {
+        terms.extend(tokenize(&m.title));
+        for tag in &m.tags {
+            terms.extend(tokenize(tag));
+        }
+    }
+    terms.extend(tokenize(&m.content));
+    terms.extend(tokenize(&m.rule));
+    terms
+}
+
+/// Half-lives in days: fact 17, pain 28, win 46, decision 87, architecture 173, reflection 35.
+pub fn decay_rate(memory_type: MemoryType) -> f64 {
+    match memory_type {
+        MemoryType::Fact => 0.04,
+        MemoryType::Pain => 0.025,
+        MemoryType::Win => 0.015,
+        MemoryType::Decision => 0.008,
+        MemoryType::Architecture => 0.004,
+        MemoryType::Reflection => 0.02,
+    }
+}
+
+pub fn severity_weight(severity: MemorySeverity) -> f64 {
+    match severity {
+        MemorySeverity::High => 1.4,
+        MemorySeverity::Medium => 1.0,
+        MemorySeverity::Low => 0.7,
+    }
+}
+
+fn days_between(from: &str, now_ms: u64) -> Option<f64> {
+    crate::millis_from_iso(from).map(|t| now_ms.saturating_sub(t) as f64 / 86_400_000.0)
+}
+
+/// Port of scoreMemory (without failure correlations, which live in Node only).
+pub fn vitality(m: &Memory, now_ms: u64) -> f64 {
+    let age = days_between(&m.created_at, now_ms).unwrap_or(0.0);
+    let decay = (-decay_rate(m.memory_type) * age).exp();
+    let access_boost = m.last_accessed.as_deref()
+        .and_then(|t| days_between(t, now_ms))
+        .map_or(0.0, |days| (-0.05 * days).exp() * 0.3);
+    let frequency = (((m.access_count + 1) as f64).log2() * 0.05).min(0.2);
+    let superseded = if m.superseded_by.is_some() { 0.6 } else { 0.0 };
+    ((decay + access_boost + frequency - superseded) * severity_weight(m.severity)).clamp(0.0, 1.0)
+}
+
+/// Rank active (non-superseded) memories for `query`, best first.
+pub fn rank(memories: &[Memory], brain: Option<&BrainState>, query: &str, limit: usize, now_ms: u64) -> Vec<RecalledMemory> {
+    let query_terms: Vec<String> = {
+        let mut seen = HashSet::new();
+        tokenize(query).into_iter().filter(|t| seen.insert(t.clone())).collect()
+    };
+    if query_terms.is_empty() {
+        return Vec::new();
+    }
+
+    let active: Vec<&Memory> = memories.iter().filter(|m| m.superseded_by.is_none()).collect();
+    let docs: Vec<HashMap<String, usize>> = active.iter()
+        .map(|m| {
+            let mut tf = HashMap::new();
+            for term in document_terms(m) {
+                *tf.entry(term).or_insert(0) += 1;
+            }
+            tf
+        })
+        .collect();
+    let lengths: Vec<usize> = docs.iter().map(|d| d.values().sum()).collect();
+    let n = docs.len() as f64;
+    let avg_len = (lengths.iter().sum::<usize>() as f64 / n.max(1.0)).max(1.0);
+
+    let idf: HashMap<&str, f64> = query_terms.iter()
+        .map(|term| {
+            let df = docs.iter().filter(|d| d.contains_key(term)).count() as f64;
+            (term.as_str(), ((n - df + 0.5) / (df + 0.5) + 1.0).ln())
+        })
+        .collect();
+
+    let mut results: Vec<RecalledMemory> = active.iter()
+        .zip(&docs)
+        .zip(&lengths)
+        .filter_map(|((m, tf), &len)| {
+            let mut text_score = 0.0;
+            let mut matched = Vec::new();
+            for term in &query_terms {
+                let Some(&f) = tf.get(term) else { continue };
+                let f = f as f64;
+                text_score += idf[term.as_str()] * f * (K1 + 1.0) / (f + K1 * (1.0 - B + B * len as f64 / avg_len));
+                matched.push(term.clone());
+            }
+            if matched.is_empty() {
+                return None;
+            }
+            let strength = brain
+                .and_then(|b| b.memory_traces.get(&m.id))
+                .map_or(m.synaptic_strength, |t| t.synaptic_strength);
+            let vitality = vitality(m, now_ms);
+            Some(RecalledMemory {
+                score: text_score * strength * (VITALITY_FLOOR + vitality),
+                memory: (*m).clone(),
+                text_score,
+                vitality,
+                strength,
+                matched_terms: matched,
+            })
+        })
+        .collect();
+
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(limit);
+    results
+}
+
+/// Bump accessCount and lastAccessed on the recalled memories, both in the
+/// file and in the results handed back.
+pub fn record_access(memories: &mut [Memory], recalled: &mut [RecalledMemory], timestamp: &str) {
+    let ids: HashSet<&str> = recalled.iter().map(|r| r.memory.id.as_str()).collect();
+    for m in memories.iter_mut().filter(|m| ids.contains(m.id.as_str())) {
+        m.access_count += 1;
+        m.last_accessed = Some(timestamp.to_string());
+    }
+    for r in recalled.iter_mut() {
+        r.memory.access_count += 1;
+        r.memory.last_accessed = Some(timestamp.to_string());
+    }
+}
+
+/// Same bookkeeping as recordMemoryTraces + recordSynapticActivity in
+/// packages/core/src/brain/state.ts, so plasticity at session end sees
+/// memories surfaced from the app too.
+pub fn record_brain_activity(state: &mut BrainState, recalled: &[RecalledMemory], timestamp: &str) {
+    let mut tags = BTreeSet::new();
+    for r in recalled {
+        let trace = state.memory_traces.entry(r.memory.id.clone()).or_insert_with(|| MemoryTrace {
+            count: 0,
+            last_accessed: String::new(),
+            synaptic_strength: 1.0,
+        });
+        trace.count += 1;
+        trace.last_accessed = timestamp.to_string();
+        if !state.active_traces.contains(&r.memory.id) {
+            state.active_traces.push(r.memory.id.clone());
+        }
+        for tag in &r.memory.tags {
+            tags.insert(tag.as_str());
+        }
+    }
+    for tag in tags {
+        let hit = state.synaptic_activity.entry(tag.to_string()).or_insert_with(|| SynapticHit {
+            count: 0,
+            last_hit: String::new(),
+        });
+        hit.count += 1;
+        hit.last_hit = timestamp.to_string();
+        *state.firing_frequency.entry(tag.to_string()).or_insert(0.0) += 1.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const NOW: &str = "2026-03-01T00:00:00.000Z";
+
+    fn memory(id: &str, fields: serde_json::Value) -> Memory {
+        let mut m = json!({
+            "id": id, "projectId": "p", "type": "fact", "title": "", "createdAt": NOW,
+        });
+        m.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(m).unwrap()
+    }
+
+    fn now_ms() -> u64 {
+        crate::millis_from_iso(NOW).unwrap()
+    }
+
+    fn corpus() -> Vec<Memory> {
+        vec![
+            memory("m_deploy", json!({ "title": "Deploy script", "content": "Run the deploy script before release" })),
+            memory("m_release", json!({ "title": "Release checklist", "content": "Tag the release" })),
+            memory("m_db", json!({ "title": "Database", "content": "Postgres connection pool" })),
+        ]
+    }
+
+    #[test]
+    fn bm25_scores_match_hand_computed_values() {
+        let results = rank(&corpus(), None, "the deploy release", 10, now_ms());
+        let scores: Vec<(&str, f64)> = results.iter().map(|r| (r.memory.id.as_str(), r.text_score)).collect();
+        // "the" is a stopword; m_db matches nothing and is left out
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].0, "m_deploy");
+        assert!((scores[0].1 - 1.8449067121117846).abs() < 1e-12, "{:?}", scores);
+        assert_eq!(scores[1].0, "m_release");
+        assert!((scores[1].1 - 0.7547503535332982).abs() < 1e-12, "{:?}", scores);
+        assert_eq!(results[0].matched_terms, ["deploy", "release"]);
+    }
+
+    #[test]
+    fn title_and_tags_outweigh_body_text() {
+        let memories = vec![
+            memory("m_body", json!({ "title": "Notes", "content": "caching layer" })),
+            memory("m_title", json!({ "title": "Caching", "content": "layer notes" })),
+            memory("m_tag", json!({ "title": "Layer", "tags": ["caching"], "content": "notes" })),
+        ];
+        let ids: Vec<String> = rank(&memories, None, "caching", 10, now_ms()).into_iter().map(|r| r.memory.id).collect();
+        assert_eq!(ids.last().unwrap(), "m_body");
+    }
+
+    #[test]
+    fn strength_vitality_and_supersession_shape_the_ranking() {
+        let mut memories = corpus();
+        memories.push(memory("m_old", json!({ "title": "Deploy script", "content": "Run the deploy script before release", "supersededBy": "m_deploy" })));
+        let brain: BrainState = serde_json::from_value(json!({
+            "sessionStart": NOW,
+            "memoryTraces": { "m_release": { "count": 1, "lastAccessed": NOW, "synapticStrength": 2.0 } },
+        }))
+        .unwrap();
+
+        let results = rank(&memories, Some(&brain), "deploy release", 10, now_ms());
+        assert!(results.iter().all(|r| r.memory.id != "m_old"));
+        let release = results.iter().find(|r| r.memory.id == "m_release").unwrap();
+        assert_eq!(release.strength, 2.0);
+        let expected = release.text_score * 2.0 * (VITALITY_FLOOR + release.vitality);
+        assert!((release.score - expected).abs() < 1e-12);
+
+        assert!(rank(&memories, None, "the of and", 10, now_ms()).is_empty());
+        assert_eq!(rank(&memories, None, "deploy release", 1, now_ms()).len(), 1);
+    }
+
+    /// Expected values from scoreMemory in packages/core/src/brain/scoring.ts,
+    /// run under Node with now = 2026-03-01T00:00:00.000Z.
+    #[test]
+    fn vitality_matches_score_memory() {
+        let cases = [
+            (json!({ "type": "fact", "severity": "medium", "createdAt": "2026-02-01T00:00:00.000Z" }), 0.32627979462303947),
+            (json!({ "type": "architecture", "severity": "low", "createdAt": "2025-09-01T00:00:00.000Z",
+                     "lastAccessed": "2026-02-20T12:00:00.000Z", "accessCount": 3 }), 0.5466580499144081),
+            (json!({ "type": "pain", "severity": "high", "createdAt": "2026-02-27T00:00:00.000Z",
+                     "lastAccessed": "2026-02-28T00:00:00.000Z", "accessCount": 50 }), 1.0),
+            (json!({ "type": "decision", "severity": "medium", "createdAt": "2026-01-01T00:00:00.000Z",
+                     "accessCount": 1, "supersededBy": "m_x" }), 0.07375351291775212),
+        ];
+        for (fields, expected) in cases {
+            let v = vitality(&memory("m", fields.clone()), now_ms());
+            assert!((v - expected).abs() < 1e-12, "{}: {} != {}", fields, v, expected);
+        }
+    }
+
+    /// Recalling a memory bumps it the way MemoryStore.incrementAccess does,
+    /// and its vitality moves to what scoreMemory gives for the bumped memory.
+    #[test]
+    fn access_bump_matches_increment_access() {
+        let mut memories = vec![
+            memory("m_win", json!({ "type": "win", "severity": "medium", "title": "Fast builds", "createdAt": "2026-01-15T00:00:00.000Z",
+                                    "lastAccessed": "2026-01-20T00:00:00.000Z", "accessCount": 2 })),
+            memory("m_other", json!({ "title": "Unrelated" })),
+        ];
+        assert!((vitality(&memories[0], now_ms()) - 0.6290051306145908).abs() < 1e-12);
+
+        let mut recalled = rank(&memories, None, "builds", 10, now_ms());
+        record_access(&mut memories, &mut recalled, NOW);
+
+        assert_eq!(memories[0].access_count, 3);
+        assert_eq!(memories[0].last_accessed.as_deref(), Some(NOW));
+        assert_eq!(memories[1].access_count, 0);
+        assert_eq!(recalled[0].memory.access_count, 3);
+        assert!((vitality(&memories[0], now_ms()) - 0.9091564206075492).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,247 @@
+// ── Project scaffolding (.hello-world/) ──────────────────────────
+//
+// Rust equivalent of `hello-world init` (Project.init in packages/core), so
+// a repo can be onboarded from the app. Also repairs a partial layout: files
+// that are missing get created, files that exist are validated against the
+// typed model and reported — never overwritten.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::model::{
+    self, ActivityFile, ApprovalsFile, ConfigFile, DecisionsFile, Direction, MemoriesFile,
+    QuestionsFile, SessionsFile, TasksFile, WatchersFile, WorkflowState,
+};
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitReport {
+    pub project_path: String,
+    pub created: Vec<String>,
+    pub existing: Vec<String>,
+    pub invalid: Vec<InvalidFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvalidFile {
+    pub file: String,
+    pub error: String,
+}
+
+struct FileSpec {
+    name: &'static str,
+    validate: fn(&str, &str) -> Result<(), String>,
+    empty: fn() -> Result<String, String>,
+    /// Whether a template may seed this file. History (questions, sessions,
+    /// approvals, watchers, activity) always starts empty.
+    seed: bool,
+}
+
+fn validate<T: DeserializeOwned>(file_name: &str, contents: &str) -> Result<(), String> {
+    model::parse_document::<T>(file_name, contents).map(|_| ())
+}
+
+fn empty<T: Default + Serialize>() -> Result<String, String> {
+    serde_json::to_string_pretty(&T::default()).map_err(|e| format!("Failed to serialize: {}", e))
+}
+
+/// Everything except config.json, which needs the project name.
+const FILES: &[FileSpec] = &[
+    FileSpec { name: "tasks.json", validate: validate::<TasksFile>, empty: empty::<TasksFile>, seed: true },
+    FileSpec { name: "decisions.json", validate: validate::<DecisionsFile>, empty: empty::<DecisionsFile>, seed: true },
+    FileSpec { name: "questions.json", validate: validate::<QuestionsFile>, empty: empty::<QuestionsFile>, seed: false },
+    FileSpec { name: "memories.json", validate: validate::<MemoriesFile>, empty: empty::<MemoriesFile>, seed: true },
+    FileSpec { name: "sessions.json", validate: validate::<SessionsFile>, empty: empty::<SessionsFile>, seed: false },
+    FileSpec { name: "approvals.json", validate: validate::<ApprovalsFile>, empty: empty::<ApprovalsFile>, seed: false },
+    FileSpec { name: "watchers.json", validate: validate::<WatchersFile>, empty: empty::<WatchersFile>, seed: false },
+    FileSpec { name: "workflow.json", validate: validate::<WorkflowState>, empty: empty::<WorkflowState>, seed: true },
+    FileSpec { name: "direction.json", validate: validate::<Direction>, empty: empty::<Direction>, seed: true },
+    FileSpec { name: "activity.json", validate: validate::<ActivityFile>, empty: empty::<ActivityFile>, seed: false },
+];
+
+/// A template is another project (or its .hello-world/ directory); the
+/// seedable scaffold files it contains replace the empty defaults.
+fn resolve_template(template: &str) -> Result<PathBuf, String> {
+    let dir = PathBuf::from(template);
+    let nested = dir.join(".hello-world");
+    let dir = if nested.is_dir() { nested } else { dir };
+    if !dir.is_dir() {
+        return Err(format!("Template not found: {}", template));
+    }
+    Ok(dir)
+}
+
+fn read_template_file(template_dir: Option<&Path>, name: &str) -> Result<Option<String>, String> {
+    let Some(dir) = template_dir else { return Ok(None) };
+    let path = dir.join(name);
+    if !path.exists() {
+        return Ok(None);
+    }
+    fs::read_to_string(&path)
+        .map(Some)
+        .map_err(|e| format!("Failed to read template {}: {}", path.display(), e))
+}
+
+fn config_contents(name: &str, template: Option<String>) -> Result<String, String> {
+    let timestamp = crate::utc_now_iso();
+    let mut config: ConfigFile = match template {
+        Some(contents) => model::parse_document("config.json", &contents)?,
+        // Defaults match ProjectConfigSchema in packages/core/src/types.ts
+        None => model::parse_document(
+            "config.json",
+            &serde_json::json!({ "config": { "name": "", "createdAt": "", "updatedAt": "" } }).to_string(),
+        )?,
+    };
+    config.config.name = name.to_string();
+    config.config.created_at = timestamp.clone();
+    config.config.updated_at = timestamp;
+    config.config.schema_version = Some(crate::migrate::CURRENT_SCHEMA_VERSION);
+    serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize: {}", e))
+}
+
+pub fn init_project(project_path: &str, name: Option<String>, template: Option<&str>) -> Result<InitReport, String> {
+    let root = Path::new(project_path);
+    if !root.is_dir() {
+        return Err(format!("Not a directory: {}", project_path));
+    }
+    let template_dir = template.map(resolve_template).transpose()?;
+
+    // Validate every template file up front so a bad template writes nothing
+    let template_config = read_template_file(template_dir.as_deref(), "config.json")?;
+    if let Some(contents) = &template_config {
+        validate::<ConfigFile>("config.json", contents).map_err(|e| format!("Invalid template: {}", e))?;
+    }
+    for spec in FILES.iter().filter(|spec| spec.seed) {
+        if let Some(contents) = read_template_file(template_dir.as_deref(), spec.name)? {
+            (spec.validate)(spec.name, &contents).map_err(|e| format!("Invalid template: {}", e))?;
+        }
+    }
+
+    let hw_dir = root.join(".hello-world");
+    fs::create_dir_all(&hw_dir)
+        .map_err(|e| format!("Failed to create {}: {}", hw_dir.display(), e))?;
+
+    let mut report = InitReport { project_path: project_path.to_string(), ..Default::default() };
+
+    let config_path = hw_dir.join("config.json");
+    if config_path.exists() {
+        check_existing(&config_path, "config.json", validate::<ConfigFile>, &mut report);
+    } else {
+        let name = name
+            .filter(|n| !n.trim().is_empty())
+            .or_else(|| root.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "unnamed".to_string());
+        crate::storage::write_atomic(&config_path, config_contents(&name, template_config)?.as_bytes())?;
+        report.created.push("config.json".to_string());
+    }
+
+    for spec in FILES {
+        let path = hw_dir.join(spec.name);
+        if path.exists() {
+            check_existing(&path, spec.name, spec.validate, &mut report);
+            continue;
+        }
+        let seeded = if spec.seed { read_template_file(template_dir.as_deref(), spec.name)? } else { None };
+        let contents = match seeded {
+            Some(contents) => contents,
+            None => (spec.empty)()?,
+        };
+        crate::storage::write_atomic(&path, contents.as_bytes())?;
+        report.created.push(spec.name.to_string());
+    }
+
+    Ok(report)
+}
+
+fn check_existing(path: &Path, name: &str, validate: fn(&str, &str) -> Result<(), String>, report: &mut InitReport) {
+    let result = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+        .and_then(|contents| validate(name, &contents));
+    match result {
+        Ok(()) => report.existing.push(name.to_string()),
+        Err(error) => report.invalid.push(InvalidFile { file: name.to_string(), error }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempProject;
+
+    const TASKS: &str = r#"{ "tasks": [{ "id": "t_1", "title": "Ship", "status": "todo",
+        "createdAt": "2025-01-01T00:00:00.000Z", "updatedAt": "2025-01-01T00:00:00.000Z" }] }"#;
+    const ACTIVITY: &str = r#"{ "activities": [{ "id": "act_1", "type": "task_created",
+        "description": "Ship", "timestamp": "2025-01-01T00:00:00.000Z" }] }"#;
+
+    fn load<T: DeserializeOwned>(project: &TempProject, name: &str) -> T {
+        model::parse_document(name, &fs::read_to_string(project.file(name)).unwrap()).unwrap()
+    }
+
+    fn all_files() -> Vec<String> {
+        std::iter::once("config.json").chain(FILES.iter().map(|spec| spec.name)).map(String::from).collect()
+    }
+
+    #[test]
+    fn fresh_scaffold_creates_every_file() {
+        let project = TempProject::new("scaffold-fresh");
+        let report = init_project(project.path(), Some("demo".to_string()), None).unwrap();
+        assert_eq!(report.created, all_files());
+        assert!(report.existing.is_empty() && report.invalid.is_empty());
+
+        let config: ConfigFile = load(&project, "config.json");
+        assert_eq!(config.config.name, "demo");
+        assert_eq!(config.config.schema_version, Some(crate::migrate::CURRENT_SCHEMA_VERSION));
+        for spec in FILES {
+            let contents = fs::read_to_string(project.file(spec.name)).unwrap();
+            (spec.validate)(spec.name, &contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn repairs_a_partial_layout_and_reports_invalid_files() {
+        let project = TempProject::new("scaffold-partial");
+        project.write("tasks.json", TASKS);
+        project.write("decisions.json", "{ not json");
+
+        let report = init_project(project.path(), None, None).unwrap();
+        assert_eq!(report.existing, vec!["tasks.json"]);
+        assert_eq!(report.invalid.len(), 1);
+        assert_eq!(report.invalid[0].file, "decisions.json");
+        assert_eq!(report.created.len(), all_files().len() - 2);
+
+        // Neither existing file was touched
+        assert_eq!(fs::read_to_string(project.file("tasks.json")).unwrap(), TASKS);
+        assert_eq!(fs::read_to_string(project.file("decisions.json")).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn template_seeds_plans_but_not_history() {
+        let template = TempProject::new("scaffold-template");
+        template.write("tasks.json", TASKS);
+        template.write("activity.json", ACTIVITY);
+        let project = TempProject::new("scaffold-from-template");
+
+        // The project root resolves to its .hello-world/
+        init_project(project.path(), None, Some(template.path())).unwrap();
+
+        let tasks: TasksFile = load(&project, "tasks.json");
+        assert_eq!(tasks.tasks.len(), 1);
+        let activity: ActivityFile = load(&project, "activity.json");
+        assert!(activity.activities.is_empty());
+    }
+
+    #[test]
+    fn bad_template_writes_nothing() {
+        let template = TempProject::new("scaffold-bad-template");
+        template.write("tasks.json", TASKS);
+        template.write("decisions.json", r#"{ "decisions": "nope" }"#);
+        let project = TempProject::new("scaffold-rejected");
+
+        let template_dir = template.file("");
+        let err = init_project(project.path(), None, template_dir.to_str()).unwrap_err();
+        assert!(err.starts_with("Invalid template"), "{}", err);
+        assert_eq!(fs::read_dir(project.file("")).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,361 @@
+// ── SQLite storage backend ───────────────────────────────────────
+//
+// Opt-in per project with `"storage": "sqlite"` in config.json. The record
+// collections the app reads most (tasks, decisions, questions, memories and
+// activity) are kept one row per record in .hello-world/hello-world.db, and
+// get_state / get_memories / get_activity are served from there.
+//
+// The MCP server only speaks JSON, so the JSON files stay current too:
+// - every locked write the app makes is mirrored into the database before
+//   the lock is released (see `write_json_file`)
+// - writes from other processes are imported when the watcher sees them,
+//   and everything is re-imported when the project is opened
+// - `export_project` rewrites the JSON files from the database, but only
+//   files that haven't changed since they were imported.
+//
+// Each document row keeps the revision of the JSON it was imported from
+// (plus the journal's size, for activity). A stored copy is only served while
+// that still matches the disk, so a mirror that missed a write falls back to
+// the JSON instead of handing out stale data, and revisions handed to the UI
+// always match what expected_revision is checked against.
+
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::model::ActivityFile;
+
+pub const DB_FILE: &str = "hello-world.db";
+
+/// Documents stored as rows, and the key of their record array.
+const COLLECTIONS: &[(&str, &str)] = &[
+    ("tasks.json", "tasks"),
+    ("decisions.json", "decisions"),
+    ("questions.json", "questions"),
+    ("memories.json", "memories"),
+    ("activity.json", "activities"),
+];
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS documents (
+        file TEXT PRIMARY KEY,
+        revision TEXT NOT NULL,
+        fields TEXT NOT NULL,
+        imported_at TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS records (
+        file TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        id TEXT,
+        body TEXT NOT NULL,
+        PRIMARY KEY (file, position)
+    );
+    CREATE INDEX IF NOT EXISTS records_by_id ON records (file, id);
+";
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqliteReport {
+    pub files: Vec<String>,
+    pub records: usize,
+    /// Files left alone, with the reason.
+    pub skipped: Vec<String>,
+}
+
+fn collection_key(file_name: &str) -> Option<&'static str> {
+    COLLECTIONS.iter().find(|(file, _)| *file == file_name).map(|(_, key)| *key)
+}
+
+pub fn is_collection(file_name: &str) -> bool {
+    collection_key(file_name).is_some()
+}
+
+fn db_error(e: rusqlite::Error) -> String {
+    format!("Failed to access {}: {}", DB_FILE, e)
+}
+
+/// Open connections by database path, so the schema is only set up once.
+static CONNECTIONS: Mutex<Vec<(PathBuf, Connection)>> = Mutex::new(Vec::new());
+
+fn open(path: &PathBuf) -> Result<Connection, String> {
+    let conn = Connection::open(path).map_err(db_error)?;
+    // WAL lets the UI read while a mirror write is in progress
+    conn.pragma_update(None, "journal_mode", "WAL").map_err(db_error)?;
+    conn.busy_timeout(crate::lock::LOCK_TIMEOUT).map_err(db_error)?;
+    conn.execute_batch(SCHEMA).map_err(db_error)?;
+    Ok(conn)
+}
+
+/// Run `f` on the project's database connection, opening it on first use.
+fn with_connection<R>(project_path: &str, f: impl FnOnce(&mut Connection) -> Result<R, String>) -> Result<R, String> {
+    let path = crate::hw_path(project_path, DB_FILE);
+    let mut connections = CONNECTIONS.lock().map_err(|_| "Lock poisoned")?;
+    let index = match connections.iter().position(|(p, _)| *p == path) {
+        Some(index) => index,
+        None => {
+            let conn = open(&path)?;
+            connections.push((path, conn));
+            connections.len() - 1
+        }
+    };
+    f(&mut connections[index].1)
+}
+
+/// Identifies the on-disk state a stored copy is taken from: the JSON's
+/// revision, and for activity also how far its journal has grown.
+fn source_revision(project_path: &str, file_name: &str) -> Result<String, String> {
+    let revision = crate::current_revision(project_path, file_name)?;
+    if file_name == "activity.json" {
+        return Ok(format!("{}+{}", revision, crate::journal::len(project_path, file_name)));
+    }
+    Ok(revision)
+}
+
+/// The JSON revision part of a `source_revision`.
+fn json_revision(source: &str) -> &str {
+    source.split_once('+').map_or(source, |(revision, _)| revision)
+}
+
+/// Replace the stored copy of `file_name` with `doc`.
+fn import(conn: &mut Connection, file_name: &str, doc: Value, revision: &str) -> Result<usize, String> {
+    let key = collection_key(file_name).ok_or_else(|| format!("{} is not stored in SQLite", file_name))?;
+    let Value::Object(mut fields) = doc else {
+        return Err(format!("Failed to import {}: not a JSON object", file_name));
+    };
+    let records = match fields.remove(key) {
+        Some(Value::Array(records)) => records,
+        None => Vec::new(),
+        Some(_) => return Err(format!("Failed to import {}: {} is not an array", file_name, key)),
+    };
+
+    let tx = conn.transaction().map_err(db_error)?;
+    tx.execute("DELETE FROM records WHERE file = ?1", params![file_name]).map_err(db_error)?;
+    {
+        let mut insert = tx
+            .prepare("INSERT INTO records (file, position, id, body) VALUES (?1, ?2, ?3, ?4)")
+            .map_err(db_error)?;
+        for (position, record) in records.iter().enumerate() {
+            insert
+                .execute(params![file_name, position as i64, record["id"].as_str(), record.to_string()])
+                .map_err(db_error)?;
+        }
+    }
+    tx.execute(
+        "INSERT INTO documents (file, revision, fields, imported_at) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT (file) DO UPDATE SET revision = ?2, fields = ?3, imported_at = ?4",
+        params![file_name, revision, Value::Object(fields).to_string(), crate::utc_now_iso()],
+    )
+    .map_err(db_error)?;
+    tx.commit().map_err(db_error)?;
+    Ok(records.len())
+}
+
+/// The stored document and the `source_revision` it was imported at, or
+/// `None` if it was never imported.
+fn load(conn: &Connection, file_name: &str) -> Result<Option<(Value, String)>, String> {
+    let key = collection_key(file_name).ok_or_else(|| format!("{} is not stored in SQLite", file_name))?;
+    let Some((revision, fields)) = conn
+        .query_row(
+            "SELECT revision, fields FROM documents WHERE file = ?1",
+            params![file_name],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()
+        .map_err(db_error)?
+    else {
+        return Ok(None);
+    };
+
+    let mut doc: Map<String, Value> = serde_json::from_str(&fields)
+        .map_err(|e| format!("Failed to parse stored {}: {}", file_name, e))?;
+    let mut query = conn
+        .prepare("SELECT body FROM records WHERE file = ?1 ORDER BY position")
+        .map_err(db_error)?;
+    let records = query
+        .query_map(params![file_name], |row| row.get::<_, String>(0))
+        .map_err(db_error)?
+        .map(|body| {
+            let body = body.map_err(db_error)?;
+            serde_json::from_str(&body).map_err(|e| format!("Failed to parse stored {}: {}", file_name, e))
+        })
+        .collect::<Result<Vec<Value>, String>>()?;
+    doc.insert(key.to_string(), Value::Array(records));
+    Ok(Some((Value::Object(doc), revision)))
+}
+
+/// What readers of `file_name` currently see on disk. Activity includes
+/// events still waiting in its journal.
+fn read_json(project_path: &str, file_name: &str) -> Result<Option<Value>, String> {
+    if !crate::hw_path(project_path, file_name).exists() {
+        return Ok(None);
+    }
+    if file_name == "activity.json" {
+        let activity: ActivityFile = crate::journal::read(project_path, file_name)?;
+        return serde_json::to_value(activity).map(Some).map_err(|e| format!("Failed to serialize: {}", e));
+    }
+    crate::read_json_file(project_path, file_name).map(Some)
+}
+
+/// The stored `file_name` and its JSON revision, if it was imported and the
+/// JSON hasn't changed since. `None` means read the JSON.
+pub fn load_current(project_path: &str, file_name: &str) -> Result<Option<(Value, String)>, String> {
+    let Some((doc, stored)) = with_connection(project_path, |conn| load(conn, file_name))? else {
+        return Ok(None);
+    };
+    if stored != source_revision(project_path, file_name)? {
+        return Ok(None);
+    }
+    Ok(Some((doc, json_revision(&stored).to_string())))
+}
+
+/// Bring the stored copy of `file_name` up to date with the JSON on disk.
+pub fn sync_file(project_path: &str, file_name: &str) -> Result<usize, String> {
+    // Take the revision first: if the file changes while it is read, the
+    // stored copy is older than it claims and reads fall back to the JSON
+    let revision = source_revision(project_path, file_name)?;
+    let Some(doc) = read_json(project_path, file_name)? else {
+        return Ok(0);
+    };
+    with_connection(project_path, |conn| import(conn, file_name, doc, &revision))
+}
+
+/// Load every collection present in .hello-world/ into the database.
+pub fn import_project(project_path: &str) -> Result<SqliteReport, String> {
+    let mut report = SqliteReport::default();
+    for (file_name, _) in COLLECTIONS {
+        if !crate::hw_path(project_path, file_name).exists() {
+            report.skipped.push(format!("{}: no such file", file_name));
+            continue;
+        }
+        report.records += sync_file(project_path, file_name)?;
+        report.files.push(file_name.to_string());
+    }
+    Ok(report)
+}
+
+/// Write the JSON files back from the database. A file that changed after it
+/// was imported holds newer data than the database and is skipped.
+pub fn export_project(project_path: &str) -> Result<SqliteReport, String> {
+    if !crate::hw_path(project_path, DB_FILE).exists() {
+        return Err(format!("No {} in this project; import it first", DB_FILE));
+    }
+    let mut report = SqliteReport::default();
+    for (file_name, key) in COLLECTIONS {
+        // Not held across the write, which mirrors back into the database
+        let Some((doc, revision)) = with_connection(project_path, |conn| load(conn, file_name))? else {
+            report.skipped.push(format!("{}: never imported", file_name));
+            continue;
+        };
+        let records = doc[*key].as_array().map_or(0, Vec::len);
+        let written = crate::with_file_lock(project_path, file_name, || {
+            let missing = crate::current_revision(project_path, file_name)? == crate::storage::MISSING_REVISION;
+            if !missing && source_revision(project_path, file_name)? != revision {
+                return Ok(false);
+            }
+            crate::write_json_file(project_path, file_name, &doc)?;
+            Ok(true)
+        })?;
+        if written {
+            report.records += records;
+            report.files.push(file_name.to_string());
+        } else {
+            report.skipped.push(format!("{}: changed since it was imported", file_name));
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempProject;
+    use serde_json::json;
+
+    fn tasks() -> Value {
+        json!({
+            "version": 2,
+            "tasks": [
+                { "id": "t_1", "title": "First", "status": "todo" },
+                { "id": "t_2", "title": "Second", "status": "done", "tags": ["x"] },
+            ],
+        })
+    }
+
+    fn activity_line(id: &str) -> String {
+        json!({ "id": id, "type": "note", "description": id, "timestamp": "2026-01-01T00:00:00.000Z" }).to_string() + "\n"
+    }
+
+    #[test]
+    fn import_round_trips_records_and_fields() {
+        let project = TempProject::new("sqlite-round-trip");
+        project.write("tasks.json", &tasks().to_string());
+
+        let report = import_project(project.path()).unwrap();
+        assert_eq!(report.files, ["tasks.json"]);
+        assert_eq!(report.records, 2);
+
+        let (doc, revision) = load_current(project.path(), "tasks.json").unwrap().unwrap();
+        assert_eq!(doc, tasks());
+        assert_eq!(revision, crate::current_revision(project.path(), "tasks.json").unwrap());
+    }
+
+    #[test]
+    fn export_rewrites_the_json_from_the_database() {
+        let project = TempProject::new("sqlite-export");
+        project.write("tasks.json", &tasks().to_string());
+        import_project(project.path()).unwrap();
+        std::fs::remove_file(project.file("tasks.json")).unwrap();
+
+        let report = export_project(project.path()).unwrap();
+        assert_eq!(report.files, ["tasks.json"]);
+        assert_eq!(crate::read_json_file(project.path(), "tasks.json").unwrap(), tasks());
+    }
+
+    #[test]
+    fn export_skips_files_changed_since_import() {
+        let project = TempProject::new("sqlite-export-changed");
+        project.write("tasks.json", &tasks().to_string());
+        import_project(project.path()).unwrap();
+        let newer = json!({ "tasks": [{ "id": "t_3", "title": "Newer", "status": "todo" }] });
+        project.write("tasks.json", &newer.to_string());
+
+        let report = export_project(project.path()).unwrap();
+        assert!(report.files.is_empty());
+        assert_eq!(report.skipped[0], "tasks.json: changed since it was imported");
+        assert_eq!(crate::read_json_file(project.path(), "tasks.json").unwrap(), newer);
+    }
+
+    #[test]
+    fn a_stale_copy_is_not_served() {
+        let project = TempProject::new("sqlite-stale");
+        project.write("tasks.json", &tasks().to_string());
+        import_project(project.path()).unwrap();
+
+        // Written behind the mirror's back, e.g. by the MCP server
+        project.write("tasks.json", r#"{"tasks":[]}"#);
+        assert!(load_current(project.path(), "tasks.json").unwrap().is_none());
+
+        sync_file(project.path(), "tasks.json").unwrap();
+        let (doc, _) = load_current(project.path(), "tasks.json").unwrap().unwrap();
+        assert_eq!(doc, json!({ "tasks": [] }));
+    }
+
+    #[test]
+    fn activity_includes_its_journal() {
+        let project = TempProject::new("sqlite-activity");
+        project.write("activity.json", &json!({ "activities": [] }).to_string());
+        project.write("activity.jsonl", &activity_line("act_1"));
+        import_project(project.path()).unwrap();
+        let (doc, revision) = load_current(project.path(), "activity.json").unwrap().unwrap();
+        assert_eq!(doc["activities"][0]["id"], "act_1");
+        assert_eq!(revision, crate::current_revision(project.path(), "activity.json").unwrap());
+
+        // An append leaves activity.json alone but still makes the copy stale
+        project.write("activity.jsonl", &(activity_line("act_1") + &activity_line("act_2")));
+        assert!(load_current(project.path(), "activity.json").unwrap().is_none());
+    }
+}
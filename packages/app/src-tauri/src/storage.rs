@@ -0,0 +1,219 @@
+// ── Crash-safe file writes ───────────────────────────────────────
+//
+// 1. Write the new contents to <file>.tmp next to the target and fsync it
+// 2. Roll the previous contents into <file>.bak (<file>.bak.1, ... older)
+// 3. Rename .tmp over the target, retrying while Windows holds the file open
+// 4. fsync the directory so the rename itself survives a power loss (Unix)
+//
+// A crash at any point leaves either the old or the new file in place, never
+// a truncated one.
+//
+// This differs from JsonStore in packages/core/src/storage.ts, which keeps a
+// single <file>.backup and silently reads it when the main file won't parse.
+// The .bak generations here are for restoring by hand: nothing reads them,
+// so a damaged file is reported as an error rather than quietly rolled back.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How many `.bak` generations to keep per file.
+pub const BACKUP_GENERATIONS: usize = 3;
+
+/// Revision reported for a document that doesn't exist yet.
+pub const MISSING_REVISION: &str = "0";
+
+const RENAME_RETRIES: u32 = 5;
+const RENAME_RETRY_MS: u64 = 50;
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// `tasks.json` → `tasks.json.bak`, `tasks.json.bak.1`, ...
+pub fn backup_path(path: &Path, generation: usize) -> PathBuf {
+    if generation == 0 {
+        with_suffix(path, ".bak")
+    } else {
+        with_suffix(path, &format!(".bak.{}", generation))
+    }
+}
+
+/// Atomically replace `path` with `contents`.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let tmp_path = with_suffix(path, ".tmp");
+    write_synced(&tmp_path, contents)?;
+
+    // Never fall back to writing the target in place: a crash mid-write
+    // would truncate it, which is what this function exists to prevent
+    rename_with_retry(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to write {}: {}", path.display(), e)
+    })?;
+    sync_parent_dir(path);
+    Ok(())
+}
+
+/// Like `write_atomic`, for files only this user should read. On unix the
+/// file is created 0600; elsewhere it inherits the directory's ACL.
+pub fn write_private(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let tmp_path = with_suffix(path, ".tmp");
+    // The mode only applies to new files, so never reuse a leftover
+    let _ = fs::remove_file(&tmp_path);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&tmp_path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    file.write_all(contents)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    drop(file);
+
+    rename_with_retry(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to write {}: {}", path.display(), e)
+    })?;
+    sync_parent_dir(path);
+    Ok(())
+}
+
+/// Atomically replace `path`, first rolling its current contents into the
+/// `.bak` generations.
+pub fn write_atomic_with_backup(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if path.exists() {
+        rotate_backups(path);
+    }
+    write_atomic(path, contents)
+}
+
+/// Content revision (etag) of a file: FNV-1a 64 of its bytes, in hex.
+/// Derived from the contents rather than stored in them, so writes from the
+/// MCP server — which knows nothing about revisions — still change it.
+pub fn content_revision(contents: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in contents {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+fn write_synced(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let mut file = File::create(path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    file.write_all(contents)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Backups are best-effort: failing to keep one must never block the write.
+fn rotate_backups(path: &Path) {
+    for generation in (1..BACKUP_GENERATIONS).rev() {
+        let older = backup_path(path, generation - 1);
+        if older.exists() {
+            let _ = fs::rename(&older, backup_path(path, generation));
+        }
+    }
+    // Copy rather than rename so the target never disappears for readers
+    let _ = fs::copy(path, backup_path(path, 0));
+}
+
+fn rename_with_retry(from: &Path, to: &Path) -> std::io::Result<()> {
+    let mut attempt = 0;
+    loop {
+        match fs::rename(from, to) {
+            Ok(()) => return Ok(()),
+            // NTFS refuses to replace a file another process (watcher, MCP
+            // server) has open; that clears within milliseconds.
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied && attempt + 1 < RENAME_RETRIES => {
+                attempt += 1;
+                std::thread::sleep(Duration::from_millis(RENAME_RETRY_MS * attempt as u64));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) {
+    if let Some(dir) = path.parent() {
+        if let Ok(d) = File::open(dir) {
+            let _ = d.sync_all();
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::TempProject;
+
+    #[test]
+    fn write_atomic_replaces_contents_without_leftovers() {
+        let project = TempProject::new("storage-atomic");
+        let target = project.file("tasks.json");
+        // A .tmp left behind by a crash is simply overwritten
+        project.write("tasks.json.tmp", "garbage from a crash that is longer than the new contents");
+
+        write_atomic(&target, b"{}").unwrap();
+        write_atomic(&target, b"{\"tasks\":[]}").unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "{\"tasks\":[]}");
+        assert!(!project.file("tasks.json.tmp").exists());
+        assert!(!backup_path(&target, 0).exists());
+    }
+
+    #[test]
+    fn failed_rename_leaves_target_untouched() {
+        let project = TempProject::new("storage-rename");
+        // A non-empty directory can't be renamed over, on any platform
+        let target = project.file("tasks.json");
+        project.write("tasks.json/keep", "original");
+
+        let err = write_atomic(&target, b"{}").unwrap_err();
+        assert!(err.contains("Failed to write"), "{}", err);
+        assert_eq!(fs::read_to_string(target.join("keep")).unwrap(), "original");
+        assert!(!project.file("tasks.json.tmp").exists());
+    }
+
+    #[test]
+    fn backups_rotate_through_generations() {
+        let project = TempProject::new("storage-backup");
+        let target = project.file("memories.json");
+        for version in 1..=5 {
+            write_atomic_with_backup(&target, format!("v{}", version).as_bytes()).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&target).unwrap(), "v5");
+        for generation in 0..BACKUP_GENERATIONS {
+            let backup = fs::read_to_string(backup_path(&target, generation)).unwrap();
+            assert_eq!(backup, format!("v{}", 4 - generation));
+        }
+        assert!(!backup_path(&target, BACKUP_GENERATIONS).exists());
+    }
+
+    #[test]
+    fn first_backed_up_write_has_nothing_to_back_up() {
+        let project = TempProject::new("storage-first");
+        let target = project.file("decisions.json");
+        write_atomic_with_backup(&target, b"v1").unwrap();
+        assert!(!backup_path(&target, 0).exists());
+    }
+
+    #[test]
+    fn revision_follows_contents() {
+        assert_eq!(content_revision(b"{}"), content_revision(b"{}"));
+        assert_ne!(content_revision(b"{}"), content_revision(b"{ }"));
+        assert_eq!(content_revision(b"").len(), 16);
+    }
+}
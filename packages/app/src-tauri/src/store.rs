@@ -0,0 +1,508 @@
+// ── Project storage abstraction ──────────────────────────────────
+//
+// A ProjectStore holds the documents of one project's .hello-world/, each
+// addressed by its file name ("collection"). Every command reads and writes
+// project documents through it, so they can run on an in-memory store in
+// tests, and another backend only has to implement four methods:
+//
+// - get: a whole document with its content revision.
+// - modify: replace a document based on its current contents, with no other
+//   writer in between. The typed `update` below and the compare-and-set
+//   `put` are built on it.
+// - append: one entry onto an append-only log (activity, chat history,
+//   chatroom messages).
+// - watch: change notifications, for collections written by other processes.
+//
+// FsStore is the real backend: the same locked, crash-safe JSON files and
+// journals as everything else, and the SQLite mirror for reads when the
+// project uses one. MemoryStore computes revisions the same way, so
+// conflicts behave identically in tests.
+//
+// The layers FsStore is built from (journals, activity rotation, migrations,
+// the SQLite mirror itself) work on the files directly through the path
+// helpers in lib.rs; nothing above them should.
+
+use std::time::Duration;
+
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, DebouncedEventKind};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::journal::{self, Journaled};
+use crate::model::{self, ActivityFile, ChatHistory, ChatroomState, StorageBackend, Versioned};
+use crate::{sqlite, storage};
+
+pub type OnChange = Box<dyn Fn(&[String]) + Send + Sync + 'static>;
+
+/// Makes the new document from the current one (`None` if missing).
+pub type Modify<'a> = Box<dyn FnOnce(Option<Versioned<Value>>) -> Result<Value, String> + 'a>;
+
+pub trait ProjectStore: Send + Sync {
+    /// The document and its revision, or `None` if it doesn't exist.
+    fn get(&self, collection: &str) -> Result<Option<Versioned<Value>>, String>;
+
+    /// Replace the document with what `modify` makes of it, holding off
+    /// every other writer meanwhile. Nothing is written if `modify` fails.
+    /// Returns the new revision.
+    fn modify(&self, collection: &str, modify: Modify<'_>) -> Result<String, String>;
+
+    /// Replace the document if it is still at `expected_revision` (always,
+    /// if `None`). Returns the new revision.
+    fn put(&self, collection: &str, doc: &Value, expected_revision: Option<&str>) -> Result<String, String> {
+        self.modify(collection, Box::new(|current| {
+            let current = current.map_or_else(|| storage::MISSING_REVISION.to_string(), |c| c.revision);
+            crate::check_revision(collection, expected_revision, &current)?;
+            Ok(doc.clone())
+        }))
+    }
+
+    /// Add `entry` to an append-only collection.
+    fn append(&self, collection: &str, entry: Value) -> Result<(), String>;
+
+    /// Call `on_change` with the names of changed collections until the
+    /// returned `Watch` is dropped.
+    fn watch(&self, on_change: OnChange) -> Result<Watch, String>;
+}
+
+/// Keeps a watch alive; dropping it unsubscribes.
+pub struct Watch {
+    _inner: Box<dyn Send>,
+}
+
+fn not_a_log(collection: &str) -> String {
+    format!("{} is not an append-only collection", collection)
+}
+
+// ── Typed access ─────────────────────────────────────────────────
+
+/// The typed equivalents of `read_versioned` / `update_document` for any
+/// store, `dyn ProjectStore` included.
+pub trait Documents: ProjectStore {
+    fn read<T: DeserializeOwned>(&self, collection: &str) -> Result<Versioned<T>, String> {
+        parse(collection, self.get(collection)?)
+    }
+
+    /// Like `read`, but a missing document yields the empty one.
+    fn read_or_default<T: DeserializeOwned + Default>(&self, collection: &str) -> Result<Versioned<T>, String> {
+        parse_or_default(collection, self.get(collection)?)
+    }
+
+    /// Read-modify-write of an existing document. Nothing is written if
+    /// `mutate` fails or the document is no longer at `expected_revision`.
+    fn update<T, R>(
+        &self,
+        collection: &str,
+        expected_revision: Option<&str>,
+        mutate: impl FnOnce(&mut T) -> Result<R, String>,
+    ) -> Result<R, String>
+    where
+        T: DeserializeOwned + Serialize,
+    {
+        update_with(self, collection, expected_revision, |doc| parse(collection, doc), mutate)
+    }
+
+    /// Like `update`, but a missing document starts from the empty one.
+    fn update_or_default<T, R>(
+        &self,
+        collection: &str,
+        expected_revision: Option<&str>,
+        mutate: impl FnOnce(&mut T) -> Result<R, String>,
+    ) -> Result<R, String>
+    where
+        T: DeserializeOwned + Serialize + Default,
+    {
+        update_with(self, collection, expected_revision, |doc| parse_or_default(collection, doc), mutate)
+    }
+
+    fn append_entry<E: Serialize>(&self, collection: &str, entry: &E) -> Result<(), String> {
+        let entry = serde_json::to_value(entry).map_err(|e| format!("Failed to serialize: {}", e))?;
+        self.append(collection, entry)
+    }
+}
+
+impl<S: ProjectStore + ?Sized> Documents for S {}
+
+fn parse<T: DeserializeOwned>(collection: &str, doc: Option<Versioned<Value>>) -> Result<Versioned<T>, String> {
+    let doc = doc.ok_or_else(|| format!("Failed to read {}: not found", collection))?;
+    Ok(Versioned { revision: doc.revision, data: model::parse_value(collection, doc.data)? })
+}
+
+fn parse_or_default<T: DeserializeOwned + Default>(collection: &str, doc: Option<Versioned<Value>>) -> Result<Versioned<T>, String> {
+    match doc {
+        Some(doc) => Ok(Versioned { revision: doc.revision, data: model::parse_value(collection, doc.data)? }),
+        None => Ok(Versioned { revision: storage::MISSING_REVISION.to_string(), data: T::default() }),
+    }
+}
+
+fn update_with<S, T, R>(
+    store: &S,
+    collection: &str,
+    expected_revision: Option<&str>,
+    parse: impl FnOnce(Option<Versioned<Value>>) -> Result<Versioned<T>, String>,
+    mutate: impl FnOnce(&mut T) -> Result<R, String>,
+) -> Result<R, String>
+where
+    S: ProjectStore + ?Sized,
+    T: Serialize,
+{
+    let mut result = None;
+    store.modify(collection, Box::new(|current| {
+        let mut doc = parse(current)?;
+        crate::check_revision(collection, expected_revision, &doc.revision)?;
+        result = Some(mutate(&mut doc.data)?);
+        serde_json::to_value(&doc.data).map_err(|e| format!("Failed to serialize: {}", e))
+    }))?;
+    result.ok_or_else(|| format!("Failed to update {}", collection))
+}
+
+// ── Filesystem (JSON) ────────────────────────────────────────────
+
+pub struct FsStore {
+    project_path: String,
+}
+
+impl FsStore {
+    pub fn new(project_path: &str) -> Self {
+        FsStore { project_path: project_path.to_string() }
+    }
+
+    /// The SQLite copy, if the project keeps one and it matches the JSON.
+    fn get_mirrored(&self, collection: &str) -> Result<Option<Versioned<Value>>, String> {
+        if !sqlite::is_collection(collection) || crate::storage_backend(&self.project_path) != StorageBackend::Sqlite {
+            return Ok(None);
+        }
+        Ok(sqlite::load_current(&self.project_path, collection)?.map(|(data, revision)| Versioned { revision, data }))
+    }
+
+    /// The document as the JSON files hold it.
+    fn get_file(&self, collection: &str) -> Result<Option<Versioned<Value>>, String> {
+        match collection {
+            "activity.json" => self.get_log::<ActivityFile>(collection),
+            "chat-out.json" => self.get_log::<ChatHistory>(collection),
+            "chatroom.json" => self.get_log::<ChatroomState>(collection),
+            _ if !crate::hw_path(&self.project_path, collection).exists() => Ok(None),
+            _ => crate::read_versioned(&self.project_path, collection).map(Some),
+        }
+    }
+
+    /// Snapshot plus journal, as every other reader of a log sees it.
+    fn get_log<T: Journaled>(&self, collection: &str) -> Result<Option<Versioned<Value>>, String> {
+        let exists = crate::hw_path(&self.project_path, collection).exists();
+        let mut doc: T = journal::read(&self.project_path, collection)?;
+        if !exists && doc.entries().is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Versioned {
+            revision: crate::current_revision(&self.project_path, collection)?,
+            data: serde_json::to_value(&doc).map_err(|e| format!("Failed to serialize: {}", e))?,
+        }))
+    }
+}
+
+impl ProjectStore for FsStore {
+    fn get(&self, collection: &str) -> Result<Option<Versioned<Value>>, String> {
+        match self.get_mirrored(collection)? {
+            Some(doc) => Ok(Some(doc)),
+            None => self.get_file(collection),
+        }
+    }
+
+    fn modify(&self, collection: &str, modify: Modify<'_>) -> Result<String, String> {
+        crate::with_file_lock(&self.project_path, collection, || {
+            let doc = modify(self.get_file(collection)?)?;
+            crate::write_json_file(&self.project_path, collection, &doc)?;
+            crate::current_revision(&self.project_path, collection)
+        })
+    }
+
+    fn append(&self, collection: &str, entry: Value) -> Result<(), String> {
+        match collection {
+            "activity.json" => journal::append::<ActivityFile>(&self.project_path, collection, &model::parse_value(collection, entry)?),
+            "chat-out.json" => journal::append::<ChatHistory>(&self.project_path, collection, &model::parse_value(collection, entry)?),
+            "chatroom.json" => journal::append::<ChatroomState>(&self.project_path, collection, &model::parse_value(collection, entry)?),
+            _ => Err(not_a_log(collection)),
+        }
+    }
+
+    fn watch(&self, on_change: OnChange) -> Result<Watch, String> {
+        let dir = crate::hw_path(&self.project_path, "");
+        let mut debouncer = new_debouncer(Duration::from_millis(500), move |result: DebounceEventResult| {
+            let Ok(events) = result else { return };
+            let mut changed: Vec<String> = Vec::new();
+            for event in events.iter().filter(|e| e.kind == DebouncedEventKind::Any) {
+                let Some(name) = event.path.file_name() else { continue };
+                let mut name = name.to_string_lossy().to_string();
+                // A journal append changes what its snapshot's readers return
+                if name.ends_with(".jsonl") {
+                    name.pop();
+                }
+                if (name.ends_with(".json") || name.ends_with(".md")) && !changed.contains(&name) {
+                    changed.push(name);
+                }
+            }
+            if !changed.is_empty() {
+                on_change(&changed);
+            }
+        })
+        .map_err(|e| format!("Failed to start file watcher: {}", e))?;
+        debouncer
+            .watcher()
+            .watch(&dir, notify::RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch {}: {}", dir.display(), e))?;
+        Ok(Watch { _inner: Box::new(debouncer) })
+    }
+}
+
+// ── In memory ────────────────────────────────────────────────────
+
+#[cfg(test)]
+pub use memory::MemoryStore;
+
+/// Documents in a map, for command tests that shouldn't touch disk.
+#[cfg(test)]
+mod memory {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex, Weak};
+
+    use serde_json::Value;
+
+    use super::{not_a_log, Modify, OnChange, ProjectStore, Watch};
+    use crate::model::Versioned;
+    use crate::storage;
+
+    /// Append-only collections and the key of their entry array.
+    const LOGS: &[(&str, &str)] = &[
+        ("activity.json", "activities"),
+        ("chat-out.json", "messages"),
+        ("chatroom.json", "messages"),
+    ];
+
+    fn log_key(collection: &str) -> Option<&'static str> {
+        LOGS.iter().find(|(file, _)| *file == collection).map(|(_, key)| *key)
+    }
+
+    fn revision_of(doc: &Value) -> Result<String, String> {
+        // The pretty form is what FsStore writes, so both stores agree on revisions
+        let contents = serde_json::to_string_pretty(doc)
+            .map_err(|e| format!("Failed to serialize: {}", e))?;
+        Ok(storage::content_revision(contents.as_bytes()))
+    }
+
+    type Subscribers = Mutex<Vec<(u64, Arc<dyn Fn(&[String]) + Send + Sync>)>>;
+
+    #[derive(Default)]
+    pub struct MemoryStore {
+        docs: Mutex<HashMap<String, Value>>,
+        subscribers: Arc<Subscribers>,
+        next_subscriber: AtomicU64,
+    }
+
+    struct Unsubscribe {
+        subscribers: Weak<Subscribers>,
+        id: u64,
+    }
+
+    impl Drop for Unsubscribe {
+        fn drop(&mut self) {
+            if let Some(subscribers) = self.subscribers.upgrade() {
+                if let Ok(mut subscribers) = subscribers.lock() {
+                    subscribers.retain(|(id, _)| *id != self.id);
+                }
+            }
+        }
+    }
+
+    impl MemoryStore {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Seed a document without notifying watchers.
+        pub fn insert(&self, collection: &str, doc: Value) {
+            if let Ok(mut docs) = self.docs.lock() {
+                docs.insert(collection.to_string(), doc);
+            }
+        }
+
+        fn notify(&self, collection: &str) {
+            // Called without the documents locked, so callbacks may use the store
+            let subscribers: Vec<_> = match self.subscribers.lock() {
+                Ok(subscribers) => subscribers.iter().map(|(_, f)| Arc::clone(f)).collect(),
+                Err(_) => return,
+            };
+            let changed = [collection.to_string()];
+            for f in subscribers {
+                f(&changed);
+            }
+        }
+    }
+
+    impl ProjectStore for MemoryStore {
+        fn get(&self, collection: &str) -> Result<Option<Versioned<Value>>, String> {
+            let docs = self.docs.lock().map_err(|_| "Lock poisoned")?;
+            docs.get(collection)
+                .map(|doc| Ok(Versioned { revision: revision_of(doc)?, data: doc.clone() }))
+                .transpose()
+        }
+
+        fn modify(&self, collection: &str, modify: Modify<'_>) -> Result<String, String> {
+            let revision = {
+                let mut docs = self.docs.lock().map_err(|_| "Lock poisoned")?;
+                let current = docs.get(collection)
+                    .map(|doc| Ok::<_, String>(Versioned { revision: revision_of(doc)?, data: doc.clone() }))
+                    .transpose()?;
+                let doc = modify(current)?;
+                let revision = revision_of(&doc)?;
+                docs.insert(collection.to_string(), doc);
+                revision
+            };
+            self.notify(collection);
+            Ok(revision)
+        }
+
+        fn append(&self, collection: &str, entry: Value) -> Result<(), String> {
+            let key = log_key(collection).ok_or_else(|| not_a_log(collection))?;
+            {
+                let mut docs = self.docs.lock().map_err(|_| "Lock poisoned")?;
+                let doc = docs.entry(collection.to_string()).or_insert_with(|| serde_json::json!({ key: [] }));
+                doc[key].as_array_mut()
+                    .ok_or_else(|| format!("Failed to append to {}: {} is not an array", collection, key))?
+                    .push(entry);
+            }
+            self.notify(collection);
+            Ok(())
+        }
+
+        fn watch(&self, on_change: OnChange) -> Result<Watch, String> {
+            let id = self.next_subscriber.fetch_add(1, Ordering::Relaxed);
+            self.subscribers.lock().map_err(|_| "Lock poisoned")?.push((id, Arc::from(on_change)));
+            Ok(Watch { _inner: Box::new(Unsubscribe { subscribers: Arc::downgrade(&self.subscribers), id }) })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ChatHistoryMessage, Direction, Timestamp};
+    use crate::testutil::TempProject;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    fn direction() -> Value {
+        json!({
+            "vision": "Ship it",
+            "notes": [{ "id": "note_1", "text": "Focus", "read": false, "capturedAt": "2026-01-01T00:00:00.000Z" }]
+        })
+    }
+
+    #[test]
+    fn put_rejects_stale_revision() {
+        let store = MemoryStore::new();
+        let first = store.put("direction.json", &direction(), Some(storage::MISSING_REVISION)).unwrap();
+        assert_eq!(store.get("direction.json").unwrap().unwrap().revision, first);
+
+        store.put("direction.json", &json!({ "vision": "Changed" }), Some(&first)).unwrap();
+        let err = store.put("direction.json", &direction(), Some(&first)).unwrap_err();
+        assert!(err.starts_with("Conflict"), "{}", err);
+    }
+
+    #[test]
+    fn typed_update_round_trips_unknown_fields() {
+        let store = MemoryStore::new();
+        let mut doc = direction();
+        doc["addedByNewerVersion"] = json!(true);
+        store.insert("direction.json", doc);
+
+        let revision = store.read::<Direction>("direction.json").unwrap().revision;
+        store.update("direction.json", Some(&revision), |d: &mut Direction| {
+            d.notes[0].read = true;
+            Ok(())
+        }).unwrap();
+
+        let stored = store.get("direction.json").unwrap().unwrap().data;
+        assert_eq!(stored["notes"][0]["read"], json!(true));
+        assert_eq!(stored["addedByNewerVersion"], json!(true));
+        let err = store.update("direction.json", Some(&revision), |_: &mut Direction| Ok(())).unwrap_err();
+        assert!(err.starts_with("Conflict"), "{}", err);
+    }
+
+    #[test]
+    fn append_only_to_logs() {
+        let store = MemoryStore::new();
+        let message = ChatHistoryMessage {
+            id: "msg_1".to_string(),
+            role: "pat".to_string(),
+            text: "hi".to_string(),
+            timestamp: Timestamp::Millis(1),
+            extra: Default::default(),
+        };
+        store.append_entry("chat-out.json", &message).unwrap();
+        let history: ChatHistory = store.read("chat-out.json").unwrap().data;
+        assert_eq!(history.messages.len(), 1);
+        assert!(store.append("tasks.json", json!({})).is_err());
+    }
+
+    #[test]
+    fn watch_stops_when_dropped() {
+        let store = MemoryStore::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let watch = store.watch(Box::new(move |files| sink.lock().unwrap().extend_from_slice(files))).unwrap();
+
+        store.put("mode.json", &json!({ "overdrive": true }), None).unwrap();
+        drop(watch);
+        store.put("mode.json", &json!({ "overdrive": false }), None).unwrap();
+        assert_eq!(*seen.lock().unwrap(), vec!["mode.json".to_string()]);
+    }
+
+    #[test]
+    fn concurrent_updates_run_one_at_a_time() {
+        let project = TempProject::new("store-concurrent");
+        let store = FsStore::new(project.path());
+        let calls = AtomicUsize::new(0);
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..5 {
+                        store.update_or_default("counter.json", None, |doc: &mut Value| {
+                            calls.fetch_add(1, Ordering::Relaxed);
+                            doc["n"] = json!(doc["n"].as_u64().unwrap_or(0) + 1);
+                            Ok(())
+                        }).unwrap();
+                    }
+                });
+            }
+        });
+
+        assert_eq!(store.get("counter.json").unwrap().unwrap().data["n"], json!(20));
+        // Each mutation ran once, on the document as the previous one left it
+        assert_eq!(calls.load(Ordering::Relaxed), 20);
+    }
+
+    #[test]
+    fn fs_store_matches_memory_store() {
+        let project = TempProject::new("store");
+        let fs_store = FsStore::new(project.path());
+        let memory = MemoryStore::new();
+
+        for store in [&fs_store as &dyn ProjectStore, &memory] {
+            assert!(store.get("direction.json").unwrap().is_none());
+            let revision = store.put("direction.json", &direction(), Some(storage::MISSING_REVISION)).unwrap();
+            assert_eq!(store.get("direction.json").unwrap().unwrap().revision, revision);
+            store.append("activity.json", json!({
+                "id": "act_1", "type": "note", "description": "d", "details": "", "timestamp": "2026-01-01T00:00:00.000Z"
+            })).unwrap();
+            let activity: ActivityFile = store.read("activity.json").unwrap().data;
+            assert_eq!(activity.activities.len(), 1);
+        }
+        assert_eq!(
+            fs_store.get("direction.json").unwrap().unwrap().revision,
+            memory.get("direction.json").unwrap().unwrap().revision
+        );
+    }
+}
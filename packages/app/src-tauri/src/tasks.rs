@@ -0,0 +1,449 @@
+// ── Task editing (tasks.json) ────────────────────────────────────
+//
+// Pure operations on TasksFile; the commands in lib.rs wrap them in a locked
+// read-modify-write. Mirrors StateManager.addTask/updateTask/removeTask in
+// packages/core, plus the checks the Node side leaves to the caller: status
+// transitions, dependency references and ordering.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::model::{Task, TaskSize, TaskStatus, TasksFile};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTask {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub status: TaskStatus,
+    #[serde(default)]
+    pub size: Option<TaskSize>,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub milestone_id: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+/// Fields to change. For the optional fields, an absent key leaves the value
+/// alone and an explicit `null` clears it.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskPatch {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<TaskStatus>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub size: Option<Option<TaskSize>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub parent_id: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub assignee: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub milestone_id: Option<Option<String>>,
+    pub tags: Option<Vec<String>>,
+    pub depends_on: Option<Vec<String>>,
+}
+
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+fn status_label(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Todo => "todo",
+        TaskStatus::InProgress => "in_progress",
+        TaskStatus::Done => "done",
+        TaskStatus::Blocked => "blocked",
+    }
+}
+
+/// Blocked tasks must be unblocked before they can finish, and finished
+/// tasks are reopened to todo rather than jumping straight back to work.
+fn transition_allowed(from: TaskStatus, to: TaskStatus) -> bool {
+    use TaskStatus::*;
+    from == to
+        || matches!(
+            (from, to),
+            (Todo, InProgress | Blocked | Done)
+                | (InProgress, Todo | Blocked | Done)
+                | (Blocked, Todo | InProgress)
+                | (Done, Todo)
+        )
+}
+
+fn find<'a>(file: &'a TasksFile, id: &str) -> Result<&'a Task, String> {
+    file.tasks.iter().find(|t| t.id == id).ok_or_else(|| format!("Task not found: {}", id))
+}
+
+fn validate_title(title: &str) -> Result<String, String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Task title cannot be empty".to_string());
+    }
+    Ok(title.to_string())
+}
+
+/// Drop duplicates (keeping the first) and check every id refers to another
+/// existing task.
+fn normalize_depends_on(file: &TasksFile, id: &str, depends_on: Vec<String>) -> Result<Vec<String>, String> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for dep in depends_on {
+        if dep == id {
+            return Err(format!("Task {} cannot depend on itself", id));
+        }
+        if !file.tasks.iter().any(|t| t.id == dep) {
+            return Err(format!("Unknown dependency: {}", dep));
+        }
+        if seen.insert(dep.clone()) {
+            out.push(dep);
+        }
+    }
+    Ok(out)
+}
+
+/// Would `id` depending on `depends_on` close a loop? Walks everything
+/// reachable from the new dependencies looking for `id`.
+fn check_dependency_cycle(file: &TasksFile, id: &str, depends_on: &[String]) -> Result<(), String> {
+    let mut stack: Vec<&str> = depends_on.iter().map(String::as_str).collect();
+    let mut visited = HashSet::new();
+    while let Some(current) = stack.pop() {
+        if current == id {
+            return Err(format!("Dependency cycle: {} would end up depending on itself", id));
+        }
+        if !visited.insert(current) {
+            continue;
+        }
+        if let Some(task) = file.tasks.iter().find(|t| t.id == current) {
+            stack.extend(task.depends_on.iter().map(String::as_str));
+        }
+    }
+    Ok(())
+}
+
+fn check_parent(file: &TasksFile, id: &str, parent_id: Option<&str>) -> Result<(), String> {
+    let Some(mut current) = parent_id else { return Ok(()) };
+    // A loop that doesn't pass through `id` can still be on disk (hand edits,
+    // other writers), so stop at the first repeat rather than spin on it
+    let mut visited = HashSet::new();
+    loop {
+        if current == id {
+            return Err(format!("Task {} cannot be its own ancestor", id));
+        }
+        if !visited.insert(current) {
+            return Err(format!("Parent chain of {} contains a cycle", id));
+        }
+        let parent = file.tasks.iter()
+            .find(|t| t.id == current)
+            .ok_or_else(|| format!("Unknown parent task: {}", current))?;
+        match parent.parent_id.as_deref() {
+            Some(next) => current = next,
+            None => return Ok(()),
+        }
+    }
+}
+
+fn check_milestone(file: &TasksFile, milestone_id: Option<&str>) -> Result<(), String> {
+    match milestone_id {
+        Some(m) if !file.milestones.iter().any(|ms| ms.id == m) => Err(format!("Unknown milestone: {}", m)),
+        _ => Ok(()),
+    }
+}
+
+/// Starting or finishing a task needs its dependencies done; Epics also need
+/// child tasks to start, and all of them done to finish.
+fn check_status(file: &TasksFile, task: &Task, to: TaskStatus) -> Result<(), String> {
+    if !matches!(to, TaskStatus::InProgress | TaskStatus::Done) || task.status == to {
+        return Ok(());
+    }
+
+    let unfinished: Vec<&str> = task.depends_on.iter()
+        .filter(|dep| file.tasks.iter().any(|t| &t.id == *dep && t.status != TaskStatus::Done))
+        .map(String::as_str)
+        .collect();
+    if !unfinished.is_empty() {
+        return Err(format!(
+            "Cannot move \"{}\" to {} — waiting on {}",
+            task.title, status_label(to), unfinished.join(", ")
+        ));
+    }
+
+    if task.size == Some(TaskSize::Epic) {
+        let children: Vec<&Task> = file.tasks.iter()
+            .filter(|t| t.parent_id.as_deref() == Some(task.id.as_str()))
+            .collect();
+        if to == TaskStatus::InProgress && children.is_empty() {
+            return Err(format!("Cannot start Epic \"{}\" — decompose it into child tasks first", task.title));
+        }
+        if to == TaskStatus::Done && children.iter().any(|c| c.status != TaskStatus::Done) {
+            return Err(format!("Cannot finish Epic \"{}\" while child tasks are still open", task.title));
+        }
+    }
+    Ok(())
+}
+
+pub fn add(file: &mut TasksFile, input: NewTask) -> Result<Task, String> {
+    let id = loop {
+        let id = crate::generate_id("t");
+        if !file.tasks.iter().any(|t| t.id == id) {
+            break id;
+        }
+    };
+    let timestamp = crate::utc_now_iso();
+    let mut task = Task {
+        id,
+        title: validate_title(&input.title)?,
+        description: input.description,
+        status: TaskStatus::Todo,
+        size: input.size,
+        parent_id: input.parent_id,
+        assignee: input.assignee,
+        milestone_id: input.milestone_id,
+        tags: input.tags,
+        depends_on: Vec::new(),
+        created_at: timestamp.clone(),
+        updated_at: timestamp,
+        extra: Default::default(),
+    };
+    // A new task can't be part of a cycle, but it can point at missing ids
+    task.depends_on = normalize_depends_on(file, &task.id, input.depends_on)?;
+    check_parent(file, &task.id, task.parent_id.as_deref())?;
+    check_milestone(file, task.milestone_id.as_deref())?;
+    if !transition_allowed(TaskStatus::Todo, input.status) {
+        return Err(format!("A new task cannot start as {}", status_label(input.status)));
+    }
+    check_status(file, &task, input.status)?;
+    task.status = input.status;
+
+    file.tasks.push(task.clone());
+    Ok(task)
+}
+
+pub fn update(file: &mut TasksFile, id: &str, patch: TaskPatch) -> Result<Task, String> {
+    let mut task = find(file, id)?.clone();
+
+    if let Some(title) = patch.title {
+        task.title = validate_title(&title)?;
+    }
+    if let Some(description) = patch.description {
+        task.description = description;
+    }
+    if let Some(size) = patch.size {
+        task.size = size;
+    }
+    if let Some(assignee) = patch.assignee {
+        task.assignee = assignee;
+    }
+    if let Some(tags) = patch.tags {
+        task.tags = tags;
+    }
+    if let Some(parent_id) = patch.parent_id {
+        check_parent(file, id, parent_id.as_deref())?;
+        task.parent_id = parent_id;
+    }
+    if let Some(milestone_id) = patch.milestone_id {
+        check_milestone(file, milestone_id.as_deref())?;
+        task.milestone_id = milestone_id;
+    }
+    if let Some(depends_on) = patch.depends_on {
+        let depends_on = normalize_depends_on(file, id, depends_on)?;
+        check_dependency_cycle(file, id, &depends_on)?;
+        task.depends_on = depends_on;
+    }
+    // Checked last so the rules see the task's new size and dependencies
+    if let Some(status) = patch.status {
+        if !transition_allowed(task.status, status) {
+            return Err(format!(
+                "Cannot move \"{}\" from {} to {}",
+                task.title, status_label(task.status), status_label(status)
+            ));
+        }
+        check_status(file, &task, status)?;
+        task.status = status;
+    }
+
+    task.updated_at = crate::utc_now_iso();
+    let slot = file.tasks.iter_mut().find(|t| t.id == id).expect("found above");
+    *slot = task.clone();
+    Ok(task)
+}
+
+/// Refuses while other tasks depend on or are children of `id`, so deleting
+/// never leaves dangling references behind.
+pub fn delete(file: &mut TasksFile, id: &str) -> Result<Task, String> {
+    find(file, id)?;
+    let referencing: Vec<&str> = file.tasks.iter()
+        .filter(|t| t.depends_on.iter().any(|d| d == id) || t.parent_id.as_deref() == Some(id))
+        .map(|t| t.id.as_str())
+        .collect();
+    if !referencing.is_empty() {
+        return Err(format!(
+            "Cannot delete task {}: referenced by {}. Remove those references first.",
+            id, referencing.join(", ")
+        ));
+    }
+    let index = file.tasks.iter().position(|t| t.id == id).expect("found above");
+    Ok(file.tasks.remove(index))
+}
+
+/// Put the listed tasks in the given order. Tasks not listed keep their
+/// positions; the listed ones are shuffled among the slots they already
+/// occupy, so reordering one board column leaves the others untouched.
+pub fn reorder(file: &mut TasksFile, ordered_ids: &[String]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for id in ordered_ids {
+        find(file, id)?;
+        if !seen.insert(id.as_str()) {
+            return Err(format!("Task listed twice: {}", id));
+        }
+    }
+
+    let slots: Vec<usize> = file.tasks.iter()
+        .enumerate()
+        .filter(|(_, t)| seen.contains(t.id.as_str()))
+        .map(|(i, _)| i)
+        .collect();
+    let reordered: Vec<Task> = ordered_ids.iter()
+        .map(|id| file.tasks.iter().find(|t| &t.id == id).cloned().expect("checked above"))
+        .collect();
+    for (slot, task) in slots.into_iter().zip(reordered) {
+        file.tasks[slot] = task;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn new_task(file: &mut TasksFile, input: serde_json::Value) -> Result<Task, String> {
+        add(file, serde_json::from_value(input).unwrap())
+    }
+
+    fn patch(file: &mut TasksFile, id: &str, input: serde_json::Value) -> Result<Task, String> {
+        update(file, id, serde_json::from_value(input).unwrap())
+    }
+
+    #[test]
+    fn rejects_disallowed_transitions() {
+        let mut file = TasksFile::default();
+        let t = new_task(&mut file, json!({ "title": "Write docs" })).unwrap();
+
+        patch(&mut file, &t.id, json!({ "status": "blocked" })).unwrap();
+        let err = patch(&mut file, &t.id, json!({ "status": "done" })).unwrap_err();
+        assert!(err.contains("from blocked to done"), "{}", err);
+
+        patch(&mut file, &t.id, json!({ "status": "todo" })).unwrap();
+        patch(&mut file, &t.id, json!({ "status": "done" })).unwrap();
+        let err = patch(&mut file, &t.id, json!({ "status": "in_progress" })).unwrap_err();
+        assert!(err.contains("from done to in_progress"), "{}", err);
+
+        // A new task is checked as a move out of todo, so it may start blocked
+        new_task(&mut file, json!({ "title": "Waiting", "status": "blocked" })).unwrap();
+    }
+
+    #[test]
+    fn rejects_dependency_cycles() {
+        let mut file = TasksFile::default();
+        let a = new_task(&mut file, json!({ "title": "A" })).unwrap();
+        let b = new_task(&mut file, json!({ "title": "B", "dependsOn": [a.id] })).unwrap();
+        let c = new_task(&mut file, json!({ "title": "C", "dependsOn": [b.id] })).unwrap();
+
+        let err = patch(&mut file, &a.id, json!({ "dependsOn": [c.id] })).unwrap_err();
+        assert!(err.contains("Dependency cycle"), "{}", err);
+        let err = patch(&mut file, &a.id, json!({ "dependsOn": [a.id] })).unwrap_err();
+        assert!(err.contains("cannot depend on itself"), "{}", err);
+        let err = patch(&mut file, &a.id, json!({ "dependsOn": ["t_missing"] })).unwrap_err();
+        assert!(err.contains("Unknown dependency"), "{}", err);
+
+        // Duplicates collapse; a diamond is not a cycle
+        let d = patch(&mut file, &c.id, json!({ "dependsOn": [a.id, b.id, a.id] })).unwrap();
+        assert_eq!(d.depends_on, vec![a.id.clone(), b.id.clone()]);
+        assert!(file.tasks.iter().find(|t| t.id == a.id).unwrap().depends_on.is_empty());
+    }
+
+    #[test]
+    fn unfinished_dependencies_block_progress() {
+        let mut file = TasksFile::default();
+        let a = new_task(&mut file, json!({ "title": "A" })).unwrap();
+        let b = new_task(&mut file, json!({ "title": "B", "dependsOn": [a.id] })).unwrap();
+
+        let err = patch(&mut file, &b.id, json!({ "status": "in_progress" })).unwrap_err();
+        assert!(err.contains(&format!("waiting on {}", a.id)), "{}", err);
+        patch(&mut file, &a.id, json!({ "status": "done" })).unwrap();
+        patch(&mut file, &b.id, json!({ "status": "in_progress" })).unwrap();
+    }
+
+    #[test]
+    fn epics_need_children_to_start_and_finish() {
+        let mut file = TasksFile::default();
+        let epic = new_task(&mut file, json!({ "title": "Billing", "size": "Epic" })).unwrap();
+
+        let err = patch(&mut file, &epic.id, json!({ "status": "in_progress" })).unwrap_err();
+        assert!(err.contains("decompose it"), "{}", err);
+
+        let child = new_task(&mut file, json!({ "title": "Invoices", "parentId": epic.id })).unwrap();
+        patch(&mut file, &epic.id, json!({ "status": "in_progress" })).unwrap();
+        let err = patch(&mut file, &epic.id, json!({ "status": "done" })).unwrap_err();
+        assert!(err.contains("child tasks are still open"), "{}", err);
+
+        patch(&mut file, &child.id, json!({ "status": "done" })).unwrap();
+        patch(&mut file, &epic.id, json!({ "status": "done" })).unwrap();
+    }
+
+    #[test]
+    fn rejects_parent_loops_and_referenced_deletes() {
+        let mut file = TasksFile::default();
+        let parent = new_task(&mut file, json!({ "title": "Parent" })).unwrap();
+        let child = new_task(&mut file, json!({ "title": "Child", "parentId": parent.id })).unwrap();
+
+        let err = patch(&mut file, &parent.id, json!({ "parentId": child.id })).unwrap_err();
+        assert!(err.contains("own ancestor"), "{}", err);
+        let err = delete(&mut file, &parent.id).unwrap_err();
+        assert!(err.contains(&child.id), "{}", err);
+
+        delete(&mut file, &child.id).unwrap();
+        delete(&mut file, &parent.id).unwrap();
+        assert!(file.tasks.is_empty());
+    }
+
+    #[test]
+    fn stops_at_a_parent_cycle_already_on_disk() {
+        let mut file = TasksFile::default();
+        let a = new_task(&mut file, json!({ "title": "A" })).unwrap();
+        let b = new_task(&mut file, json!({ "title": "B", "parentId": a.id })).unwrap();
+        let c = new_task(&mut file, json!({ "title": "C" })).unwrap();
+        // A <-> B, as a hand edit might leave it
+        file.tasks.iter_mut().find(|t| t.id == a.id).unwrap().parent_id = Some(b.id.clone());
+
+        let err = patch(&mut file, &c.id, json!({ "parentId": a.id })).unwrap_err();
+        assert_eq!(err, format!("Parent chain of {} contains a cycle", c.id));
+    }
+
+    #[test]
+    fn reorder_only_moves_listed_tasks() {
+        let mut file = TasksFile::default();
+        let ids: Vec<String> = ["A", "B", "C", "D"].iter()
+            .map(|title| new_task(&mut file, json!({ "title": title })).unwrap().id)
+            .collect();
+
+        reorder(&mut file, &[ids[3].clone(), ids[1].clone()]).unwrap();
+        let titles: Vec<&str> = file.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, ["A", "D", "C", "B"]);
+        assert!(reorder(&mut file, &[ids[0].clone(), ids[0].clone()]).is_err());
+    }
+}
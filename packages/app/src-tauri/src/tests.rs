@@ -0,0 +1,434 @@
+// ── Command layer tests ──────────────────────────────────────────
+//
+// Commands run against a temp-dir copy of this repo's own .hello-world/, so
+// they see the same shapes the MCP server writes. The samples are live
+// project data, so a test that needs a particular state (an open question,
+// an open session) seeds it rather than relying on what's there. The
+// store-generic halves of the commands also run against MemoryStore.
+
+use std::fs;
+
+use serde_json::{json, Value};
+
+use super::*;
+use crate::model::QuestionStatus;
+use crate::store::MemoryStore;
+use crate::testutil::TempProject;
+
+const SAMPLES: &[(&str, &str)] = &[
+    ("config.json", include_str!("../../../../.hello-world/config.json")),
+    ("tasks.json", include_str!("../../../../.hello-world/tasks.json")),
+    ("decisions.json", include_str!("../../../../.hello-world/decisions.json")),
+    ("questions.json", include_str!("../../../../.hello-world/questions.json")),
+    ("approvals.json", include_str!("../../../../.hello-world/approvals.json")),
+    ("direction.json", include_str!("../../../../.hello-world/direction.json")),
+    ("watchers.json", include_str!("../../../../.hello-world/watchers.json")),
+    ("chatroom.json", include_str!("../../../../.hello-world/chatroom.json")),
+    ("sessions.json", include_str!("../../../../.hello-world/sessions.json")),
+    ("mode.json", include_str!("../../../../.hello-world/mode.json")),
+];
+
+/// A project directory holding the sample files, removed on drop.
+struct Fixture {
+    project: TempProject,
+}
+
+impl Fixture {
+    fn new(name: &str) -> Self {
+        let project = TempProject::new(name);
+        for (file, contents) in SAMPLES {
+            project.write(file, contents);
+        }
+        Fixture { project }
+    }
+
+    fn path(&self) -> &str {
+        self.project.path()
+    }
+
+    fn json(&self, file: &str) -> Value {
+        read_json_file(self.path(), file).unwrap()
+    }
+
+    fn revision(&self, file: &str) -> String {
+        current_revision(self.path(), file).unwrap()
+    }
+
+    /// Edit a file the way another process would, outside the commands.
+    fn edit(&self, file: &str, f: impl FnOnce(&mut Value)) {
+        let mut doc = self.json(file);
+        f(&mut doc);
+        write_json_file(self.path(), file, &doc).unwrap();
+    }
+}
+
+fn sample(file: &str) -> Value {
+    let (_, contents) = SAMPLES.iter().find(|(f, _)| *f == file).unwrap();
+    serde_json::from_str(contents).unwrap()
+}
+
+fn pending_approval(id: &str) -> Value {
+    json!({
+        "id": id,
+        "action": "git_push",
+        "description": "Push to main",
+        "tier": "block",
+        "status": "pending",
+        "createdAt": "2026-02-25T09:25:58.163Z"
+    })
+}
+
+fn assert_conflict(result: Result<impl std::fmt::Debug, String>) {
+    let err = result.unwrap_err();
+    assert!(err.starts_with("Conflict"), "{}", err);
+}
+
+// ── get_state ────────────────────────────────────────────────────
+
+#[test]
+fn get_state_merges_split_files() {
+    let fx = Fixture::new("state");
+    let state = get_state(fx.path()).unwrap();
+
+    assert_eq!(state.tasks.len(), sample("tasks.json")["tasks"].as_array().unwrap().len());
+    assert_eq!(state.decisions.len(), sample("decisions.json")["decisions"].as_array().unwrap().len());
+    assert_eq!(state.questions.len(), sample("questions.json")["questions"].as_array().unwrap().len());
+    assert_eq!(state.revisions.tasks, fx.revision("tasks.json"));
+    assert_eq!(state.revisions.decisions, fx.revision("decisions.json"));
+    assert_eq!(state.revisions.questions, fx.revision("questions.json"));
+}
+
+#[test]
+fn get_state_treats_missing_files_as_empty() {
+    let fx = Fixture::new("state-missing");
+    fs::remove_file(hw_path(fx.path(), "questions.json")).unwrap();
+
+    let state = get_state(fx.path()).unwrap();
+    assert!(state.questions.is_empty());
+    assert_eq!(state.revisions.questions, storage::MISSING_REVISION);
+    assert!(!state.tasks.is_empty());
+}
+
+#[test]
+fn get_state_revision_tracks_each_file() {
+    let fx = Fixture::new("state-revisions");
+    let before = get_state(fx.path()).unwrap().revisions;
+    fx.edit("decisions.json", |d| d["decisions"].as_array_mut().unwrap().truncate(1));
+
+    let after = get_state(fx.path()).unwrap().revisions;
+    assert_eq!(after.tasks, before.tasks);
+    assert_eq!(after.questions, before.questions);
+    assert_ne!(after.decisions, before.decisions);
+}
+
+// ── resolve_approval ─────────────────────────────────────────────
+
+#[test]
+fn resolve_approval_moves_request_to_resolved() {
+    let fx = Fixture::new("approve");
+    fx.edit("approvals.json", |d| d["pending"].as_array_mut().unwrap().push(pending_approval("apr_test")));
+    let resolved_before = fx.json("approvals.json")["resolved"].as_array().unwrap().len();
+
+    resolve_approval(fx.path(), "apr_test".into(), "rejected".into(), Some(fx.revision("approvals.json"))).unwrap();
+
+    let doc = fx.json("approvals.json");
+    assert!(doc["pending"].as_array().unwrap().is_empty());
+    let resolved = doc["resolved"].as_array().unwrap();
+    assert_eq!(resolved.len(), resolved_before + 1);
+    let last = resolved.last().unwrap();
+    assert_eq!(last["id"], "apr_test");
+    assert_eq!(last["status"], "rejected");
+    assert!(last["resolvedAt"].is_string());
+    // Fields the app doesn't model survive the rewrite
+    assert_eq!(last["createdAt"], "2026-02-25T09:25:58.163Z");
+}
+
+#[test]
+fn resolve_approval_rejects_bad_input_without_writing() {
+    let fx = Fixture::new("approve-bad");
+    fx.edit("approvals.json", |d| d["pending"].as_array_mut().unwrap().push(pending_approval("apr_test")));
+    let revision = fx.revision("approvals.json");
+
+    let err = resolve_approval(fx.path(), "apr_test".into(), "maybe".into(), None).unwrap_err();
+    assert!(err.contains("Invalid approval decision"), "{}", err);
+    let err = resolve_approval(fx.path(), "apr_missing".into(), "approved".into(), None).unwrap_err();
+    assert!(err.contains("not found"), "{}", err);
+    assert_eq!(fx.revision("approvals.json"), revision);
+}
+
+#[test]
+fn resolve_approval_detects_concurrent_write() {
+    let fx = Fixture::new("approve-conflict");
+    fx.edit("approvals.json", |d| d["pending"].as_array_mut().unwrap().push(pending_approval("apr_test")));
+    let stale = fx.revision("approvals.json");
+    fx.edit("approvals.json", |d| d["pending"].as_array_mut().unwrap().push(pending_approval("apr_other")));
+
+    assert_conflict(resolve_approval(fx.path(), "apr_test".into(), "approved".into(), Some(stale)));
+    assert_eq!(fx.json("approvals.json")["pending"].as_array().unwrap().len(), 2);
+}
+
+// ── answer_question ──────────────────────────────────────────────
+
+/// Add an open question, whatever state the sample's questions are in.
+fn open_question(fx: &Fixture) -> String {
+    fx.edit("questions.json", |d| {
+        d["questions"].as_array_mut().unwrap().push(json!({
+            "id": "q_test",
+            "question": "Which database?",
+            "status": "open",
+            "createdAt": "2026-02-25T09:25:58.163Z"
+        }));
+    });
+    "q_test".to_string()
+}
+
+#[test]
+fn answer_question_defaults_to_pat() {
+    let fx = Fixture::new("answer");
+    let id = open_question(&fx);
+
+    let q = answer_question(fx.path(), id.clone(), "  Use SQLite  ".into(), None, Some(fx.revision("questions.json"))).unwrap();
+    assert_eq!(q.answer.as_deref(), Some("Use SQLite"));
+    assert_eq!(q.answered_by, Some(DecidedBy::Pat));
+
+    let stored = get_state(fx.path()).unwrap().questions.into_iter().find(|q| q.id == id).unwrap();
+    assert_eq!(stored.status, QuestionStatus::Answered);
+    assert_eq!(stored.answer.as_deref(), Some("Use SQLite"));
+}
+
+#[test]
+fn answer_question_rejects_empty_and_stale() {
+    let fx = Fixture::new("answer-bad");
+    let id = open_question(&fx);
+    let stale = fx.revision("questions.json");
+
+    let err = answer_question(fx.path(), id.clone(), "   ".into(), None, None).unwrap_err();
+    assert!(err.contains("empty"), "{}", err);
+    assert!(answer_question(fx.path(), "q_missing".into(), "yes".into(), None, None).is_err());
+    assert_eq!(fx.revision("questions.json"), stale);
+
+    fx.edit("questions.json", |d| d["touched"] = json!(true));
+    assert_conflict(answer_question(fx.path(), id, "yes".into(), Some(DecidedBy::Claude), Some(stale)));
+}
+
+// ── link_question ────────────────────────────────────────────────
+
+#[test]
+fn link_question_rejects_missing_targets_without_writing() {
+    let fx = Fixture::new("link-missing");
+    let id = open_question(&fx);
+    let before = fx.revision("questions.json");
+
+    let err = link_question(fx.path(), id.clone(), Some("t_missing".into()), None, None).unwrap_err();
+    assert_eq!(err, "Task not found: t_missing");
+    let err = link_question(fx.path(), id.clone(), None, Some("d_missing".into()), None).unwrap_err();
+    assert_eq!(err, "Decision not found: d_missing");
+    let err = ask_question(fx.path(), "Why?".into(), None, Some("t_missing".into()), None).unwrap_err();
+    assert_eq!(err, "Task not found: t_missing");
+    assert_eq!(fx.revision("questions.json"), before);
+
+    let task_id = fx.json("tasks.json")["tasks"][0]["id"].as_str().unwrap().to_string();
+    let linked = link_question(fx.path(), id.clone(), Some(task_id.clone()), None, None).unwrap();
+    assert_eq!(linked.linked_task_id, Some(task_id));
+    let cleared = link_question(fx.path(), id, Some(String::new()), None, None).unwrap();
+    assert_eq!(cleared.linked_task_id, None);
+}
+
+// ── kill_watcher ─────────────────────────────────────────────────
+
+#[test]
+fn kill_watcher_rejects_unknown_id() {
+    let fx = Fixture::new("kill-unknown");
+    let revision = fx.revision("watchers.json");
+    let err = kill_watcher(fx.path(), "w_missing".into(), None).unwrap_err();
+    assert!(err.contains("not found"), "{}", err);
+    assert_eq!(fx.revision("watchers.json"), revision);
+}
+
+#[cfg(unix)]
+#[test]
+fn kill_watcher_kills_process_and_completes_it() {
+    let fx = Fixture::new("kill");
+    let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+    fx.edit("watchers.json", |d| {
+        d["active"] = json!([{
+            "id": "w_test",
+            "type": "copy",
+            "label": "Copy build",
+            "pid": child.id(),
+            "spawnedAt": "2026-02-25T09:25:58.163Z",
+            "status": "active",
+            "config": { "copies": [], "label": "Copy build", "timeoutMinutes": 5 }
+        }]);
+    });
+
+    kill_watcher(fx.path(), "w_test".into(), Some(fx.revision("watchers.json"))).unwrap();
+
+    let status = child.wait().unwrap();
+    assert!(!status.success());
+    let doc = fx.json("watchers.json");
+    assert!(doc["active"].as_array().unwrap().is_empty());
+    let last = doc["completed"].as_array().unwrap().last().unwrap().clone();
+    assert_eq!(last["id"], "w_test");
+    assert_eq!(last["status"], "killed");
+    assert!(last["completedAt"].is_string());
+}
+
+// ── mark_direction_note_read ─────────────────────────────────────
+
+#[test]
+fn mark_direction_note_read_only_touches_that_note() {
+    let fx = Fixture::new("direction");
+    fx.edit("direction.json", |d| {
+        d["notes"] = json!(["n_a", "n_b", "n_c"].map(|id| json!({
+            "id": id, "text": "Note", "read": false, "capturedAt": "2026-02-25T09:25:58.163Z"
+        })));
+    });
+    let before = fx.json("direction.json");
+    let target = "n_b".to_string();
+
+    mark_direction_note_read(fx.path(), target.clone(), Some(fx.revision("direction.json"))).unwrap();
+
+    let after = fx.json("direction.json");
+    for note in after["notes"].as_array().unwrap() {
+        assert_eq!(note["read"], json!(note["id"] == target.as_str()), "{}", note["id"]);
+    }
+    assert_eq!(after["vision"], before["vision"]);
+    assert_eq!(after["scope"], before["scope"]);
+}
+
+#[test]
+fn mark_direction_note_read_detects_concurrent_write() {
+    let fx = Fixture::new("direction-conflict");
+    let stale = fx.revision("direction.json");
+    fx.edit("direction.json", |d| d["vision"] = json!("Changed elsewhere"));
+    assert_conflict(mark_direction_note_read(fx.path(), "n_001".into(), Some(stale)));
+}
+
+// ── Chatroom ─────────────────────────────────────────────────────
+
+#[test]
+fn post_pat_chatroom_message_queues_and_appends() {
+    let fx = Fixture::new("chatroom");
+    let before = get_chatroom(fx.path()).unwrap();
+
+    post_pat_chatroom_message(fx.path(), "Ship it".into(), Some(before.revision.clone())).unwrap();
+
+    let after = get_chatroom(fx.path()).unwrap();
+    assert_ne!(after.revision, before.revision);
+    assert_eq!(after.data.session.pending_pat_message.as_deref(), Some("Ship it"));
+    assert!(!after.data.session.waiting_for_input);
+    assert_eq!(after.data.messages.len(), before.data.messages.len() + 1);
+    let last = after.data.messages.last().unwrap();
+    assert_eq!(last.agent_id, "pat");
+    assert_eq!(last.text, "Ship it");
+    assert_eq!(last.message_type, ChatMessageType::Pat);
+    assert_eq!(after.data.agents.len(), before.data.agents.len());
+
+    assert_conflict(post_pat_chatroom_message(fx.path(), "Again".into(), Some(before.revision)));
+}
+
+// ── Session stamping on close ────────────────────────────────────
+
+#[test]
+fn stamp_session_end_stamps_latest_open_session_once() {
+    let fx = Fixture::new("close");
+    fx.edit("sessions.json", |d| {
+        let session = |id: &str, started: &str| json!({
+            "id": id, "startedAt": started, "tasksCompleted": [], "decisionsMade": [],
+            "costUsd": 0, "tokensUsed": 0, "summary": ""
+        });
+        let mut ended = session("s_1", "2026-02-24T09:00:00.000Z");
+        ended["endedAt"] = json!("2026-02-24T10:00:00.000Z");
+        d["sessions"] = json!([ended, session("s_2", "2026-02-25T09:00:00.000Z")]);
+    });
+    let sessions_before = fx.json("sessions.json")["sessions"].clone();
+
+    stamp_session_end(fx.path()).unwrap();
+    let after = fx.json("sessions.json")["sessions"].clone();
+    let count = after.as_array().unwrap().len();
+    let ended_at = after[count - 1]["endedAt"].as_str().unwrap().to_string();
+    assert!(millis_from_iso(&ended_at).is_some(), "{}", ended_at);
+    // Earlier sessions are left as they were
+    assert_eq!(after[count - 2], sessions_before[count - 2]);
+
+    stamp_session_end(fx.path()).unwrap();
+    assert_eq!(fx.json("sessions.json")["sessions"][count - 1]["endedAt"], json!(ended_at));
+}
+
+#[test]
+fn stamp_session_end_without_sessions_file_creates_nothing() {
+    let fx = Fixture::new("close-missing");
+    fs::remove_file(hw_path(fx.path(), "sessions.json")).unwrap();
+    assert!(stamp_session_end(fx.path()).is_err());
+    assert!(!hw_path(fx.path(), "sessions.json").exists());
+}
+
+// ── Store-generic halves on MemoryStore ──────────────────────────
+
+fn memory_store() -> MemoryStore {
+    let store = MemoryStore::new();
+    for file in ["approvals.json", "direction.json", "chatroom.json", "mode.json"] {
+        store.insert(file, sample(file));
+    }
+    store
+}
+
+#[test]
+fn approval_decision_in_memory() {
+    let store = memory_store();
+    store.update("approvals.json", None, |d: &mut Value| {
+        d["pending"].as_array_mut().unwrap().push(pending_approval("apr_mem"));
+        Ok(())
+    }).unwrap();
+
+    apply_approval_decision(&store, "apr_mem", "approved", None).unwrap();
+    let approvals: ApprovalsFile = store.read("approvals.json").unwrap().data;
+    assert!(approvals.pending.is_empty());
+    assert_eq!(approvals.resolved.last().unwrap().status, ApprovalStatus::Approved);
+}
+
+#[test]
+fn mode_toggle_in_memory() {
+    let store = MemoryStore::new();
+    let mode = toggle_mode(&store, true, Some(storage::MISSING_REVISION)).unwrap();
+    assert!(mode.overdrive);
+    assert!(matches!(mode.toggled_at, Some(Timestamp::Iso(_))), "{:?}", mode.toggled_at);
+    assert_eq!(mode.toggled_by.as_deref(), Some("pat"));
+
+    let stored: Versioned<ModeState> = store.read("mode.json").unwrap();
+    assert_conflict(toggle_mode(&store, false, Some(storage::MISSING_REVISION)));
+    toggle_mode(&store, false, Some(&stored.revision)).unwrap();
+    assert!(!store.read::<ModeState>("mode.json").unwrap().data.overdrive);
+}
+
+#[test]
+fn chat_and_notes_in_memory() {
+    let store = memory_store();
+    post_chatroom_message(&store, "hello", None).unwrap();
+    mark_note_read(&store, "n_001", None).unwrap();
+    record_chat_message(&store, "pat", "hi").unwrap();
+    record_chat_message(&store, "claude", "hello").unwrap();
+
+    let chatroom: ChatroomState = store.read("chatroom.json").unwrap().data;
+    assert_eq!(chatroom.session.pending_pat_message.as_deref(), Some("hello"));
+    let history: ChatHistory = store.read("chat-out.json").unwrap().data;
+    assert_eq!(history.messages.iter().map(|m| m.role.as_str()).collect::<Vec<_>>(), ["pat", "claude"]);
+    assert_ne!(history.messages[0].id, history.messages[1].id);
+    // New writes are ISO strings, whatever older files hold
+    assert!(history.messages.iter().all(|m| matches!(m.timestamp, Timestamp::Iso(_))));
+    let pat = chatroom.messages.last().unwrap();
+    assert!(millis_from_iso(&pat.timestamp).is_some(), "{}", pat.timestamp);
+}
+
+// ── Chat history ─────────────────────────────────────────────────
+
+#[test]
+fn get_chat_history_reports_malformed_file() {
+    let fx = Fixture::new("chat-history");
+    assert!(get_chat_history(fx.path()).unwrap().messages.is_empty());
+
+    fx.project.write("chat-out.json", r#"{ "messages": [{ "id": "m1", "role": "pat" }] }"#);
+    let err = get_chat_history(fx.path()).unwrap_err();
+    assert!(err.contains("chat-out.json") && err.contains("messages[0]"), "{}", err);
+}
@@ -0,0 +1,48 @@
+// ── Test helpers ─────────────────────────────────────────────────
+//
+// One temp project directory for every module's tests, so each test gets
+// its own `.hello-world/` and nothing is left behind.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// `<tmp>/hw-<pid>-<n>-<name>/` holding an empty `.hello-world/`, removed
+/// on drop. `n` keeps two tests with the same name apart.
+pub struct TempProject {
+    root: PathBuf,
+}
+
+impl TempProject {
+    pub fn new(name: &str) -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let n = NEXT.fetch_add(1, Ordering::Relaxed);
+        let root = std::env::temp_dir().join(format!("hw-{}-{}-{}", std::process::id(), n, name));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(".hello-world")).unwrap();
+        TempProject { root }
+    }
+
+    /// The project path, as the commands take it.
+    pub fn path(&self) -> &str {
+        self.root.to_str().unwrap()
+    }
+
+    /// A file under `.hello-world/`.
+    pub fn file(&self, name: &str) -> PathBuf {
+        self.root.join(".hello-world").join(name)
+    }
+
+    /// Write a file under `.hello-world/`, creating directories on the way.
+    pub fn write(&self, name: &str, contents: &str) {
+        let path = self.file(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+}
+
+impl Drop for TempProject {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
@@ -1,5 +1,4 @@
 import { invoke } from '@tauri-apps/api/core';
-import { listen } from '@tauri-apps/api/event';
 import { Activity, useCallback, useEffect, useRef, useState } from 'react';
 import { AgentsView } from './components/AgentsView.js';
 import { ApprovalQueue } from './components/ApprovalQueue.js';
@@ -21,6 +20,7 @@ import { SkillsView } from './components/SkillsView.js';
 import { TaskBoard } from './components/TaskBoard.js';
 import { TerminalView } from './components/TerminalView.js';
 import { WatchersView } from './components/WatchersView.js';
+import { checkEventContract, listenBackend } from './events.js';
 import { useAppStore, type View } from './stores/app.js';
 
 const KEY_MAP: Record<string, View> = {
@@ -107,6 +107,10 @@ export function App() {
       .finally(() => setBootstrapping(false));
   }, []);
 
+  useEffect(() => {
+    checkEventContract().catch(console.error);
+  }, []);
+
   useEffect(() => {
     if (!projectPath) return;
     invoke('start_watching', { projectPath }).catch(console.error);
@@ -119,11 +123,10 @@ export function App() {
   const prevDelibStatus = useRef<string>('idle');
   useEffect(() => {
     if (!projectPath) return;
-    const unlisten = listen<string[]>('hw-files-changed', async (e) => {
+    const unlisten = listenBackend('hw-files-changed', async (e) => {
       if (!e.payload.includes('chatroom.json')) return;
       try {
-        const raw = await invoke<string>('get_chatroom', { projectPath });
-        const cr = JSON.parse(raw) as { session: { status: string } };
+        const cr = await invoke<{ session: { status: string } }>('get_chatroom', { projectPath });
         const status = cr.session.status;
         if (prevDelibStatus.current !== 'active' && status === 'active') {
           setView('agents');
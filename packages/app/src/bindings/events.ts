@@ -0,0 +1,41 @@
+// Generated from packages/app/src-tauri/src/events.rs. Do not edit.
+// Regenerate with HW_UPDATE_BINDINGS=1 cargo test events
+
+export const CONTRACT_VERSION = 1;
+
+export type FilesChanged = Array<string>;
+
+export type ToolSummary = { type?: string, summary: string, files?: Array<string>, events?: Array<ToolEvent>, };
+
+export type ToolEvent = { tool: string, summary: string, };
+
+export type ChatChunk = { text: string, done: boolean, };
+
+export type PtyData = string;
+
+export type PtyDied = null;
+
+export type PtyLine = string;
+
+export type StorageError = string;
+
+export type MigrationReport = { fromVersion: number, toVersion: number, applied: Array<AppliedMigration>, 
+/**
+ * Files written or removed, for hw-files-changed.
+ */
+files: Array<string>, backupDir?: string, };
+
+export type AppliedMigration = { version: number, description: string, changes: Array<string>, };
+
+export type EventPayloads = {
+  'hw-files-changed': FilesChanged;
+  'hw-tool-summary': ToolSummary;
+  'hw-chat-chunk': ChatChunk;
+  'pty-data': PtyData;
+  'pty-died': PtyDied;
+  'hw-pty-line': PtyLine;
+  'hw-storage-error': StorageError;
+  'hw-migrated': MigrationReport;
+};
+
+export type EventName = keyof EventPayloads;
@@ -11,10 +11,19 @@ interface ActivityEvent {
   timestamp: string;
 }
 
-interface ActivityData {
+interface ActivityPage {
   activities: ActivityEvent[];
+  nextCursor?: string | null;
+  total: number;
+  typeCounts: Record<string, number>;
 }
 
+const PAGE_SIZE = 100;
+/** get_activity_page's largest page; older events need a cursor. */
+const MAX_LOADED = 2000;
+/** Filter chips for the most frequent types; the log has dozens. */
+const MAX_TYPE_CHIPS = 8;
+
 const TYPE_CONFIG: Record<string, { label: string; badge: string; color: string; bg: string }> = {
   context_loaded: { label: 'START', badge: 'SS', color: 'text-gray-400', bg: 'bg-gray-500/15' },
   session_end: { label: 'END', badge: 'SE', color: 'text-gray-400', bg: 'bg-gray-500/15' },
@@ -149,14 +158,24 @@ export function ActivityStream() {
   const projectPath = useProjectPath();
   const themeId = useThemeStore((s) => s.themeId);
   const accent = getTheme(themeId).accent;
-  const { data, loading } = useTauriData<ActivityData>('get_activity', projectPath);
+  const [filter, setFilter] = useState<string | null>(null);
+  const [limit, setLimit] = useState(PAGE_SIZE);
+  const { data, loading } = useTauriData<ActivityPage>('get_activity_page', projectPath, {
+    limit,
+    types: filter ? [filter] : [],
+  });
   const containerRef = useRef<HTMLDivElement>(null);
   const prevCountRef = useRef(0);
 
-  // Activities from file are newest-first (ActivityStore.getAll() reverses)
-  // The file stores chronologically but we read it and reverse in the store
-  // However get_activity reads raw JSON — we need to reverse here
-  const activities = data?.activities ? [...data.activities].reverse() : [];
+  // get_activity_page returns newest first, across rotated segments too
+  const activities = data?.activities ?? [];
+  const typeCounts = data?.typeCounts ?? {};
+  const totalAll = Object.values(typeCounts).reduce((sum, n) => sum + n, 0);
+  const chips = Object.entries(typeCounts)
+    .sort((a, b) => b[1] - a[1])
+    .slice(0, MAX_TYPE_CHIPS)
+    .map(([type]) => type);
+  if (filter && !chips.includes(filter)) chips.push(filter);
 
   useEffect(() => {
     if (activities.length > prevCountRef.current && containerRef.current) {
@@ -165,7 +184,8 @@ export function ActivityStream() {
     prevCountRef.current = activities.length;
   }, [activities.length]);
 
-  if (loading) {
+  // Filter and paging changes refetch in place; only the first load shows the spinner
+  if (loading && !data) {
     return (
       <div className="flex-1 flex items-center justify-center bg-[#0a0a0f]">
         <p className="text-sm text-gray-500">Loading activity...</p>
@@ -173,7 +193,7 @@ export function ActivityStream() {
     );
   }
 
-  if (activities.length === 0) {
+  if (totalAll === 0) {
     return (
       <div className="flex-1 flex items-center justify-center bg-[#0a0a0f]">
         <p className="text-sm text-gray-500">
@@ -185,9 +205,37 @@ export function ActivityStream() {
 
   return (
     <div ref={containerRef} className="flex-1 overflow-y-auto bg-[#0a0a0f]">
+      <div className="flex flex-wrap gap-1 px-4 py-2 border-b border-gray-800/50">
+        {[null, ...chips].map((type) => (
+          <button
+            key={type ?? 'all'}
+            type="button"
+            onClick={() => {
+              setFilter(type);
+              setLimit(PAGE_SIZE);
+            }}
+            className={`text-[10px] font-mono px-2 py-1 rounded-full transition-colors cursor-pointer ${
+              filter === type
+                ? 'bg-gray-700 text-gray-100'
+                : 'text-gray-500 hover:text-gray-300 hover:bg-gray-800/50'
+            }`}
+          >
+            {type ? type.replace(/_/g, ' ') : 'all'} ({type ? (typeCounts[type] ?? 0) : totalAll})
+          </button>
+        ))}
+      </div>
       {activities.map((item) => (
         <ActivityRow key={item.id} item={item} accent={accent} />
       ))}
+      {data && data.total > activities.length && limit < MAX_LOADED && (
+        <button
+          type="button"
+          onClick={() => setLimit((l) => l + PAGE_SIZE)}
+          className="w-full py-2 text-xs text-gray-500 hover:text-gray-300"
+        >
+          Load older ({data.total - activities.length} remaining)
+        </button>
+      )}
     </div>
   );
 }
@@ -19,6 +19,7 @@ interface ApprovalItem {
 interface ApprovalsData {
   pending: ApprovalItem[];
   resolved: ApprovalItem[];
+  revision: string;
 }
 
 const TIER_STYLES: Record<string, { bg: string; text: string; border: string; label: string }> = {
@@ -182,6 +183,7 @@ export function ApprovalQueue({ standalone = false }: { standalone?: boolean })
         projectPath,
         requestId: confirmingId,
         decision: confirmingAction === 'approve' ? 'approved' : 'rejected',
+        expectedRevision: data?.revision,
       });
     } catch (err) {
       console.error('Failed to resolve approval:', err);
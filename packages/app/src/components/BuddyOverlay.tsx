@@ -2,6 +2,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { emit, listen } from '@tauri-apps/api/event';
 import { currentMonitor, getCurrentWindow, LogicalPosition } from '@tauri-apps/api/window';
 import { useEffect, useRef, useState } from 'react';
+import { listenBackend } from '../events.js';
 import { getTheme } from '../stores/theme.js';
 
 type ActivityState = 'waiting' | 'responding' | 'shocked' | 'happy';
@@ -296,7 +297,7 @@ export function BuddyOverlay() {
 
   // State machine
   useEffect(() => {
-    const filesU = listen<string[]>('hw-files-changed', async (e) => {
+    const filesU = listenBackend('hw-files-changed', async (e) => {
       const pp = projectPathRef.current;
       if (!pp) return;
       // Overdrive mode toggle
@@ -309,15 +310,16 @@ export function BuddyOverlay() {
       // Approvals check
       if (!e.payload.includes('approvals.json')) return;
       try {
-        const raw = await invoke<string>('get_approvals', { projectPath: pp });
-        const arr = JSON.parse(raw) as Array<{ status: string }>;
-        setHasError(arr.some((a) => a.status === 'pending'));
+        const approvals = await invoke<{ pending: Array<{ status: string }> }>('get_approvals', {
+          projectPath: pp,
+        });
+        setHasError(approvals.pending.length > 0);
       } catch {
         /* ignore */
       }
     });
 
-    const summaryU = listen<{ type: string }>('hw-tool-summary', (e) => {
+    const summaryU = listenBackend('hw-tool-summary', (e) => {
       const type = e.payload?.type;
       if (type === 'typing') {
         if (safetyTimer.current) clearTimeout(safetyTimer.current);
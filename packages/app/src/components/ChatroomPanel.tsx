@@ -1,6 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
-import { listen } from '@tauri-apps/api/event';
 import { useCallback, useEffect, useRef, useState } from 'react';
+import { listenBackend } from '../events.js';
 import { useProjectPath } from '../hooks/useProjectPath.js';
 
 interface ChatAgent {
@@ -159,7 +159,7 @@ export function ChatroomPanel({ fullHeight = false }: { fullHeight?: boolean })
   const fetchState = useCallback(async () => {
     if (!projectPath) return;
     try {
-      setState(JSON.parse(await invoke<string>('get_chatroom', { projectPath })) as ChatroomState);
+      setState(await invoke<ChatroomState>('get_chatroom', { projectPath }));
     } catch (err) {
       console.error('[ChatroomPanel] fetchState error:', err);
       setState(null);
@@ -172,7 +172,7 @@ export function ChatroomPanel({ fullHeight = false }: { fullHeight?: boolean })
 
   useEffect(() => {
     if (!projectPath) return;
-    const u = listen<string[]>('hw-files-changed', (e) => {
+    const u = listenBackend('hw-files-changed', (e) => {
       if (e.payload.includes('chatroom.json')) fetchState();
     });
     return () => {
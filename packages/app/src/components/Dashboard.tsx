@@ -15,6 +15,10 @@ interface StateData {
   tasks: Task[];
 }
 
+interface TaskGraphData {
+  ready: string[];
+}
+
 interface WorkflowData {
   phase: string;
   strikes: number;
@@ -87,6 +91,7 @@ function formatDuration(startedAt: string, endedAt?: string): string {
 export function Dashboard() {
   const projectPath = useProjectPath();
   const { data: stateData } = useTauriData<StateData>('get_state', projectPath);
+  const { data: graphData } = useTauriData<TaskGraphData>('get_task_graph', projectPath);
   const { data: workflowData } = useTauriData<WorkflowData>('get_workflow', projectPath);
   const { data: sessionsData } = useTauriData<SessionsData>('get_sessions', projectPath);
   const { data: directionData, refetch: refetchDirection } = useTauriData<DirectionData>(
@@ -103,6 +108,10 @@ export function Dashboard() {
 
   const activeTask = tasks.find((t) => t.status === 'in_progress');
   const todoTasks = tasks.filter((t) => t.status === 'todo');
+  // Ready tasks (dependencies done) in dependency order; plain todo list until the graph loads
+  const upNext = graphData
+    ? graphData.ready.flatMap((id) => tasks.filter((t) => t.id === id))
+    : todoTasks;
   const doneTasks = tasks.filter((t) => t.status === 'done');
 
   const phaseIdx = PHASE_ORDER.indexOf(phase);
@@ -255,18 +264,18 @@ export function Dashboard() {
             </div>
           </div>
 
-          {todoTasks.length > 0 && (
+          {upNext.length > 0 && (
             <div>
               <p className="text-[10px] uppercase tracking-widest text-gray-600 mb-1.5">Up Next</p>
               <div className="flex flex-col gap-1">
-                {todoTasks.slice(0, 3).map((t) => (
+                {upNext.slice(0, 3).map((t) => (
                   <p key={t.id} className="text-[11px] text-gray-400 truncate leading-snug">
                     <span className="text-gray-700 mr-1">·</span>
                     {t.title}
                   </p>
                 ))}
-                {todoTasks.length > 3 && (
-                  <p className="text-[10px] text-gray-600">+{todoTasks.length - 3} more</p>
+                {upNext.length > 3 && (
+                  <p className="text-[10px] text-gray-600">+{upNext.length - 3} more</p>
                 )}
               </div>
             </div>
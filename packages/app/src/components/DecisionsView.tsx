@@ -1,4 +1,5 @@
-import { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { useEffect, useState } from 'react';
 import { useProjectPath } from '../hooks/useProjectPath.js';
 import { useTauriData } from '../hooks/useTauriData.js';
 import { EmptyState, ErrorState, LoadingState } from './LoadingState.js';
@@ -13,6 +14,10 @@ interface Decision {
   rationale: string;
   decidedAt: string;
   decidedBy: 'pat' | 'claude' | 'both';
+  tags?: string[];
+  supersedes?: string;
+  supersededBy?: string;
+  history?: unknown[];
 }
 
 interface StateData {
@@ -42,7 +47,7 @@ function DecisionCard({ decision }: { decision: Decision }) {
     <button
       type="button"
       onClick={() => setExpanded((prev) => !prev)}
-      className="w-full text-left bg-[#1a1a24] border border-gray-800 rounded-lg p-4 hover:border-gray-700 transition-colors cursor-pointer"
+      className={`w-full text-left bg-[#1a1a24] border border-gray-800 rounded-lg p-4 hover:border-gray-700 transition-colors cursor-pointer ${decision.supersededBy ? 'opacity-50' : ''}`}
     >
       <div className="flex items-start justify-between gap-3">
         <span className="text-sm font-medium text-gray-100 leading-snug">🧭 {decision.title}</span>
@@ -58,6 +63,24 @@ function DecisionCard({ decision }: { decision: Decision }) {
         <span className={`text-[10px] font-medium px-2 py-0.5 rounded-full ${by.style}`}>
           {by.emoji} {by.label}
         </span>
+        {decision.supersededBy && (
+          <span className="text-[10px] px-2 py-0.5 rounded-full bg-gray-700/40 text-gray-400">
+            Superseded
+          </span>
+        )}
+        {decision.supersedes && (
+          <span className="text-[10px] px-2 py-0.5 rounded-full bg-amber-500/15 text-amber-300">
+            Replaces an earlier decision
+          </span>
+        )}
+        {(decision.history?.length ?? 0) > 0 && (
+          <span className="text-[10px] text-gray-500">amended {decision.history?.length}×</span>
+        )}
+        {decision.tags?.map((tag) => (
+          <span key={tag} className="text-[10px] text-gray-500">
+            #{tag}
+          </span>
+        ))}
       </div>
 
       {expanded && (
@@ -100,6 +123,22 @@ function DecisionCard({ decision }: { decision: Decision }) {
 export function DecisionsView() {
   const projectPath = useProjectPath();
   const { data, loading, error, refetch } = useTauriData<StateData>('get_state', projectPath);
+  const [query, setQuery] = useState('');
+  const [results, setResults] = useState<Decision[] | null>(null);
+
+  useEffect(() => {
+    const text = query.trim();
+    if (!projectPath || !text) {
+      setResults(null);
+      return;
+    }
+    const timer = setTimeout(() => {
+      invoke<Decision[]>('query_decisions', { projectPath, text, includeSuperseded: true })
+        .then(setResults)
+        .catch(() => setResults([]));
+    }, 200);
+    return () => clearTimeout(timer);
+  }, [query, projectPath, data]);
 
   if (loading) return <LoadingState />;
   if (error) return <ErrorState message={error} onRetry={refetch} />;
@@ -115,7 +154,17 @@ export function DecisionsView() {
         <EmptyState message="No decisions recorded yet. Use hw_record_decision when an architectural choice is made — these accumulate into your project's decision log." />
       ) : (
         <div className="space-y-3">
-          {[...decisions].reverse().map((d) => (
+          <input
+            type="search"
+            value={query}
+            onChange={(e) => setQuery(e.target.value)}
+            placeholder="What did we decide about..."
+            className="w-full bg-[#1a1a24] border border-gray-800 rounded-lg px-3 py-2 text-xs text-gray-200 placeholder-gray-600 focus:outline-none focus:border-gray-600"
+          />
+          {results?.length === 0 && (
+            <p className="text-xs text-gray-600 italic">No decisions match "{query.trim()}"</p>
+          )}
+          {(results ?? [...decisions].reverse()).map((d) => (
             <DecisionCard key={d.id} decision={d} />
           ))}
         </div>
@@ -1,4 +1,5 @@
-import { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { useEffect, useState } from 'react';
 import { useProjectPath } from '../hooks/useProjectPath.js';
 import { useTauriData } from '../hooks/useTauriData.js';
 import { EmptyState, ErrorState, LoadingState } from './LoadingState.js';
@@ -7,7 +8,7 @@ import { ViewShell } from './ViewShell.js';
 interface Memory {
   id: string;
   projectId: string;
-  type: 'pain' | 'win' | 'fact' | 'decision' | 'architecture';
+  type: 'pain' | 'win' | 'fact' | 'decision' | 'architecture' | 'reflection';
   title: string;
   content: string;
   rule: string;
@@ -31,10 +32,15 @@ interface BrainState {
   };
 }
 
-interface MemoriesData {
+interface MemoryPage {
   memories: Memory[];
+  total: number;
+  typeCounts: Record<string, number>;
+  revision: string;
 }
 
+const PAGE_SIZE = 50;
+
 type MemoryType = Memory['type'] | 'all';
 
 const TYPE_STYLE: Record<string, string> = {
@@ -43,6 +49,7 @@ const TYPE_STYLE: Record<string, string> = {
   fact: 'bg-blue-500/20 text-blue-300',
   decision: 'bg-orange-500/20 text-orange-300',
   architecture: 'bg-violet-500/20 text-violet-300',
+  reflection: 'bg-cyan-500/20 text-cyan-300',
 };
 
 const SEVERITY_STYLE: Record<string, string> = {
@@ -76,63 +83,98 @@ const PHASE_STYLE: Record<string, string> = {
   late: 'bg-red-500',
 };
 
-function MemoryCard({ memory }: { memory: Memory }) {
+function MemoryCard({ memory, revision }: { memory: Memory; revision?: string }) {
+  const projectPath = useProjectPath();
   const [expanded, setExpanded] = useState(false);
+  const [editing, setEditing] = useState(false);
+  const [draft, setDraft] = useState({ title: '', content: '', rule: '' });
+  const [error, setError] = useState<string | null>(null);
   const strengthPercent = Math.min(100, Math.max(0, (memory.synapticStrength / 2) * 100));
 
-  return (
-    <button
-      type="button"
-      onClick={() => setExpanded((prev) => !prev)}
-      className="w-full text-left bg-[#1a1a24] border border-gray-800 rounded-lg p-4 hover:border-gray-700 transition-colors cursor-pointer"
-    >
-      <div className="flex items-start justify-between gap-3">
-        <div className="flex items-center gap-2">
-          <span
-            className={`text-[10px] font-medium px-2 py-0.5 rounded-full ${TYPE_STYLE[memory.type]}`}
-          >
-            {memory.type}
-          </span>
-          <span
-            className={`text-[10px] font-medium px-2 py-0.5 rounded-full ${SEVERITY_STYLE[memory.severity]}`}
-          >
-            {memory.severity}
-          </span>
-        </div>
-        <div className="flex items-center gap-2 shrink-0">
-          <span className="text-[10px] text-gray-600" title="Access count">
-            {memory.accessCount}x
-          </span>
-          <div
-            className="w-12 h-1.5 bg-gray-800 rounded-full overflow-hidden"
-            title={`Synaptic strength: ${memory.synapticStrength.toFixed(2)}`}
-          >
-            <div
-              className="h-full bg-emerald-500/60 rounded-full"
-              style={{ width: `${strengthPercent}%` }}
-            />
-          </div>
-        </div>
-      </div>
+  function startEdit() {
+    setDraft({ title: memory.title, content: memory.content, rule: memory.rule });
+    setError(null);
+    setEditing(true);
+  }
+
+  async function save() {
+    try {
+      await invoke('update_memory', {
+        projectPath,
+        id: memory.id,
+        patch: draft,
+        expectedRevision: revision,
+      });
+      setEditing(false);
+    } catch (err) {
+      setError(String(err));
+    }
+  }
 
-      <span className="block text-sm font-medium text-gray-100 mt-2 leading-snug">
-        {memory.title}
-      </span>
+  async function remove() {
+    if (!window.confirm(`Delete memory "${memory.title}"?`)) return;
+    try {
+      await invoke('delete_memory', { projectPath, id: memory.id, expectedRevision: revision });
+    } catch (err) {
+      setError(String(err));
+    }
+  }
 
-      {memory.tags.length > 0 && (
-        <div className="flex flex-wrap gap-1 mt-2">
-          {memory.tags.map((tag) => (
+  return (
+    <div className="w-full text-left bg-[#1a1a24] border border-gray-800 rounded-lg p-4 hover:border-gray-700 transition-colors">
+      <button
+        type="button"
+        onClick={() => setExpanded((prev) => !prev)}
+        className="w-full text-left cursor-pointer"
+      >
+        <div className="flex items-start justify-between gap-3">
+          <div className="flex items-center gap-2">
             <span
-              key={tag}
-              className={`text-[10px] font-medium px-1.5 py-0.5 rounded-full ${tagColor(tag)}`}
+              className={`text-[10px] font-medium px-2 py-0.5 rounded-full ${TYPE_STYLE[memory.type]}`}
             >
-              {tag}
+              {memory.type}
             </span>
-          ))}
+            <span
+              className={`text-[10px] font-medium px-2 py-0.5 rounded-full ${SEVERITY_STYLE[memory.severity]}`}
+            >
+              {memory.severity}
+            </span>
+          </div>
+          <div className="flex items-center gap-2 shrink-0">
+            <span className="text-[10px] text-gray-600" title="Access count">
+              {memory.accessCount}x
+            </span>
+            <div
+              className="w-12 h-1.5 bg-gray-800 rounded-full overflow-hidden"
+              title={`Synaptic strength: ${memory.synapticStrength.toFixed(2)}`}
+            >
+              <div
+                className="h-full bg-emerald-500/60 rounded-full"
+                style={{ width: `${strengthPercent}%` }}
+              />
+            </div>
+          </div>
         </div>
-      )}
 
-      {expanded && (
+        <span className="block text-sm font-medium text-gray-100 mt-2 leading-snug">
+          {memory.title}
+        </span>
+
+        {memory.tags.length > 0 && (
+          <div className="flex flex-wrap gap-1 mt-2">
+            {memory.tags.map((tag) => (
+              <span
+                key={tag}
+                className={`text-[10px] font-medium px-1.5 py-0.5 rounded-full ${tagColor(tag)}`}
+              >
+                {tag}
+              </span>
+            ))}
+          </div>
+        )}
+      </button>
+
+      {expanded && !editing && (
         <div className="mt-3 pt-3 border-t border-gray-800 space-y-2">
           {memory.content && (
             <p className="text-xs text-gray-400 leading-relaxed">{memory.content}</p>
@@ -145,38 +187,115 @@ function MemoryCard({ memory }: { memory: Memory }) {
               <p className="text-xs text-yellow-300/80 mt-0.5">{memory.rule}</p>
             </div>
           )}
+          <div className="flex items-center gap-3 pt-1">
+            <button
+              type="button"
+              onClick={startEdit}
+              className="text-[11px] text-gray-500 hover:text-gray-200"
+            >
+              Edit
+            </button>
+            <button
+              type="button"
+              onClick={remove}
+              className="text-[11px] text-gray-600 hover:text-red-400"
+            >
+              Delete
+            </button>
+            {error && <span className="text-[10px] text-red-400 truncate">{error}</span>}
+          </div>
+        </div>
+      )}
+
+      {expanded && editing && (
+        <div className="mt-3 pt-3 border-t border-gray-800 space-y-2">
+          <input
+            value={draft.title}
+            onChange={(e) => setDraft({ ...draft, title: e.target.value })}
+            className="w-full bg-[#0a0a0f] border border-gray-700 rounded px-2 py-1 text-sm text-gray-200 focus:outline-none focus:border-gray-500"
+          />
+          <textarea
+            value={draft.content}
+            onChange={(e) => setDraft({ ...draft, content: e.target.value })}
+            rows={4}
+            className="w-full bg-[#0a0a0f] border border-gray-700 rounded p-2 text-xs text-gray-300 focus:outline-none focus:border-gray-500 resize-none"
+          />
+          <textarea
+            value={draft.rule}
+            onChange={(e) => setDraft({ ...draft, rule: e.target.value })}
+            rows={2}
+            placeholder="Rule"
+            className="w-full bg-[#0a0a0f] border border-gray-700 rounded p-2 text-xs text-yellow-300/80 placeholder-gray-600 focus:outline-none focus:border-gray-500 resize-none"
+          />
+          <div className="flex items-center gap-2">
+            <button
+              type="button"
+              onClick={save}
+              className="px-3 py-1 text-xs bg-blue-600/20 hover:bg-blue-600/30 text-blue-300 border border-blue-600/30 rounded"
+            >
+              Save
+            </button>
+            <button
+              type="button"
+              onClick={() => setEditing(false)}
+              className="px-3 py-1 text-xs text-gray-400 hover:text-gray-200"
+            >
+              Cancel
+            </button>
+            {error && <span className="text-[10px] text-red-400 truncate">{error}</span>}
+          </div>
         </div>
       )}
-    </button>
+    </div>
   );
 }
 
 export function MemoryView() {
   const projectPath = useProjectPath();
+  const [filter, setFilter] = useState<MemoryType>('all');
+  const [search, setSearch] = useState('');
+  const [text, setText] = useState('');
+  const [limit, setLimit] = useState(PAGE_SIZE);
+
+  // Debounce typing so each keystroke doesn't hit the backend
+  useEffect(() => {
+    const timer = setTimeout(() => {
+      setText(search.trim());
+      setLimit(PAGE_SIZE);
+    }, 200);
+    return () => clearTimeout(timer);
+  }, [search]);
+
   const {
-    data: memoriesData,
-    loading: memLoading,
+    data: page,
     error: memError,
     refetch: memRefetch,
-  } = useTauriData<MemoriesData>('get_memories', projectPath);
+  } = useTauriData<MemoryPage>('list_memories', projectPath, {
+    query: { text: text || undefined, types: filter === 'all' ? [] : [filter], limit },
+  });
   const {
     data: brainData,
     loading: brainLoading,
     error: brainError,
   } = useTauriData<BrainState>('get_brain_state', projectPath);
-  const [filter, setFilter] = useState<MemoryType>('all');
 
-  if (memLoading || brainLoading) return <LoadingState />;
+  // Filter and search changes refetch in place; only the first load shows the spinner
+  if (!page && !memError) return <LoadingState />;
+  if (brainLoading && !brainData) return <LoadingState />;
   if (memError) return <ErrorState message={memError} onRetry={memRefetch} />;
   if (brainError) return <ErrorState message={brainError} />;
 
-  const memories = memoriesData?.memories ?? [];
+  const filtered = page?.memories ?? [];
+  const counts = page?.typeCounts ?? {};
+  const totalAll = Object.values(counts).reduce((sum, n) => sum + n, 0);
   const brain = brainData?.state;
-  const filtered = filter === 'all' ? memories : memories.filter((m) => m.type === filter);
-  const types: MemoryType[] = ['all', 'pain', 'win', 'fact', 'decision', 'architecture'];
+  const types: MemoryType[] = ['all', 'pain', 'win', 'fact', 'decision', 'architecture', 'reflection'];
 
   return (
-    <ViewShell title="Memory" description={`${memories.length} memories stored in the brain`}>
+    <ViewShell
+      title="Memory"
+      description={`${totalAll} memories${text ? ` matching "${text}"` : ' stored in the brain'}`}
+    >
       {brain && (
         <div className="flex items-center gap-4 mb-6 px-4 py-3 bg-[#1a1a24] border border-gray-800 rounded-lg">
           <div className="flex items-center gap-2">
@@ -202,14 +321,25 @@ export function MemoryView() {
         </div>
       )}
 
-      <div className="flex gap-1 mb-4">
+      <input
+        type="search"
+        value={search}
+        onChange={(e) => setSearch(e.target.value)}
+        placeholder="Search title, content, rule, tags..."
+        className="w-full mb-3 bg-[#1a1a24] border border-gray-800 rounded-lg px-3 py-2 text-xs text-gray-200 placeholder-gray-600 focus:outline-none focus:border-gray-600"
+      />
+
+      <div className="flex flex-wrap gap-1 mb-4">
         {types.map((t) => {
-          const count = t === 'all' ? memories.length : memories.filter((m) => m.type === t).length;
+          const count = t === 'all' ? totalAll : (counts[t] ?? 0);
           return (
             <button
               key={t}
               type="button"
-              onClick={() => setFilter(t)}
+              onClick={() => {
+                setFilter(t);
+                setLimit(PAGE_SIZE);
+              }}
               className={`text-[11px] font-medium px-3 py-1.5 rounded-full transition-colors cursor-pointer ${
                 filter === t
                   ? 'bg-gray-700 text-gray-100'
@@ -225,16 +355,27 @@ export function MemoryView() {
       {filtered.length === 0 ? (
         <EmptyState
           message={
-            filter === 'all'
-              ? 'No memories stored yet. Claude auto-captures pain, win, and decision memories as it works. You can also call hw_store_memory directly.'
-              : `No ${filter} memories`
+            text
+              ? `No memories match "${text}"`
+              : filter === 'all'
+                ? 'No memories stored yet. Claude auto-captures pain, win, and decision memories as it works. You can also call hw_store_memory directly.'
+                : `No ${filter} memories`
           }
         />
       ) : (
         <div className="space-y-3">
           {filtered.map((m) => (
-            <MemoryCard key={m.id} memory={m} />
+            <MemoryCard key={m.id} memory={m} revision={page?.revision} />
           ))}
+          {page && page.total > filtered.length && (
+            <button
+              type="button"
+              onClick={() => setLimit((l) => l + PAGE_SIZE)}
+              className="w-full py-2 text-xs text-gray-500 hover:text-gray-300"
+            >
+              Show more ({page.total - filtered.length} remaining)
+            </button>
+          )}
         </div>
       )}
     </ViewShell>
@@ -18,6 +18,13 @@ interface DirectionNote {
   actionId?: string;
 }
 
+interface ProjectContextData {
+  text: string;
+  estimatedTokens: number;
+  tokenBudget: number;
+  sections: Array<{ title: string; included: number; total: number }>;
+}
+
 interface DirectionData {
   vision?: string;
   scope?: ScopeEntry[];
@@ -27,6 +34,7 @@ interface DirectionData {
 export function ProjectContextView() {
   const projectPath = useProjectPath();
   const { data, loading } = useTauriData<DirectionData>('get_direction', projectPath);
+  const { data: briefing } = useTauriData<ProjectContextData>('get_project_context', projectPath);
 
   if (loading) {
     return (
@@ -136,6 +144,35 @@ export function ProjectContextView() {
           </section>
         )}
 
+        {/* What a new session is told */}
+        {briefing && (
+          <section className="px-5 py-4 border-b border-gray-800/40">
+            <p className="text-[10px] uppercase tracking-widest text-gray-600 mb-2">
+              Session Briefing · ~{briefing.estimatedTokens} / {briefing.tokenBudget} tokens
+            </p>
+            <div className="flex flex-wrap gap-1.5 mb-2">
+              {briefing.sections.map((s) => (
+                <span
+                  key={s.title}
+                  className={`text-[10px] font-mono px-1.5 py-0.5 rounded ${
+                    s.included < s.total ? 'bg-amber-500/10 text-amber-400' : 'bg-gray-800 text-gray-500'
+                  }`}
+                >
+                  {s.title} {s.included}/{s.total}
+                </span>
+              ))}
+            </div>
+            <details>
+              <summary className="text-xs text-gray-500 cursor-pointer hover:text-gray-300">
+                Show full text
+              </summary>
+              <pre className="mt-2 text-[11px] text-gray-400 whitespace-pre-wrap leading-relaxed font-mono">
+                {briefing.text}
+              </pre>
+            </details>
+          </section>
+        )}
+
         {/* Processed notes log */}
         {processed.length > 0 && (
           <section className="px-5 py-4">
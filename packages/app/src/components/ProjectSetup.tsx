@@ -10,6 +10,7 @@ export function ProjectSetup({ onProjectSet }: Props) {
   const [path, setPath] = useState('');
   const [error, setError] = useState('');
   const [saving, setSaving] = useState(false);
+  const [needsInit, setNeedsInit] = useState(false);
 
   async function browse() {
     const selected = await open({
@@ -20,6 +21,7 @@ export function ProjectSetup({ onProjectSet }: Props) {
     if (selected && typeof selected === 'string') {
       setPath(selected);
       setError('');
+      setNeedsInit(false);
     }
   }
 
@@ -31,10 +33,29 @@ export function ProjectSetup({ onProjectSet }: Props) {
     try {
       await invoke('set_app_project_path', { projectPath: trimmed });
       onProjectSet(trimmed);
+    } catch (err) {
+      const message = String(err);
+      setError(message);
+      setNeedsInit(message.includes('.hello-world/ not found'));
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  async function handleInit() {
+    const trimmed = path.trim();
+    if (!trimmed) return;
+    setSaving(true);
+    setError('');
+    try {
+      await invoke('init_project', { projectPath: trimmed });
+      await invoke('set_app_project_path', { projectPath: trimmed });
+      onProjectSet(trimmed);
     } catch (err) {
       setError(String(err));
     } finally {
       setSaving(false);
+      setNeedsInit(false);
     }
   }
 
@@ -63,6 +84,7 @@ export function ProjectSetup({ onProjectSet }: Props) {
                 onChange={(e) => {
                   setPath(e.target.value);
                   setError('');
+                  setNeedsInit(false);
                 }}
                 onKeyDown={(e) => {
                   if (e.key === 'Enter') handleSave();
@@ -95,6 +117,17 @@ export function ProjectSetup({ onProjectSet }: Props) {
           >
             {saving ? 'Checking...' : 'Open Project'}
           </button>
+
+          {needsInit && (
+            <button
+              type="button"
+              onClick={handleInit}
+              disabled={saving}
+              className="w-full py-2.5 rounded-lg bg-[#1a1a24] border border-gray-700 hover:border-gray-500 disabled:opacity-40 text-sm text-gray-200 transition-colors cursor-pointer"
+            >
+              Initialize a new project here
+            </button>
+          )}
         </div>
       </div>
     </div>
@@ -0,0 +1,134 @@
+import { invoke } from '@tauri-apps/api/core';
+import { open } from '@tauri-apps/plugin-dialog';
+import { ChevronDown, Pin, Plus, X } from 'lucide-react';
+import { useEffect, useRef, useState } from 'react';
+import { useAppStore } from '../stores/app.js';
+
+interface ProjectEntry {
+  path: string;
+  name: string;
+  pinned: boolean;
+  lastOpenedAt?: string;
+}
+
+export function ProjectSwitcher() {
+  const { projectPath, projectName, setProject } = useAppStore();
+  const [isOpen, setIsOpen] = useState(false);
+  const [projects, setProjects] = useState<ProjectEntry[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const ref = useRef<HTMLDivElement>(null);
+
+  function refresh() {
+    invoke<ProjectEntry[]>('list_projects')
+      .then(setProjects)
+      .catch(() => {});
+  }
+
+  useEffect(() => {
+    if (!isOpen) return;
+    refresh();
+    setError(null);
+    const onClick = (e: MouseEvent) => {
+      if (ref.current && !ref.current.contains(e.target as Node)) setIsOpen(false);
+    };
+    window.addEventListener('mousedown', onClick);
+    return () => window.removeEventListener('mousedown', onClick);
+  }, [isOpen]);
+
+  async function switchTo(path: string) {
+    try {
+      const entry = await invoke<ProjectEntry>('switch_project', { projectPath: path });
+      setProject(entry.path, entry.name);
+      setIsOpen(false);
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function addAndSwitch() {
+    const selected = await open({
+      directory: true,
+      multiple: false,
+      title: 'Select Hello World project folder',
+    });
+    if (selected && typeof selected === 'string') await switchTo(selected);
+  }
+
+  async function togglePin(p: ProjectEntry) {
+    await invoke('set_project_pinned', { projectPath: p.path, pinned: !p.pinned }).catch(() => {});
+    refresh();
+  }
+
+  async function remove(p: ProjectEntry) {
+    await invoke('remove_project', { projectPath: p.path }).catch(() => {});
+    refresh();
+  }
+
+  return (
+    <div ref={ref} className="relative min-w-0 flex-1">
+      <button
+        type="button"
+        onClick={() => setIsOpen((prev) => !prev)}
+        className="flex items-center gap-1 max-w-full text-[11px] font-semibold text-white/70 hover:text-white tracking-wide"
+      >
+        <span className="truncate">{projectName ?? 'Hello World'}</span>
+        <ChevronDown size={11} className="shrink-0 opacity-60" />
+      </button>
+
+      {isOpen && (
+        <div className="absolute left-0 top-7 z-50 w-64 bg-[#13131c] border border-gray-800 rounded-lg shadow-xl py-1">
+          {projects.map((p) => {
+            const isActive = p.path === projectPath;
+            return (
+              <div
+                key={p.path}
+                className={`group flex items-center gap-2 px-3 py-1.5 ${isActive ? 'bg-white/[0.06]' : 'hover:bg-white/[0.03]'}`}
+              >
+                <button
+                  type="button"
+                  onClick={() => (isActive ? setIsOpen(false) : switchTo(p.path))}
+                  className="flex-1 min-w-0 text-left"
+                  title={p.path}
+                >
+                  <div className={`text-xs truncate ${isActive ? 'text-white' : 'text-gray-300'}`}>
+                    {p.name}
+                  </div>
+                  <div className="text-[10px] text-gray-600 truncate">{p.path}</div>
+                </button>
+                <button
+                  type="button"
+                  onClick={() => togglePin(p)}
+                  title={p.pinned ? 'Unpin' : 'Pin'}
+                  className={`shrink-0 ${p.pinned ? 'text-amber-400' : 'text-gray-600 opacity-0 group-hover:opacity-100 hover:text-gray-300'}`}
+                >
+                  <Pin size={11} />
+                </button>
+                {!isActive && (
+                  <button
+                    type="button"
+                    onClick={() => remove(p)}
+                    title="Remove from list"
+                    className="shrink-0 text-gray-600 opacity-0 group-hover:opacity-100 hover:text-red-400"
+                  >
+                    <X size={11} />
+                  </button>
+                )}
+              </div>
+            );
+          })}
+
+          <button
+            type="button"
+            onClick={addAndSwitch}
+            className="w-full flex items-center gap-2 px-3 py-1.5 mt-1 border-t border-gray-800 text-xs text-gray-400 hover:text-white"
+          >
+            <Plus size={12} />
+            Open project...
+          </button>
+
+          {error && <div className="px-3 py-1.5 text-[10px] text-red-400">{error}</div>}
+        </div>
+      )}
+    </div>
+  );
+}
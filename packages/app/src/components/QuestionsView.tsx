@@ -9,22 +9,27 @@ interface Question {
   id: string;
   question: string;
   context: string;
-  status: 'open' | 'answered' | 'deferred';
+  status: 'open' | 'answered' | 'deferred' | 'dismissed';
   answer?: string;
   createdAt: string;
   answeredAt?: string;
+  answeredBy?: string;
+  answerHistory?: Array<{ answer: string; answeredAt?: string; answeredBy?: string }>;
+  dismissedReason?: string;
   linkedTaskId?: string;
   linkedDecisionId?: string;
 }
 
 interface StateData {
   questions: Question[];
+  revisions: { questions: string };
 }
 
 const STATUS_STYLE: Record<string, string> = {
   open: 'bg-yellow-500/20 text-yellow-300',
   answered: 'bg-green-500/20 text-green-300',
   deferred: 'bg-gray-500/20 text-gray-400',
+  dismissed: 'bg-gray-700/30 text-gray-500',
 };
 
 function formatDate(iso: string): string {
@@ -34,10 +39,11 @@ function formatDate(iso: string): string {
 
 interface AnswerPanelProps {
   q: Question;
+  revision?: string;
   onClose: () => void;
 }
 
-function AnswerPanel({ q, onClose }: AnswerPanelProps) {
+function AnswerPanel({ q, revision, onClose }: AnswerPanelProps) {
   const projectPath = useProjectPath();
   const [answer, setAnswer] = useState('');
   const [submitting, setSubmitting] = useState(false);
@@ -49,7 +55,12 @@ function AnswerPanel({ q, onClose }: AnswerPanelProps) {
     setSubmitting(true);
     setError(null);
     try {
-      await invoke('answer_question', { projectPath, id: q.id, answer: answer.trim() });
+      await invoke('answer_question', {
+        projectPath,
+        id: q.id,
+        answer: answer.trim(),
+        expectedRevision: revision,
+      });
       setSuccess(true);
       setTimeout(() => onClose(), 1200);
     } catch (err) {
@@ -109,11 +120,24 @@ function AnswerPanel({ q, onClose }: AnswerPanelProps) {
   );
 }
 
-function QuestionCard({ q }: { q: Question }) {
+function QuestionCard({ q, revision }: { q: Question; revision?: string }) {
+  const projectPath = useProjectPath();
   const [expanded, setExpanded] = useState(false);
   const [answerOpen, setAnswerOpen] = useState(false);
-  const hasContent = q.context || q.answer;
-  const isOpen = q.status === 'open';
+  const [actionError, setActionError] = useState<string | null>(null);
+  const history = q.answerHistory ?? [];
+  const hasContent = q.context || q.answer || q.dismissedReason || history.length > 0;
+  const canAnswer = q.status !== 'dismissed';
+
+  async function act(command: 'defer_question' | 'dismiss_question' | 'reopen_question') {
+    if (!projectPath) return;
+    setActionError(null);
+    try {
+      await invoke(command, { projectPath, id: q.id, expectedRevision: revision });
+    } catch (err) {
+      setActionError(String(err));
+    }
+  }
 
   return (
     <div className="space-y-0">
@@ -167,10 +191,37 @@ function QuestionCard({ q }: { q: Question }) {
                 {q.answeredAt && (
                   <p className="text-[10px] text-gray-600 mt-1">
                     Answered {formatDate(q.answeredAt)}
+                    {q.answeredBy && ` by ${q.answeredBy}`}
                   </p>
                 )}
               </div>
             )}
+            {q.dismissedReason && (
+              <div>
+                <span className="text-[10px] uppercase tracking-wider text-gray-500 font-semibold">
+                  Dismissed
+                </span>
+                <p className="text-xs text-gray-500 mt-1 leading-relaxed">{q.dismissedReason}</p>
+              </div>
+            )}
+            {history.length > 0 && (
+              <div>
+                <span className="text-[10px] uppercase tracking-wider text-gray-500 font-semibold">
+                  Earlier answers
+                </span>
+                {[...history].reverse().map((h, i) => (
+                  <p key={i} className="text-xs text-gray-500 mt-1 leading-relaxed line-through">
+                    {h.answer}
+                    {h.answeredAt && (
+                      <span className="no-underline text-[10px] text-gray-600 ml-2">
+                        {formatDate(h.answeredAt)}
+                        {h.answeredBy && ` · ${h.answeredBy}`}
+                      </span>
+                    )}
+                  </p>
+                ))}
+              </div>
+            )}
             {(q.linkedTaskId || q.linkedDecisionId) && (
               <div className="flex items-center gap-2 pt-1">
                 <span className="text-[10px] text-gray-600">routed to</span>
@@ -189,20 +240,50 @@ function QuestionCard({ q }: { q: Question }) {
           </div>
         )}
 
-        {isOpen && (
-          <div className="mt-3 pt-3 border-t border-gray-800">
+        <div className="mt-3 pt-3 border-t border-gray-800 flex items-center gap-2">
+          {canAnswer && (
             <button
               type="button"
               onClick={() => setAnswerOpen((prev) => !prev)}
               className="text-[11px] px-2.5 py-1 bg-yellow-500/10 hover:bg-yellow-500/20 text-yellow-400 border border-yellow-500/20 rounded transition-colors"
             >
-              {answerOpen ? 'Cancel' : 'Answer'}
+              {answerOpen ? 'Cancel' : q.status === 'answered' ? 'Re-answer' : 'Answer'}
             </button>
-          </div>
-        )}
+          )}
+          {q.status === 'open' && (
+            <button
+              type="button"
+              onClick={() => act('defer_question')}
+              className="text-[11px] px-2.5 py-1 text-gray-400 hover:text-gray-200 transition-colors"
+            >
+              Defer
+            </button>
+          )}
+          {(q.status === 'open' || q.status === 'deferred') && (
+            <button
+              type="button"
+              onClick={() => act('dismiss_question')}
+              className="text-[11px] px-2.5 py-1 text-gray-500 hover:text-gray-300 transition-colors"
+            >
+              Dismiss
+            </button>
+          )}
+          {q.status !== 'open' && (
+            <button
+              type="button"
+              onClick={() => act('reopen_question')}
+              className="text-[11px] px-2.5 py-1 text-gray-500 hover:text-gray-300 transition-colors"
+            >
+              Reopen
+            </button>
+          )}
+          {actionError && <span className="text-[10px] text-red-400 truncate">{actionError}</span>}
+        </div>
       </div>
 
-      {isOpen && answerOpen && <AnswerPanel q={q} onClose={() => setAnswerOpen(false)} />}
+      {canAnswer && answerOpen && (
+        <AnswerPanel q={q} revision={revision} onClose={() => setAnswerOpen(false)} />
+      )}
     </div>
   );
 }
@@ -218,6 +299,8 @@ export function QuestionsView() {
   const open = questions.filter((q) => q.status === 'open');
   const answered = questions.filter((q) => q.status === 'answered');
   const deferred = questions.filter((q) => q.status === 'deferred');
+  const dismissed = questions.filter((q) => q.status === 'dismissed');
+  const revision = data?.revisions.questions;
 
   return (
     <ViewShell
@@ -235,7 +318,7 @@ export function QuestionsView() {
               </h3>
               <div className="space-y-2">
                 {[...open].reverse().map((q) => (
-                  <QuestionCard key={q.id} q={q} />
+                  <QuestionCard key={q.id} q={q} revision={revision} />
                 ))}
               </div>
             </section>
@@ -247,7 +330,7 @@ export function QuestionsView() {
               </h3>
               <div className="space-y-2">
                 {[...answered].reverse().map((q) => (
-                  <QuestionCard key={q.id} q={q} />
+                  <QuestionCard key={q.id} q={q} revision={revision} />
                 ))}
               </div>
             </section>
@@ -259,7 +342,19 @@ export function QuestionsView() {
               </h3>
               <div className="space-y-2">
                 {[...deferred].reverse().map((q) => (
-                  <QuestionCard key={q.id} q={q} />
+                  <QuestionCard key={q.id} q={q} revision={revision} />
+                ))}
+              </div>
+            </section>
+          )}
+          {dismissed.length > 0 && (
+            <section>
+              <h3 className="text-[10px] uppercase tracking-wider text-gray-600 font-semibold mb-2">
+                Dismissed
+              </h3>
+              <div className="space-y-2">
+                {[...dismissed].reverse().map((q) => (
+                  <QuestionCard key={q.id} q={q} revision={revision} />
                 ))}
               </div>
             </section>
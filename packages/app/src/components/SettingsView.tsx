@@ -15,10 +15,18 @@ interface ProjectConfig {
   dailyBudgetUsd: number;
   createdAt: string;
   updatedAt: string;
+  storage?: 'json' | 'sqlite';
 }
 
 interface ConfigData {
   config: ProjectConfig;
+  revision: string;
+}
+
+interface SqliteReport {
+  files: string[];
+  records: number;
+  skipped: string[];
 }
 
 const MODELS = [
@@ -46,6 +54,8 @@ export function SettingsView() {
   const [form, setForm] = useState<ProjectConfig | null>(null);
   const [saving, setSaving] = useState(false);
   const [saved, setSaved] = useState(false);
+  const [storageBusy, setStorageBusy] = useState(false);
+  const [storageResult, setStorageResult] = useState<string | null>(null);
   const { themeId, setTheme } = useThemeStore();
 
   useEffect(() => {
@@ -62,7 +72,8 @@ export function SettingsView() {
       form.description !== data.config.description ||
       form.defaultModel !== data.config.defaultModel ||
       form.dailyBudgetUsd !== data.config.dailyBudgetUsd ||
-      form.gitIntegration !== data.config.gitIntegration);
+      form.gitIntegration !== data.config.gitIntegration ||
+      (form.storage ?? 'json') !== (data.config.storage ?? 'json'));
 
   function update<K extends keyof ProjectConfig>(key: K, value: ProjectConfig[K]) {
     setForm((prev) => (prev ? { ...prev, [key]: value } : prev));
@@ -74,7 +85,11 @@ export function SettingsView() {
     setSaving(true);
     try {
       const updated = { ...form, updatedAt: new Date().toISOString() };
-      await invoke('save_config', { projectPath, config: { config: updated } });
+      await invoke('save_config', {
+        projectPath,
+        config: { config: updated },
+        expectedRevision: data?.revision,
+      });
       setSaved(true);
       setTimeout(() => setSaved(false), 2000);
       refetch();
@@ -85,6 +100,23 @@ export function SettingsView() {
     }
   }
 
+  async function runStorage(command: 'import_sqlite' | 'export_sqlite') {
+    setStorageBusy(true);
+    setStorageResult(null);
+    try {
+      const report = await invoke<SqliteReport>(command, { projectPath });
+      const verb = command === 'import_sqlite' ? 'Imported' : 'Exported';
+      const skipped = report.skipped.length ? ` Skipped ${report.skipped.join('; ')}.` : '';
+      setStorageResult(
+        `${verb} ${report.records} records from ${report.files.length} files.${skipped}`,
+      );
+    } catch (err) {
+      setStorageResult(`Failed: ${err}`);
+    } finally {
+      setStorageBusy(false);
+    }
+  }
+
   const inputClass =
     'w-full bg-[#1a1a24] border border-gray-700 rounded-lg px-3 py-2 text-sm text-gray-100 outline-none focus:border-blue-500/50 transition-colors';
   const labelClass =
@@ -245,6 +277,46 @@ export function SettingsView() {
           </label>
         </section>
 
+        <section>
+          <h2 className="text-sm font-semibold text-gray-200 mb-4">Storage</h2>
+          <div className="space-y-3">
+            <div>
+              <label className={labelClass}>Backend</label>
+              <select
+                value={form.storage ?? 'json'}
+                onChange={(e) => update('storage', e.target.value as 'json' | 'sqlite')}
+                className={inputClass}
+              >
+                <option value="json">JSON files</option>
+                <option value="sqlite">SQLite (hello-world.db)</option>
+              </select>
+            </div>
+            <div className="flex gap-2">
+              <button
+                type="button"
+                onClick={() => runStorage('import_sqlite')}
+                disabled={storageBusy}
+                className="text-xs px-3 py-1.5 rounded-lg bg-gray-800 text-gray-300 hover:bg-gray-700 transition-colors cursor-pointer disabled:opacity-50"
+              >
+                Import JSON into SQLite
+              </button>
+              <button
+                type="button"
+                onClick={() => runStorage('export_sqlite')}
+                disabled={storageBusy}
+                className="text-xs px-3 py-1.5 rounded-lg bg-gray-800 text-gray-300 hover:bg-gray-700 transition-colors cursor-pointer disabled:opacity-50"
+              >
+                Export SQLite to JSON
+              </button>
+            </div>
+            {storageResult && <p className="text-[10px] text-gray-400">{storageResult}</p>}
+            <p className="text-[10px] text-gray-600">
+              JSON files stay current either way, so the MCP server and git keep working. Switching
+              to SQLite imports the project on save.
+            </p>
+          </div>
+        </section>
+
         <section>
           <h2 className="text-sm font-semibold text-gray-200 mb-4">Metadata</h2>
           <div className="space-y-2 text-xs text-gray-400">
@@ -4,6 +4,7 @@ import { useCallback } from 'react';
 import { useProjectPath } from '../hooks/useProjectPath.js';
 import { useTauriData } from '../hooks/useTauriData.js';
 import { useAppStore, type View } from '../stores/app.js';
+import { ProjectSwitcher } from './ProjectSwitcher.js';
 
 // Anthropic A-mark — matches Lucide stroke style at small sizes
 function AnthropicIcon({ size = 16 }: { size?: number }) {
@@ -127,7 +128,7 @@ interface ModeData {
 }
 
 export function Sidebar({ onShowHelp }: SidebarProps) {
-  const { activeView, setView, projectName, sidebarCollapsed, toggleSidebar } = useAppStore();
+  const { activeView, setView, sidebarCollapsed, toggleSidebar } = useAppStore();
   const projectPath = useProjectPath();
   const { data: workflowData } = useTauriData<WorkflowData>('get_workflow', projectPath);
   const { data: modeData } = useTauriData<ModeData>('get_mode', projectPath);
@@ -150,11 +151,7 @@ export function Sidebar({ onShowHelp }: SidebarProps) {
     >
       {/* Project name */}
       <div className="h-9 flex items-center px-3 border-b border-gray-800/70 drag-region shrink-0">
-        {!sidebarCollapsed && (
-          <span className="text-[11px] font-semibold text-white/70 truncate tracking-wide">
-            {projectName ?? 'Hello World'}
-          </span>
-        )}
+        {!sidebarCollapsed && <ProjectSwitcher />}
       </div>
 
       {/* Nav sections */}
@@ -20,6 +20,15 @@ interface StateData {
 interface WorkflowData {
   phase: string;
 }
+interface GraphNode {
+  id: string;
+  blockedBy: string[];
+}
+interface TaskGraphData {
+  nodes: GraphNode[];
+  cycles: string[][];
+  dangling: { taskId: string; missingId: string }[];
+}
 
 const PHASE_COLORS: Record<string, { bg: string; text: string }> = {
   idle: { bg: 'bg-gray-800', text: 'text-gray-400' },
@@ -60,11 +69,13 @@ function relativeAge(iso: string): string {
 function TodoRow({
   task,
   index,
+  waitingOn,
   expanded,
   onToggle,
 }: {
   task: Task;
   index: number;
+  waitingOn: string[];
   expanded: boolean;
   onToggle: () => void;
 }) {
@@ -99,6 +110,11 @@ function TodoRow({
               ))}
             </div>
           )}
+          {waitingOn.length > 0 && (
+            <p className="text-[10px] text-amber-500/70 mt-1 truncate">
+              waiting on {waitingOn.join(', ')}
+            </p>
+          )}
           {expanded && task.description && (
             <p className="text-xs text-gray-500 mt-2 leading-relaxed whitespace-pre-wrap">
               {task.description}
@@ -117,6 +133,7 @@ export function TaskBoard() {
   const projectPath = useProjectPath();
   const { data, loading, error, refetch } = useTauriData<StateData>('get_state', projectPath);
   const { data: wf } = useTauriData<WorkflowData>('get_workflow', projectPath);
+  const { data: graph } = useTauriData<TaskGraphData>('get_task_graph', projectPath);
   const [expandedTodo, setExpandedTodo] = useState<string | null>(null);
   const [doneOpen, setDoneOpen] = useState(false);
   const [expandedDone, setExpandedDone] = useState<string | null>(null);
@@ -131,6 +148,10 @@ export function TaskBoard() {
   const active = tasks.filter((t) => t.status === 'in_progress');
   const todo = tasks.filter((t) => t.status === 'todo');
   const done = tasks.filter((t) => t.status === 'done');
+  const titleOf = (id: string) => tasks.find((t) => t.id === id)?.title ?? `${id} (deleted)`;
+  const waitingOn = (id: string) =>
+    (graph?.nodes.find((n) => n.id === id)?.blockedBy ?? []).map(titleOf);
+  const graphProblems = (graph?.cycles.length ?? 0) + (graph?.dangling.length ?? 0);
 
   if (tasks.length === 0) {
     return (
@@ -165,6 +186,19 @@ export function TaskBoard() {
       </div>
 
       <div className="flex-1 overflow-y-auto min-h-0">
+        {graph && graphProblems > 0 && (
+          <div className="mx-4 mt-4 rounded-lg border border-amber-800/50 bg-amber-950/20 px-4 py-2.5 text-xs text-amber-300/80 space-y-1">
+            {graph.cycles.map((cycle) => (
+              <p key={cycle.join()}>Dependency cycle: {cycle.map(titleOf).join(' → ')}</p>
+            ))}
+            {graph.dangling.map((d) => (
+              <p key={`${d.taskId}-${d.missingId}`}>
+                "{titleOf(d.taskId)}" depends on missing task {d.missingId}
+              </p>
+            ))}
+          </div>
+        )}
+
         {/* Zone 1: Blocked banner */}
         {blocked.length > 0 && (
           <div className="mx-4 mt-4 rounded-lg border border-red-800/50 bg-red-950/20 px-4 py-3">
@@ -250,6 +284,7 @@ export function TaskBoard() {
                   key={task.id}
                   task={task}
                   index={i + 1}
+                  waitingOn={waitingOn(task.id)}
                   expanded={expandedTodo === task.id}
                   onToggle={() => setExpandedTodo((v) => (v === task.id ? null : task.id))}
                 />
@@ -1,12 +1,13 @@
 import { invoke } from '@tauri-apps/api/core';
-import { listen } from '@tauri-apps/api/event';
 import { FitAddon } from '@xterm/addon-fit';
 import { WebLinksAddon } from '@xterm/addon-web-links';
 import { Terminal } from '@xterm/xterm';
 import { useCallback, useEffect, useMemo, useRef, useState } from 'react';
 import '@xterm/xterm/css/xterm.css';
+import { listenBackend } from '../events.js';
 import { useProjectPath } from '../hooks/useProjectPath.js';
 import { useTauriData } from '../hooks/useTauriData.js';
+import { useAppStore } from '../stores/app.js';
 
 interface Task {
   id: string;
@@ -55,13 +56,6 @@ const PHASE_DOT: Record<string, string> = {
 
 // -- Dual-panel bottom flowchart types --
 
-interface ToolSummaryPayload {
-  type: string;
-  files?: string[];
-  events?: Array<{ tool: string; summary: string }>;
-  summary?: string;
-}
-
 // Map filenames to system node IDs
 const FILE_TO_SYS_NODE: Record<string, string> = {
   'tasks.json': 'sys-tasks',
@@ -206,13 +200,13 @@ function BrainPipeline({
 
   // Listen for hw-tool-summary -- activate brain nodes on retrieval (MCP or auto-cue hook)
   useEffect(() => {
-    const unlistenPromise = listen<ToolSummaryPayload>('hw-tool-summary', (event) => {
+    const unlistenPromise = listenBackend('hw-tool-summary', (event) => {
       const payload = event.payload;
       const events = payload.events ?? [];
       const hasRetrieval = events.some((e) =>
         e.tool === 'hw_retrieve_memories' || e.tool === 'auto_cue'
       );
-      const isBrainRetrieval = hasRetrieval || (payload as Record<string, unknown>).type === 'brain_retrieval';
+      const isBrainRetrieval = hasRetrieval || payload.type === 'brain_retrieval';
       if (isBrainRetrieval) {
         const summaryText = (payload.summary ?? '') + events.map((e) => e.summary).join(' ');
         const lower = summaryText.toLowerCase();
@@ -241,7 +235,7 @@ function BrainPipeline({
 
   // Listen for hw-files-changed -- brief flash on relevant brain nodes
   useEffect(() => {
-    const unlistenPromise = listen<string[]>('hw-files-changed', (event) => {
+    const unlistenPromise = listenBackend('hw-files-changed', (event) => {
       for (const f of event.payload) {
         const nodeId = FILE_TO_BRAIN_NODE[f];
         if (nodeId) {
@@ -353,7 +347,7 @@ function SystemInfrastructure({
 
   // Listen for hw-tool-summary
   useEffect(() => {
-    const unlistenPromise = listen<ToolSummaryPayload>('hw-tool-summary', (event) => {
+    const unlistenPromise = listenBackend('hw-tool-summary', (event) => {
       const payload = event.payload;
       if (payload.events && payload.events.length > 0) {
         setLastTool(payload.events[0].tool.replace(/^hw_/, ''));
@@ -380,7 +374,7 @@ function SystemInfrastructure({
 
   // Listen for hw-files-changed
   useEffect(() => {
-    const unlistenPromise = listen<string[]>('hw-files-changed', (event) => {
+    const unlistenPromise = listenBackend('hw-files-changed', (event) => {
       const fileNodes = event.payload
         .map((f) => FILE_TO_SYS_NODE[f])
         .filter(Boolean);
@@ -678,7 +672,7 @@ export function TerminalView() {
 
     const startSession = async () => {
       // Await listener registration BEFORE spawning PTY — prevents dropped startup events
-      unlistenData = await listen<string>('pty-data', (event) => {
+      unlistenData = await listenBackend('pty-data', (event) => {
         const binary = atob(event.payload);
         const bytes = new Uint8Array(binary.length);
         for (let i = 0; i < binary.length; i++) bytes[i] = binary.charCodeAt(i);
@@ -695,11 +689,13 @@ export function TerminalView() {
         }
       });
 
-      unlistenDied = await listen('pty-died', () => {
+      unlistenDied = await listenBackend('pty-died', () => {
         setStatus('starting');
         // Auto-respawn after brief delay
         setTimeout(() => {
-          invoke('start_pty_session', { projectPath }).catch((e: unknown) => {
+          // Read the store, not the closure — the project may have been switched since mount
+          const current = useAppStore.getState().projectPath;
+          invoke('start_pty_session', { projectPath: current }).catch((e: unknown) => {
             setStatus('error');
             setError(String(e));
           });
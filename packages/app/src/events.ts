@@ -0,0 +1,23 @@
+import { invoke } from '@tauri-apps/api/core';
+import { type EventCallback, listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { CONTRACT_VERSION, type EventName, type EventPayloads } from './bindings/events.js';
+
+export type * from './bindings/events.js';
+
+/** `listen` for an event the Rust backend emits, typed from the generated bindings. */
+export function listenBackend<K extends EventName>(
+  name: K,
+  handler: EventCallback<EventPayloads[K]>,
+): Promise<UnlistenFn> {
+  return listen<EventPayloads[K]>(name, handler);
+}
+
+/** Warn when the running backend emits a different event contract than this UI was built for. */
+export async function checkEventContract(): Promise<void> {
+  const backend = await invoke<number>('get_event_contract_version');
+  if (backend !== CONTRACT_VERSION) {
+    console.warn(
+      `Event contract mismatch: backend v${backend}, UI v${CONTRACT_VERSION}. Rebuild both.`,
+    );
+  }
+}
@@ -1,16 +1,19 @@
 import { invoke } from '@tauri-apps/api/core';
-import { listen } from '@tauri-apps/api/event';
 import { useCallback, useEffect, useRef, useState } from 'react';
+import { listenBackend } from '../events.js';
 
 // Map commands to the JSON files they read
 const COMMAND_FILE_MAP: Record<string, string[]> = {
   get_config: ['config.json'],
   save_config: ['config.json'],
   get_state: ['tasks.json', 'decisions.json', 'questions.json'],
+  get_task_graph: ['tasks.json'],
   get_memories: ['memories.json'],
+  list_memories: ['memories.json'],
   get_sessions: ['sessions.json'],
   get_brain_state: ['brain-state.json'],
   get_activity: ['activity.json'],
+  get_activity_page: ['activity.json'],
   get_approvals: ['approvals.json'],
   get_workflow: ['workflow.json'],
   get_direction: ['direction.json'],
@@ -18,6 +21,17 @@ const COMMAND_FILE_MAP: Record<string, string[]> = {
   get_watchers: ['watchers.json'],
   get_chatroom: ['chatroom.json'],
   get_mode: ['mode.json'],
+  get_project_context: [
+    'config.json',
+    'tasks.json',
+    'decisions.json',
+    'questions.json',
+    'direction.json',
+    'approvals.json',
+    'memories.json',
+    'workflow.json',
+    'restart-handoff.json',
+  ],
 };
 
 // ── Shared polling heartbeat ──────────────────────────────────────
@@ -51,13 +65,13 @@ function subscribePoll(fn: RefetchFn) {
 const pendingRefetches = new Map<string, ReturnType<typeof setTimeout>>();
 const DEBOUNCE_MS = 150;
 
-function debouncedRefetch(command: string, fn: () => void) {
-  const existing = pendingRefetches.get(command);
+function debouncedRefetch(key: string, fn: () => void) {
+  const existing = pendingRefetches.get(key);
   if (existing) clearTimeout(existing);
   pendingRefetches.set(
-    command,
+    key,
     setTimeout(() => {
-      pendingRefetches.delete(command);
+      pendingRefetches.delete(key);
       fn();
     }, DEBOUNCE_MS),
   );
@@ -72,20 +86,27 @@ interface TauriDataState<T> {
   refetch: () => void;
 }
 
-export function useTauriData<T>(command: string, projectPath: string): TauriDataState<T> {
+/** `args` are passed to the command alongside projectPath (e.g. filters). */
+export function useTauriData<T>(
+  command: string,
+  projectPath: string,
+  args?: Record<string, unknown>,
+): TauriDataState<T> {
   const [data, setData] = useState<T | null>(null);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  // Compared by value so callers can pass an inline object
+  const argsKey = JSON.stringify(args ?? {});
 
   const fetchData = useCallback(() => {
     if (!projectPath) return;
     setLoading(true);
     setError(null);
-    invoke<T>(command, { projectPath })
+    invoke<T>(command, { projectPath, ...JSON.parse(argsKey) })
       .then(setData)
       .catch((err) => setError(String(err)))
       .finally(() => setLoading(false));
-  }, [command, projectPath]);
+  }, [command, projectPath, argsKey]);
 
   useEffect(() => {
     fetchData();
@@ -97,12 +118,12 @@ export function useTauriData<T>(command: string, projectPath: string): TauriData
     const relevantFiles = COMMAND_FILE_MAP[command] ?? [];
     if (relevantFiles.length === 0) return;
 
-    const unlisten = listen<string[]>('hw-files-changed', (event) => {
+    const unlisten = listenBackend('hw-files-changed', (event) => {
       const changed = event.payload;
       const shouldRefetch = relevantFiles.some((f) => changed.includes(f));
       if (shouldRefetch) {
-        debouncedRefetch(command, () => {
-          invoke<T>(command, { projectPath })
+        debouncedRefetch(command + argsKey, () => {
+          invoke<T>(command, { projectPath, ...JSON.parse(argsKey) })
             .then(setData)
             .catch((err) => setError(String(err)));
         });
@@ -112,13 +133,13 @@ export function useTauriData<T>(command: string, projectPath: string): TauriData
     return () => {
       unlisten.then((fn) => fn());
     };
-  }, [command, projectPath]);
+  }, [command, projectPath, argsKey]);
 
   // Shared 30s polling fallback — one global timer for all hooks
   const refetchRef = useRef<RefetchFn>(() => {});
   refetchRef.current = () => {
     if (!projectPath) return;
-    invoke<T>(command, { projectPath })
+    invoke<T>(command, { projectPath, ...JSON.parse(argsKey) })
       .then(setData)
       .catch(() => {});
   };
@@ -0,0 +1,57 @@
+import { mkdirSync, mkdtempSync, rmSync, writeFileSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import { join } from 'node:path';
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { ActivityStore } from '../activity.js';
+import { ChatroomStore } from '../chatroom/chatroom-state.js';
+import { readJournal, withJournal } from '../journal.js';
+
+describe('journals', () => {
+  let tmpDir: string;
+  let hwDir: string;
+
+  beforeEach(() => {
+    tmpDir = mkdtempSync(join(tmpdir(), 'hw-journal-'));
+    hwDir = join(tmpDir, '.hello-world');
+    mkdirSync(hwDir);
+  });
+
+  afterEach(() => {
+    rmSync(tmpDir, { recursive: true, force: true });
+  });
+
+  const line = (entry: object) => JSON.stringify(entry) + '\n';
+
+  it('ignores a torn last line', () => {
+    writeFileSync(join(hwDir, 'activity.jsonl'), line({ id: 'act_1' }) + '{"id":"act_to');
+    expect(readJournal(join(hwDir, 'activity.json'))).toEqual([{ id: 'act_1' }]);
+  });
+
+  it('skips entries the snapshot already holds', () => {
+    writeFileSync(join(hwDir, 'activity.jsonl'), line({ id: 'act_1' }) + line({ id: 'act_2' }));
+    const merged = withJournal([{ id: 'act_1' }], join(hwDir, 'activity.json'));
+    expect(merged.map((e) => e.id)).toEqual(['act_1', 'act_2']);
+  });
+
+  it('ActivityStore sees events the app journaled', () => {
+    const store = new ActivityStore(tmpDir);
+    store.append('task_added', 'From the MCP server');
+    writeFileSync(
+      join(hwDir, 'activity.jsonl'),
+      line({ id: 'act_app', type: 'note', description: 'From the app', details: '', timestamp: new Date().toISOString() }),
+    );
+    expect(store.getRecent().map((e) => e.description)).toEqual(['From the app', 'From the MCP server']);
+  });
+
+  it('ChatroomStore only merges messages for the current session', () => {
+    const chatroom = new ChatroomStore(tmpDir);
+    const state = chatroom.startSession('Pricing', [], 'pat', {});
+    const message = (id: string, sessionId: string) =>
+      line({ id, agentId: 'pat', text: id, timestamp: '', type: 'pat', sessionId });
+    writeFileSync(join(hwDir, 'chatroom.jsonl'), message('msg_old', 'chat_gone') + message('msg_new', state.session.id));
+
+    const ids = chatroom.read().messages.map((m) => m.id);
+    expect(ids).toContain('msg_new');
+    expect(ids).not.toContain('msg_old');
+  });
+});
@@ -0,0 +1,72 @@
+import { existsSync, mkdtempSync, readFileSync, rmSync, utimesSync, writeFileSync } from 'node:fs';
+import { tmpdir } from 'node:os';
+import { join } from 'node:path';
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { lockPath, withFileLock } from '../lock.js';
+import { JsonStore } from '../storage.js';
+
+describe('withFileLock', () => {
+  let tmpDir: string;
+  let target: string;
+
+  beforeEach(() => {
+    tmpDir = mkdtempSync(join(tmpdir(), 'hw-lock-'));
+    target = join(tmpDir, 'tasks.json');
+  });
+
+  afterEach(() => {
+    rmSync(tmpDir, { recursive: true, force: true });
+  });
+
+  it('holds the lock only while the callback runs', () => {
+    const seen = withFileLock(target, () => JSON.parse(readFileSync(lockPath(target), 'utf-8')));
+    expect(seen.pid).toBe(process.pid);
+    expect(existsSync(lockPath(target))).toBe(false);
+  });
+
+  it('times out while another holder has the lock', () => {
+    writeFileSync(lockPath(target), JSON.stringify({ pid: 4242, token: 'other' }));
+    expect(() => withFileLock(target, () => 1, 50)).toThrow(/held by pid 4242/);
+    expect(existsSync(lockPath(target))).toBe(true);
+  });
+
+  it('breaks a stale lock', () => {
+    writeFileSync(lockPath(target), JSON.stringify({ pid: 1, token: 'crashed' }));
+    const old = new Date(Date.now() - 60_000);
+    utimesSync(lockPath(target), old, old);
+
+    expect(withFileLock(target, () => 'ok')).toBe('ok');
+    expect(existsSync(lockPath(target))).toBe(false);
+  });
+
+  it('never releases a lock that someone else now holds', () => {
+    withFileLock(target, () => {
+      // Our lock was broken as stale and re-acquired by another writer
+      writeFileSync(lockPath(target), JSON.stringify({ pid: 4242, token: 'other' }));
+    });
+    expect(JSON.parse(readFileSync(lockPath(target), 'utf-8')).token).toBe('other');
+  });
+});
+
+describe('JsonStore.update', () => {
+  let tmpDir: string;
+
+  beforeEach(() => {
+    tmpDir = mkdtempSync(join(tmpdir(), 'hw-lock-'));
+  });
+
+  afterEach(() => {
+    rmSync(tmpDir, { recursive: true, force: true });
+  });
+
+  it('builds on the file on disk, not a stale cached copy', () => {
+    const store = new JsonStore(tmpDir, 'counter.json', { n: 0 });
+    store.write({ n: 1 });
+    // Another process writes behind this store's back
+    const other = new JsonStore(tmpDir, 'counter.json', { n: 0 });
+    other.update((d) => ({ n: d.n + 1 }));
+
+    expect(store.update((d) => ({ n: d.n + 1 })).n).toBe(3);
+    expect(existsSync(lockPath(join(tmpDir, '.hello-world', 'counter.json')))).toBe(false);
+  });
+});
@@ -1,4 +1,7 @@
+import { join } from 'node:path';
+import { withJournal } from './journal.js';
 import { JsonStore } from './storage.js';
+import { HW_DIR } from './types.js';
 import { generateId, now } from './utils.js';
 
 export type ActivityType =
@@ -36,9 +39,11 @@ const MAX_EVENTS = 500;
 
 export class ActivityStore {
   private store: JsonStore<ActivityData>;
+  private readonly filePath: string;
 
   constructor(projectRoot: string) {
     this.store = new JsonStore<ActivityData>(projectRoot, 'activity.json', { activities: [] });
+    this.filePath = join(projectRoot, HW_DIR, 'activity.json');
   }
 
   append(type: ActivityType, description: string, details = ''): ActivityEvent {
@@ -60,11 +65,16 @@ export class ActivityStore {
   }
 
   getRecent(count = 200): ActivityEvent[] {
-    const all = this.store.read().activities;
-    return all.slice(-count).reverse(); // newest first
+    return this.readAll().slice(-count).reverse(); // newest first
   }
 
   getAll(): ActivityEvent[] {
-    return this.store.read().activities.slice().reverse(); // newest first
+    return this.readAll().reverse(); // newest first
+  }
+
+  /** Includes events the desktop app has journaled but not yet compacted. */
+  private readAll(): ActivityEvent[] {
+    this.store.invalidate();
+    return withJournal(this.store.read().activities, this.filePath);
   }
 }
@@ -1,6 +1,7 @@
 import { randomBytes } from 'crypto';
 import { mkdirSync, readFileSync, writeFileSync } from 'fs';
 import { join } from 'path';
+import { withJournal } from '../journal.js';
 import type {
   ChatAgent,
   ChatMessage,
@@ -27,11 +28,20 @@ export class ChatroomStore {
   }
 
   read(): ChatroomState {
+    let state: ChatroomState;
     try {
-      return JSON.parse(readFileSync(this.path, 'utf-8')) as ChatroomState;
+      state = JSON.parse(readFileSync(this.path, 'utf-8')) as ChatroomState;
     } catch {
       return structuredClone(EMPTY_CHATROOM);
     }
+    // Pat's messages from the desktop app are journaled, tagged with the
+    // session they were posted to
+    state.messages = withJournal<ChatMessage & { sessionId?: string }>(
+      state.messages,
+      this.path,
+      (m) => m.sessionId === state.session.id,
+    );
+    return state;
   }
 
   write(state: ChatroomState): void {
@@ -0,0 +1,48 @@
+import { readFileSync } from 'node:fs';
+
+/**
+ * Reader for the append-only journals the desktop app keeps next to some
+ * documents (`activity.jsonl`, `chatroom.jsonl`; see journal.rs in
+ * packages/app/src-tauri). Each line is one event; the app folds them into
+ * the `.json` snapshot from time to time, so until then a reader must apply
+ * them itself to see everything.
+ *
+ * A torn last line (crash mid-append) is ignored, and so are entries already
+ * in the snapshot: a crash during compaction can leave an event in both.
+ */
+export function readJournal<E>(docPath: string): E[] {
+  let contents: string;
+  try {
+    contents = readFileSync(docPath.replace(/\.json$/, '.jsonl'), 'utf-8');
+  } catch {
+    return [];
+  }
+  const complete = contents.slice(0, contents.lastIndexOf('\n') + 1);
+  const entries: E[] = [];
+  for (const line of complete.split('\n')) {
+    if (!line.trim()) continue;
+    try {
+      entries.push(JSON.parse(line) as E);
+    } catch {
+      /* skip a damaged line rather than lose the rest */
+    }
+  }
+  return entries;
+}
+
+/** `snapshot` followed by the journal entries it doesn't already hold. */
+export function withJournal<E extends { id: string }>(
+  snapshot: E[],
+  docPath: string,
+  accepts: (entry: E) => boolean = () => true,
+): E[] {
+  const seen = new Set(snapshot.map((e) => e.id));
+  const merged = snapshot.slice();
+  for (const entry of readJournal<E>(docPath)) {
+    if (accepts(entry) && !seen.has(entry.id)) {
+      seen.add(entry.id);
+      merged.push(entry);
+    }
+  }
+  return merged;
+}
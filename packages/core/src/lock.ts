@@ -0,0 +1,143 @@
+import {
+  closeSync,
+  linkSync,
+  openSync,
+  readFileSync,
+  renameSync,
+  statSync,
+  unlinkSync,
+  writeSync,
+} from 'node:fs';
+import { randomBytes } from 'node:crypto';
+
+/**
+ * Cross-process advisory lock for .hello-world files.
+ *
+ * Same protocol as packages/app/src-tauri/src/lock.rs, so the MCP server and
+ * the desktop app never interleave a read-modify-write of the same file:
+ * - Exclusively create `<file>.lock` holding `{ pid, token, acquiredAt }`.
+ * - Delete it when done. Holders must finish well within STALE_AFTER_MS.
+ * - A lock older than STALE_AFTER_MS belongs to a crashed writer. It is broken
+ *   by renaming it to a unique name and re-checking the renamed file, never by
+ *   deleting `<file>.lock` directly (that could delete a fresh lock).
+ */
+export const LOCK_TIMEOUT_MS = 5000;
+export const STALE_AFTER_MS = 30_000;
+
+const POLL_MS = 10;
+
+export function lockPath(target: string): string {
+  return target + '.lock';
+}
+
+export function withFileLock<R>(
+  target: string,
+  fn: () => R,
+  timeoutMs = LOCK_TIMEOUT_MS,
+  staleAfterMs = STALE_AFTER_MS,
+): R {
+  const token = acquire(target, timeoutMs, staleAfterMs);
+  try {
+    return fn();
+  } finally {
+    release(lockPath(target), token);
+  }
+}
+
+function acquire(target: string, timeoutMs: number, staleAfterMs: number): string {
+  const path = lockPath(target);
+  const token = `${process.pid}-${randomBytes(8).toString('hex')}`;
+  const deadline = Date.now() + timeoutMs;
+
+  for (;;) {
+    try {
+      const fd = openSync(path, 'wx');
+      try {
+        writeSync(fd, JSON.stringify({ pid: process.pid, token, acquiredAt: new Date().toISOString() }));
+      } finally {
+        closeSync(fd);
+      }
+      return token;
+    } catch (err: unknown) {
+      if ((err as NodeJS.ErrnoException).code !== 'EEXIST') throw err;
+    }
+
+    if (isStale(path, staleAfterMs)) {
+      // Crashed holder -- take the lock file aside, then make sure what we
+      // took is the stale one and not a lock someone acquired meanwhile
+      const claimed = claim(path, token);
+      if (claimed) {
+        if (isStale(claimed, staleAfterMs)) removeQuietly(claimed);
+        else restore(claimed, path);
+      }
+      continue;
+    }
+
+    if (Date.now() >= deadline) {
+      const holder = readLock(path)?.pid;
+      throw new Error(
+        `Timed out after ${timeoutMs}ms waiting for lock on ${target}` +
+          (holder ? ` (held by pid ${holder})` : ''),
+      );
+    }
+    sleepSync(POLL_MS);
+  }
+}
+
+function release(path: string, token: string): void {
+  // Take the lock file aside before checking it, so a lock that was broken
+  // as stale and re-acquired by someone else is never deleted
+  const claimed = claim(path, token);
+  if (!claimed) return;
+  if (readLock(claimed)?.token === token) removeQuietly(claimed);
+  else restore(claimed, path);
+}
+
+/** Atomically move the lock to a name only this caller knows. */
+function claim(path: string, token: string): string | null {
+  const claimed = `${path}.${token}`;
+  try {
+    renameSync(path, claimed);
+    return claimed;
+  } catch {
+    return null;
+  }
+}
+
+/** Put back a lock we shouldn't have taken, unless the slot was refilled. */
+function restore(claimed: string, path: string): void {
+  try {
+    linkSync(claimed, path);
+  } catch {
+    /* a newer lock holds the slot */
+  }
+  removeQuietly(claimed);
+}
+
+function isStale(path: string, staleAfterMs: number): boolean {
+  try {
+    return Date.now() - statSync(path).mtimeMs > staleAfterMs;
+  } catch {
+    return false;
+  }
+}
+
+function readLock(path: string): { pid?: number; token?: string } | null {
+  try {
+    return JSON.parse(readFileSync(path, 'utf-8'));
+  } catch {
+    return null;
+  }
+}
+
+function removeQuietly(path: string): void {
+  try {
+    unlinkSync(path);
+  } catch {
+    /* already gone */
+  }
+}
+
+function sleepSync(ms: number): void {
+  Atomics.wait(new Int32Array(new SharedArrayBuffer(4)), 0, 0, ms);
+}
@@ -87,10 +87,10 @@ const text = (t: string) => ({ content: [{ type: 'text' as const, text: t }] });
 
 const SYNC_FILE = join(projectRoot, '.hello-world', 'sync.json');
 
-function readSyncPort(): { port: number; pid: number } | null {
+function readSyncPort(): { port: number; pid: number; token: string } | null {
   try {
     const raw = JSON.parse(readFileSync(SYNC_FILE, 'utf-8'));
-    if (typeof raw.port === 'number' && typeof raw.pid === 'number') return raw;
+    if (typeof raw.port === 'number' && typeof raw.pid === 'number' && typeof raw.token === 'string') return raw;
   } catch { /* app not running */ }
   return null;
 }
@@ -182,7 +182,7 @@ function scheduleNotify(tool: string, args: Record<string, unknown>): void {
     try {
       await fetch(`http://127.0.0.1:${sync.port}/notify`, {
         method: 'POST',
-        headers: { 'Content-Type': 'application/json' },
+        headers: { 'Content-Type': 'application/json', Authorization: `Bearer ${sync.token}` },
         body,
       });
     } catch { /* app not running */ }
@@ -8,6 +8,7 @@ import {
   writeFileSync,
 } from 'node:fs';
 import { join } from 'node:path';
+import { withFileLock } from './lock.js';
 import { HW_DIR } from './types.js';
 
 /**
@@ -18,6 +19,10 @@ import { HW_DIR } from './types.js';
  * 2. Copy current file to .backup
  * 3. Rename .tmp to target (with Windows NTFS retry loop)
  * 4. On read failure, fall back to .backup
+ *
+ * `update` holds `<file>.lock` (see lock.ts) across the read-modify-write and
+ * re-reads from disk first, so it never builds on a stale cached copy or
+ * interleaves with the desktop app writing the same file.
  */
 export class JsonStore<T> {
   private data: T | null = null;
@@ -63,8 +68,7 @@ export class JsonStore<T> {
 
   write(data: T): void {
     this.data = data;
-    const dir = join(this.projectRoot, HW_DIR);
-    if (!existsSync(dir)) mkdirSync(dir, { recursive: true });
+    this.ensureDir();
 
     const tmpPath = this.filePath + '.tmp';
     const backupPath = this.filePath + '.backup';
@@ -95,16 +99,24 @@ export class JsonStore<T> {
   }
 
   update(fn: (data: T) => T): T {
-    const current = this.read();
-    const updated = fn(current);
-    this.write(updated);
-    return updated;
+    this.ensureDir();
+    return withFileLock(this.filePath, () => {
+      this.invalidate();
+      const updated = fn(this.read());
+      this.write(updated);
+      return updated;
+    });
   }
 
   invalidate(): void {
     this.data = null;
   }
 
+  private ensureDir(): void {
+    const dir = join(this.projectRoot, HW_DIR);
+    if (!existsSync(dir)) mkdirSync(dir, { recursive: true });
+  }
+
   /** Rename with retry loop for Windows NTFS (file may be held by watcher) */
   private atomicRename(src: string, dest: string): boolean {
     const MAX_RETRIES = 5;
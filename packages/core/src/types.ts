@@ -16,6 +16,18 @@ export const ProjectConfigSchema = z.object({
   dailyBudgetUsd: z.number().default(5.0),
   createdAt: z.string().datetime(),
   updatedAt: z.string().datetime(),
+  // .hello-world/ layout version, upgraded by the desktop app (migrate.rs)
+  schemaVersion: z.number().int().optional(),
+  // Session briefing built by the desktop app (context.rs)
+  context: z
+    .object({
+      tokenBudget: z.number().int().positive().optional(),
+      truncation: z.enum(['priority', 'proportional']).optional(),
+    })
+    .passthrough()
+    .optional(),
+  // Where the desktop app reads collections from (sqlite.rs); JSON is always kept current
+  storage: z.enum(['json', 'sqlite']).optional(),
 });
 
 export type ProjectConfig = z.infer<typeof ProjectConfigSchema>;
@@ -72,13 +84,19 @@ export const DecisionSchema = z.object({
   rationale: z.string(),
   decidedAt: z.string().datetime(),
   decidedBy: z.enum(['pat', 'claude', 'both']),
+  // Written by the desktop app's decision log (decisions.rs)
+  tags: z.array(z.string()).optional(),
+  supersedes: z.string().optional(),
+  supersededBy: z.string().optional(),
+  supersededAt: z.string().datetime().optional(),
+  history: z.array(z.record(z.unknown())).optional(),
 });
 
 export type Decision = z.infer<typeof DecisionSchema>;
 
 // ── Known Unknowns ──────────────────────────────────────────────
 
-export const QuestionStatus = z.enum(['open', 'answered', 'deferred']);
+export const QuestionStatus = z.enum(['open', 'answered', 'deferred', 'dismissed']);
 export type QuestionStatus = z.infer<typeof QuestionStatus>;
 
 export const QuestionSchema = z.object({
@@ -88,6 +106,10 @@ export const QuestionSchema = z.object({
   status: QuestionStatus.default('open'),
   answer: z.string().optional(),
   answeredAt: z.string().datetime().optional(),
+  // Written by the desktop app (questions.rs)
+  answeredBy: z.enum(['pat', 'claude', 'both']).optional(),
+  answerHistory: z.array(z.record(z.unknown())).optional(),
+  dismissedReason: z.string().optional(),
   createdAt: z.string().datetime(),
   linkedTaskId: z.string().optional(),
   linkedDecisionId: z.string().optional(),
@@ -125,6 +147,7 @@ export const MemorySchema = z.object({
   supersededBy: z.string().optional(),
   qualityScore: z.number().min(0).max(1).optional(),
   fingerprint: z.string().optional(),
+  lastDecayedAt: z.string().datetime().optional(),
   // Reflection fields (only used when type='reflection')
   relatedTaskId: z.string().optional(),
   surfacedMemoryIds: z.array(z.string()).optional(),